@@ -0,0 +1,14 @@
+PRAGMA foreign_keys = ON;
+
+-- Token usage reported by coding agents, accumulated per execution process
+CREATE TABLE execution_process_token_usage (
+    execution_process_id        BLOB PRIMARY KEY,
+    model                       TEXT,
+    input_tokens                INTEGER NOT NULL DEFAULT 0,
+    output_tokens               INTEGER NOT NULL DEFAULT 0,
+    cache_creation_input_tokens INTEGER NOT NULL DEFAULT 0,
+    cache_read_input_tokens     INTEGER NOT NULL DEFAULT 0,
+    created_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
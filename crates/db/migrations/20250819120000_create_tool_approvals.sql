@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- Tool uses an agent asked permission for, answered by a human from the board
+CREATE TABLE tool_approvals (
+    id                   BLOB PRIMARY KEY,
+    execution_process_id BLOB NOT NULL,
+    tool_name            TEXT NOT NULL,
+    tool_input           TEXT NOT NULL DEFAULT '{}', -- JSON
+    status               TEXT NOT NULL DEFAULT 'pending'
+                            CHECK (status IN ('pending','approved','denied')),
+    reason               TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    responded_at         TEXT,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_tool_approvals_execution_process_id ON tool_approvals(execution_process_id);
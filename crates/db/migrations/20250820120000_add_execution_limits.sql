@@ -0,0 +1,8 @@
+-- Per-project limits for coding agent executions, NULL means unlimited
+ALTER TABLE projects ADD COLUMN max_duration_secs INTEGER;
+ALTER TABLE projects ADD COLUMN max_idle_secs INTEGER;
+ALTER TABLE projects ADD COLUMN max_tool_calls INTEGER;
+
+-- Why an execution process was stopped by vibe-kanban rather than exiting on its own
+ALTER TABLE execution_processes ADD COLUMN stop_reason TEXT
+    CHECK (stop_reason IN ('max_duration','max_idle','max_tool_calls'));
@@ -0,0 +1,3 @@
+-- Links an attempt started automatically after a failure to the attempt it replaces
+ALTER TABLE task_attempts ADD COLUMN fallback_from_attempt_id BLOB
+    REFERENCES task_attempts(id) ON DELETE SET NULL;
@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Plans presented by plan-mode coding agents, approved or rejected by a human from the board
+CREATE TABLE plan_reviews (
+    id                   BLOB PRIMARY KEY,
+    execution_process_id BLOB NOT NULL,
+    plan                 TEXT NOT NULL,
+    status               TEXT NOT NULL DEFAULT 'pending'
+                            CHECK (status IN ('pending','approved','rejected')),
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    responded_at         TEXT,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_plan_reviews_execution_process_id ON plan_reviews(execution_process_id);
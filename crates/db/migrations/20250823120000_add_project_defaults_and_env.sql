@@ -0,0 +1,5 @@
+-- Profile variant new attempts in the project start with, as JSON; NULL uses the global default
+ALTER TABLE projects ADD COLUMN default_profile TEXT;
+
+-- Environment variables for every process spawned for the project's attempts, as a JSON object
+ALTER TABLE projects ADD COLUMN env_vars TEXT NOT NULL DEFAULT '{}';
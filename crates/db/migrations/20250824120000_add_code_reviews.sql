@@ -0,0 +1,43 @@
+PRAGMA foreign_keys = ON;
+
+-- 1. Add the replacement column with 'codereview' allowed
+ALTER TABLE execution_processes
+  ADD COLUMN run_reason_new TEXT NOT NULL DEFAULT 'setupscript'
+    CHECK (run_reason_new IN ('setupscript',
+                              'cleanupscript',
+                              'codingagent',
+                              'devserver',
+                              'codereview'));
+
+-- 2. Copy existing values across
+UPDATE execution_processes
+  SET run_reason_new = run_reason;
+
+-- 3. Drop the index on the old column, then the column itself
+DROP INDEX IF EXISTS idx_execution_processes_type;
+ALTER TABLE execution_processes DROP COLUMN run_reason;
+
+-- 4. Rename the new column back and re-create the index
+ALTER TABLE execution_processes
+  RENAME COLUMN run_reason_new TO run_reason;
+CREATE INDEX idx_execution_processes_type
+        ON execution_processes(run_reason);
+
+-- Profile variant that reviews an attempt's changes whenever its coding agent finishes, as
+-- JSON; NULL disables reviews
+ALTER TABLE projects ADD COLUMN review_profile TEXT;
+
+-- Findings of review runs, optionally pointing at a line of a changed file
+CREATE TABLE review_comments (
+    id                   BLOB PRIMARY KEY,
+    execution_process_id BLOB NOT NULL,
+    file_path            TEXT,
+    line                 INTEGER,
+    severity             TEXT NOT NULL DEFAULT 'info'
+                            CHECK (severity IN ('info','warning','error')),
+    body                 TEXT NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_review_comments_execution_process_id ON review_comments(execution_process_id);
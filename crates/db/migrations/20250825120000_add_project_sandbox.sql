@@ -0,0 +1,6 @@
+-- Run the project's coding agents and scripts in a Linux sandbox where only the worktree,
+-- the agent's config and a temp dir are writable
+ALTER TABLE projects ADD COLUMN sandboxed BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Whether sandboxed processes keep network access
+ALTER TABLE projects ADD COLUMN sandbox_network BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,4 @@
+-- Resource limits for every process spawned for the project's attempts; NULL is unlimited
+ALTER TABLE projects ADD COLUMN max_memory_mb INTEGER;
+ALTER TABLE projects ADD COLUMN max_cpu_secs INTEGER;
+ALTER TABLE projects ADD COLUMN max_processes INTEGER;
@@ -0,0 +1,30 @@
+PRAGMA foreign_keys = ON;
+
+-- 1. Add the replacement column with 'queued' allowed
+ALTER TABLE execution_processes
+  ADD COLUMN status_new TEXT NOT NULL DEFAULT 'running'
+    CHECK (status_new IN ('queued',
+                          'running',
+                          'completed',
+                          'failed',
+                          'killed'));
+
+-- 2. Copy existing values across
+UPDATE execution_processes
+  SET status_new = status;
+
+-- 3. Drop the index on the old column, then the column itself
+DROP INDEX IF EXISTS idx_execution_processes_status;
+ALTER TABLE execution_processes DROP COLUMN status;
+
+-- 4. Rename the new column back and re-create the index
+ALTER TABLE execution_processes
+  RENAME COLUMN status_new TO status;
+CREATE INDEX idx_execution_processes_status
+        ON execution_processes(status);
+
+-- 1-based place of a queued process in the queue; NULL once it has started
+ALTER TABLE execution_processes ADD COLUMN queue_position INTEGER;
+
+-- Coding agents of the project's attempts that may run at once; NULL is unlimited
+ALTER TABLE projects ADD COLUMN max_concurrent_agents INTEGER;
@@ -0,0 +1,16 @@
+-- A task is blocked by other tasks of its project until they are all done
+CREATE TABLE task_dependencies (
+    task_id            BLOB NOT NULL,
+    blocked_by_task_id BLOB NOT NULL,
+    created_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, blocked_by_task_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (blocked_by_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    CHECK (task_id != blocked_by_task_id)
+);
+
+CREATE INDEX idx_task_dependencies_blocked_by_task_id ON task_dependencies(blocked_by_task_id);
+
+-- Profile variant an attempt is started with once all of the task's blockers are done, as
+-- JSON; NULL leaves the task for the user to start
+ALTER TABLE tasks ADD COLUMN auto_start_profile TEXT;
@@ -0,0 +1,31 @@
+PRAGMA foreign_keys = ON;
+
+-- 1. Add the replacement column with 'testscript' allowed
+ALTER TABLE execution_processes
+  ADD COLUMN run_reason_new TEXT NOT NULL DEFAULT 'setupscript'
+    CHECK (run_reason_new IN ('setupscript',
+                              'cleanupscript',
+                              'codingagent',
+                              'devserver',
+                              'codereview',
+                              'testscript'));
+
+-- 2. Copy existing values across
+UPDATE execution_processes
+  SET run_reason_new = run_reason;
+
+-- 3. Drop the index on the old column, then the column itself
+DROP INDEX IF EXISTS idx_execution_processes_type;
+ALTER TABLE execution_processes DROP COLUMN run_reason;
+
+-- 4. Rename the new column back and re-create the index
+ALTER TABLE execution_processes
+  RENAME COLUMN run_reason_new TO run_reason;
+CREATE INDEX idx_execution_processes_type
+        ON execution_processes(run_reason);
+
+-- Script run after each coding agent run; the agent is asked to fix it when it fails
+ALTER TABLE projects ADD COLUMN test_script TEXT;
+
+-- Fix follow-ups in a row before giving up; NULL uses the default
+ALTER TABLE projects ADD COLUMN max_fix_iterations INTEGER;
@@ -0,0 +1,29 @@
+PRAGMA foreign_keys = ON;
+
+-- 1. Add the replacement column with 'pipelinestep' allowed
+ALTER TABLE execution_processes
+  ADD COLUMN run_reason_new TEXT NOT NULL DEFAULT 'setupscript'
+    CHECK (run_reason_new IN ('setupscript',
+                              'cleanupscript',
+                              'codingagent',
+                              'devserver',
+                              'codereview',
+                              'testscript',
+                              'pipelinestep'));
+
+-- 2. Copy existing values across
+UPDATE execution_processes
+  SET run_reason_new = run_reason;
+
+-- 3. Drop the index on the old column, then the column itself
+DROP INDEX IF EXISTS idx_execution_processes_type;
+ALTER TABLE execution_processes DROP COLUMN run_reason;
+
+-- 4. Rename the new column back and re-create the index
+ALTER TABLE execution_processes
+  RENAME COLUMN run_reason_new TO run_reason;
+CREATE INDEX idx_execution_processes_type
+        ON execution_processes(run_reason);
+
+-- Ordered steps (JSON) run after each coding agent run, before the test script
+ALTER TABLE projects ADD COLUMN pipeline TEXT NOT NULL DEFAULT '[]';
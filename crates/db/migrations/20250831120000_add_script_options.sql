@@ -0,0 +1,8 @@
+-- Language (JSON) the setup, cleanup and dev scripts are written in; NULL is bash
+ALTER TABLE projects ADD COLUMN script_language TEXT;
+
+-- Directory the scripts run in, relative to the worktree root; NULL is the root
+ALTER TABLE projects ADD COLUMN script_working_dir TEXT;
+
+-- Seconds the setup and cleanup scripts may run for; NULL is unlimited
+ALTER TABLE projects ADD COLUMN script_timeout_secs INTEGER;
@@ -0,0 +1,2 @@
+-- URL a dev server reported it's listening on, detected from its output
+ALTER TABLE execution_processes ADD COLUMN dev_server_url TEXT;
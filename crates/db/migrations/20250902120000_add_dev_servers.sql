@@ -0,0 +1,11 @@
+-- Named dev servers (JSON) replace the single dev script
+ALTER TABLE projects ADD COLUMN dev_servers TEXT NOT NULL DEFAULT '[]';
+
+UPDATE projects
+SET dev_servers = json_array(json_object('name', 'dev', 'script', dev_script, 'health_check', NULL))
+WHERE dev_script IS NOT NULL AND trim(dev_script) != '';
+
+ALTER TABLE projects DROP COLUMN dev_script;
+
+-- Health of a running dev server, from its health check
+ALTER TABLE execution_processes ADD COLUMN dev_server_health TEXT;
@@ -0,0 +1,35 @@
+PRAGMA foreign_keys = ON;
+
+-- Logs are appended as chunks of JSONL lines instead of rewriting one growing row, so a
+-- range of lines can be read without loading the whole log
+CREATE TABLE execution_process_log_chunks (
+    execution_id      BLOB NOT NULL,
+    seq               INTEGER NOT NULL,
+    byte_offset       INTEGER NOT NULL,   -- bytes of the log before this chunk
+    byte_size         INTEGER NOT NULL,
+    line_offset       INTEGER NOT NULL,   -- lines of the log before this chunk
+    line_count        INTEGER NOT NULL,
+    logs              TEXT NOT NULL,      -- JSONL format (one LogMsg per line)
+    inserted_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (execution_id, seq),
+    FOREIGN KEY (execution_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_execution_process_log_chunks_line_offset
+    ON execution_process_log_chunks(execution_id, line_offset);
+
+-- Existing logs become the first chunk of their execution
+INSERT INTO execution_process_log_chunks
+    (execution_id, seq, byte_offset, byte_size, line_offset, line_count, logs, inserted_at)
+SELECT execution_id,
+       0,
+       0,
+       byte_size,
+       0,
+       length(logs) - length(replace(logs, char(10), '')),
+       logs,
+       inserted_at
+FROM execution_process_logs
+WHERE logs != '';
+
+DROP TABLE execution_process_logs;
@@ -0,0 +1,20 @@
+PRAGMA foreign_keys = ON;
+
+-- 1. Add the replacement column with the resource limits allowed
+ALTER TABLE execution_processes
+  ADD COLUMN stop_reason_new TEXT
+    CHECK (stop_reason_new IN ('max_duration',
+                               'max_idle',
+                               'max_tool_calls',
+                               'max_memory',
+                               'max_cpu',
+                               'max_processes'));
+
+-- 2. Copy existing values across
+UPDATE execution_processes
+  SET stop_reason_new = stop_reason;
+
+-- 3. Drop the old column and rename the new one back
+ALTER TABLE execution_processes DROP COLUMN stop_reason;
+ALTER TABLE execution_processes
+  RENAME COLUMN stop_reason_new TO stop_reason;
@@ -0,0 +1,11 @@
+PRAGMA foreign_keys = ON;
+
+-- The normalized conversation of a finished coding agent run, stored the first time it is
+-- read, so windows of it are read from here instead of normalizing the raw logs again
+CREATE TABLE execution_process_normalized_entries (
+    execution_id  BLOB NOT NULL,
+    entry_index   INTEGER NOT NULL,
+    entry         TEXT NOT NULL,      -- JSON (NormalizedEntry)
+    PRIMARY KEY (execution_id, entry_index),
+    FOREIGN KEY (execution_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Sandboxed processes no longer get network access unless the project allows it. Projects
+-- that aren't sandboxed only had it by default, so they start out without it once they are.
+UPDATE projects SET sandbox_network = FALSE WHERE sandboxed = FALSE;
@@ -12,6 +12,8 @@ use super::{task::Task, task_attempt::TaskAttempt};
 #[sqlx(type_name = "execution_process_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum ExecutionProcessStatus {
+    /// Waiting for a free coding agent slot
+    Queued,
     Running,
     Completed,
     Failed,
@@ -26,6 +28,42 @@ pub enum ExecutionProcessRunReason {
     CleanupScript,
     CodingAgent,
     DevServer,
+    CodeReview,
+    TestScript,
+    PipelineStep,
+}
+
+impl ExecutionProcessRunReason {
+    /// Whether the process runs a coding agent, and so counts towards the concurrency limits
+    pub fn is_coding_agent(&self) -> bool {
+        matches!(self, Self::CodingAgent | Self::CodeReview)
+    }
+}
+
+/// Why vibe-kanban stopped a process that hit one of its execution limits
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(type_name = "execution_process_stop_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionProcessStopReason {
+    MaxDuration,
+    MaxIdle,
+    MaxToolCalls,
+    /// Resource limits enforced by vibe-kanban rather than the kernel
+    MaxMemory,
+    MaxCpu,
+    MaxProcesses,
+}
+
+/// Whether a running dev server passes its health check
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(type_name = "dev_server_health", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum DevServerHealth {
+    /// Not yet passed its health check
+    Starting,
+    Healthy,
+    /// Failing a health check it passed before
+    Unhealthy,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
@@ -37,8 +75,15 @@ pub struct ExecutionProcess {
     pub executor_action: sqlx::types::Json<ExecutorActionField>,
     pub status: ExecutionProcessStatus,
     pub exit_code: Option<i64>,
+    pub stop_reason: Option<ExecutionProcessStopReason>,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    /// 1-based place in the queue while the process is queued
+    #[ts(type = "number | null")]
+    pub queue_position: Option<i64>,
+    /// URL a dev server reported it's listening on
+    pub dev_server_url: Option<String>,
+    pub dev_server_health: Option<DevServerHealth>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -84,8 +129,12 @@ impl ExecutionProcess {
                 executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                 status as "status!: ExecutionProcessStatus",
                 exit_code,
+                stop_reason as "stop_reason?: ExecutionProcessStopReason",
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                queue_position,
+                dev_server_url,
+                dev_server_health as "dev_server_health?: DevServerHealth",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -107,8 +156,12 @@ impl ExecutionProcess {
                 executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                 status as "status!: ExecutionProcessStatus",
                 exit_code,
+                stop_reason as "stop_reason?: ExecutionProcessStopReason",
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                queue_position,
+                dev_server_url,
+                dev_server_health as "dev_server_health?: DevServerHealth",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -133,8 +186,12 @@ impl ExecutionProcess {
                 executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                 status as "status!: ExecutionProcessStatus",
                 exit_code,
+                stop_reason as "stop_reason?: ExecutionProcessStopReason",
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                queue_position,
+                dev_server_url,
+                dev_server_health as "dev_server_health?: DevServerHealth",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -157,8 +214,12 @@ impl ExecutionProcess {
                 executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                 status as "status!: ExecutionProcessStatus",
                 exit_code,
+                stop_reason as "stop_reason?: ExecutionProcessStopReason",
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                queue_position,
+                dev_server_url,
+                dev_server_health as "dev_server_health?: DevServerHealth",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -169,7 +230,7 @@ impl ExecutionProcess {
         .await
     }
 
-    /// Find running dev servers for a specific project
+    /// Find running dev servers for a specific project, with the health of each
     pub async fn find_running_dev_servers_by_project(
         pool: &SqlitePool,
         project_id: Uuid,
@@ -183,8 +244,12 @@ impl ExecutionProcess {
                 ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                 ep.status as "status!: ExecutionProcessStatus",
                 ep.exit_code,
+                ep.stop_reason as "stop_reason?: ExecutionProcessStopReason",
                 ep.started_at as "started_at!: DateTime<Utc>",
                 ep.completed_at as "completed_at?: DateTime<Utc>",
+                ep.queue_position,
+                ep.dev_server_url,
+                ep.dev_server_health as "dev_server_health?: DevServerHealth",
                 ep.created_at as "created_at!: DateTime<Utc>", 
                 ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
@@ -200,6 +265,30 @@ impl ExecutionProcess {
         .await
     }
 
+    /// The URL of the task attempt's running dev server `name`, once it has reported one
+    pub async fn find_running_dev_server_url(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        name: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT dev_server_url as "dev_server_url!: String"
+               FROM execution_processes
+               WHERE task_attempt_id = $1
+               AND run_reason = 'devserver'
+               AND status = 'running'
+               AND dev_server_url IS NOT NULL
+               AND json_extract(executor_action, '$.dev_server.name') = $2
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            task_attempt_id,
+            name
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| r.dev_server_url))
+    }
+
     /// Find latest session_id by task attempt (simple scalar query)
     pub async fn find_latest_session_id_by_task_attempt(
         pool: &SqlitePool,
@@ -243,8 +332,12 @@ impl ExecutionProcess {
                 executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                 status as "status!: ExecutionProcessStatus",
                 exit_code,
+                stop_reason as "stop_reason?: ExecutionProcessStopReason",
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                queue_position,
+                dev_server_url,
+                dev_server_health as "dev_server_health?: DevServerHealth",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -259,11 +352,190 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Find the running coding agent process of the task attempt living in `container_ref`
+    pub async fn find_running_coding_agent_by_container_ref(
+        pool: &SqlitePool,
+        container_ref: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT
+                ep.id as "id!: Uuid",
+                ep.task_attempt_id as "task_attempt_id!: Uuid",
+                ep.run_reason as "run_reason!: ExecutionProcessRunReason",
+                ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                ep.status as "status!: ExecutionProcessStatus",
+                ep.exit_code,
+                ep.stop_reason as "stop_reason?: ExecutionProcessStopReason",
+                ep.started_at as "started_at!: DateTime<Utc>",
+                ep.completed_at as "completed_at?: DateTime<Utc>",
+                ep.queue_position,
+                ep.dev_server_url,
+                ep.dev_server_health as "dev_server_health?: DevServerHealth",
+                ep.created_at as "created_at!: DateTime<Utc>",
+                ep.updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ta.id = ep.task_attempt_id
+               WHERE ta.container_ref = $1
+               AND ep.run_reason = 'codingagent'
+               AND ep.status = 'running'
+               ORDER BY ep.created_at DESC
+               LIMIT 1"#,
+            container_ref
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Find queued execution processes, oldest first
+    pub async fn find_queued(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT
+                id as "id!: Uuid",
+                task_attempt_id as "task_attempt_id!: Uuid",
+                run_reason as "run_reason!: ExecutionProcessRunReason",
+                executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                status as "status!: ExecutionProcessStatus",
+                exit_code,
+                stop_reason as "stop_reason?: ExecutionProcessStopReason",
+                started_at as "started_at!: DateTime<Utc>",
+                completed_at as "completed_at?: DateTime<Utc>",
+                queue_position,
+                dev_server_url,
+                dev_server_health as "dev_server_health?: DevServerHealth",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes
+               WHERE status = 'queued'
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Count the coding agents (including reviews) running across all projects
+    pub async fn count_running_agents(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM execution_processes
+               WHERE status = 'running'
+               AND run_reason IN ('codingagent', 'codereview')"#
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(result.count)
+    }
+
+    /// Count the coding agents (including reviews) running for a specific project
+    pub async fn count_running_agents_by_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ep.task_attempt_id = ta.id
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE ep.status = 'running'
+               AND ep.run_reason IN ('codingagent', 'codereview')
+               AND t.project_id = $1"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(result.count)
+    }
+
+    /// Move a queued process to running, restarting its clock. Returns false if it was no
+    /// longer queued, e.g. because it was stopped in the meantime.
+    pub async fn mark_started(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET status = 'running', started_at = $1, queue_position = NULL
+               WHERE id = $2 AND status = 'queued'"#,
+            now,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark a queued process killed before it started. Returns false if it was no longer
+    /// queued.
+    pub async fn cancel_queued(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET status = 'killed', completed_at = $1, queue_position = NULL
+               WHERE id = $2 AND status = 'queued'"#,
+            now,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record the URL a dev server reported it's listening on
+    pub async fn set_dev_server_url(
+        pool: &SqlitePool,
+        id: Uuid,
+        dev_server_url: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE execution_processes SET dev_server_url = $1 WHERE id = $2",
+            dev_server_url,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the outcome of a dev server's health check
+    pub async fn set_dev_server_health(
+        pool: &SqlitePool,
+        id: Uuid,
+        health: DevServerHealth,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE execution_processes SET dev_server_health = $1 WHERE id = $2",
+            health,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Set the queue position of a queued process, leaving the row untouched if it is
+    /// unchanged so no needless update events are sent
+    pub async fn set_queue_position(
+        pool: &SqlitePool,
+        id: Uuid,
+        queue_position: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET queue_position = $1
+               WHERE id = $2 AND status = 'queued' AND queue_position IS NOT $1"#,
+            queue_position,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     /// Create a new execution process
     pub async fn create(
         pool: &SqlitePool,
         data: &CreateExecutionProcess,
         process_id: Uuid,
+        status: ExecutionProcessStatus,
     ) -> Result<Self, sqlx::Error> {
         let now = Utc::now();
         let executor_action_json = sqlx::types::Json(&data.executor_action);
@@ -283,15 +555,19 @@ impl ExecutionProcess {
                 executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                 status as "status!: ExecutionProcessStatus",
                 exit_code,
+                stop_reason as "stop_reason?: ExecutionProcessStopReason",
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                queue_position,
+                dev_server_url,
+                dev_server_health as "dev_server_health?: DevServerHealth",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>""#,
             process_id,
             data.task_attempt_id,
             data.run_reason,
             executor_action_json,
-            ExecutionProcessStatus::Running,
+            status,
             None::<i64>,           // exit_code
             now,                   // started_at
             None::<DateTime<Utc>>, // completed_at
@@ -325,7 +601,7 @@ impl ExecutionProcess {
 
         sqlx::query!(
             r#"UPDATE execution_processes 
-               SET status = $1, exit_code = $2, completed_at = $3
+               SET status = $1, exit_code = $2, completed_at = $3, queue_position = NULL
                WHERE id = $4"#,
             status,
             exit_code,
@@ -338,6 +614,23 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    /// Record why a process is being stopped, before it is killed
+    pub async fn set_stop_reason(
+        pool: &SqlitePool,
+        id: Uuid,
+        stop_reason: ExecutionProcessStopReason,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes SET stop_reason = $1 WHERE id = $2"#,
+            stop_reason,
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn delete_by_task_attempt_id(
         pool: &SqlitePool,
         task_attempt_id: Uuid,
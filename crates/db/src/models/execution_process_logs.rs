@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
@@ -5,6 +7,8 @@ use ts_rs::TS;
 use utils::log_msg::LogMsg;
 use uuid::Uuid;
 
+/// The logs of an execution process, or a range of their lines, put back together from the
+/// chunks they were appended in
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct ExecutionProcessLogs {
     pub execution_id: Uuid,
@@ -13,11 +17,17 @@ pub struct ExecutionProcessLogs {
     pub inserted_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, TS)]
-pub struct CreateExecutionProcessLogs {
+/// Lines appended to the logs of an execution process in one go
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutionProcessLogChunk {
     pub execution_id: Uuid,
-    pub logs: String,
+    pub seq: i64,
+    pub byte_offset: i64,
     pub byte_size: i64,
+    pub line_offset: i64,
+    pub line_count: i64,
+    pub logs: String, // JSONL format
+    pub inserted_at: DateTime<Utc>,
 }
 
 impl ExecutionProcessLogs {
@@ -26,48 +36,80 @@ impl ExecutionProcessLogs {
         pool: &SqlitePool,
         execution_id: Uuid,
     ) -> Result<Option<Self>, sqlx::Error> {
-        sqlx::query_as!(
-            ExecutionProcessLogs,
-            r#"SELECT 
+        Self::find_lines(pool, execution_id, 0..i64::MAX).await
+    }
+
+    /// Find the given range of lines of the logs, reading only the chunks that hold them
+    pub async fn find_lines(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        lines: Range<i64>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let chunks = sqlx::query_as!(
+            ExecutionProcessLogChunk,
+            r#"SELECT
                 execution_id as "execution_id!: Uuid",
-                logs,
+                seq,
+                byte_offset,
                 byte_size,
+                line_offset,
+                line_count,
+                logs,
                 inserted_at as "inserted_at!: DateTime<Utc>"
-               FROM execution_process_logs 
-               WHERE execution_id = $1"#,
-            execution_id
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1
+                 AND line_offset >= COALESCE(
+                     (SELECT MAX(line_offset) FROM execution_process_log_chunks
+                      WHERE execution_id = $1 AND line_offset <= $2),
+                     0)
+                 AND line_offset < $3
+               ORDER BY seq"#,
+            execution_id,
+            lines.start,
+            lines.end
         )
-        .fetch_optional(pool)
-        .await
-    }
+        .fetch_all(pool)
+        .await?;
 
-    /// Create or update execution process logs
-    pub async fn upsert(
-        pool: &SqlitePool,
-        data: &CreateExecutionProcessLogs,
-    ) -> Result<Self, sqlx::Error> {
-        let now = Utc::now();
+        let Some(inserted_at) = chunks.last().map(|chunk| chunk.inserted_at) else {
+            return Ok(None);
+        };
+        let mut logs = String::new();
+        for chunk in &chunks {
+            let chunk_lines = chunk.line_offset..chunk.line_offset + chunk.line_count;
+            if lines.start <= chunk_lines.start && chunk_lines.end <= lines.end {
+                logs.push_str(&chunk.logs);
+                continue;
+            }
+            for (line, text) in (chunk.line_offset..).zip(chunk.logs.split_inclusive('\n')) {
+                if lines.contains(&line) {
+                    logs.push_str(text);
+                }
+            }
+        }
 
-        sqlx::query_as!(
-            ExecutionProcessLogs,
-            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at)
-               VALUES ($1, $2, $3, $4)
-               ON CONFLICT (execution_id) DO UPDATE
-               SET logs = EXCLUDED.logs, 
-                   byte_size = EXCLUDED.byte_size,
-                   inserted_at = EXCLUDED.inserted_at
-               RETURNING 
-                execution_id as "execution_id!: Uuid",
-                logs,
-                byte_size,
-                inserted_at as "inserted_at!: DateTime<Utc>""#,
-            data.execution_id,
-            data.logs,
-            data.byte_size,
-            now
+        Ok(Some(Self {
+            execution_id,
+            byte_size: logs.len() as i64,
+            logs,
+            inserted_at,
+        }))
+    }
+
+    /// Number of lines logged so far for an execution process
+    pub async fn line_count(pool: &SqlitePool, execution_id: Uuid) -> Result<i64, sqlx::Error> {
+        let line_count = sqlx::query_scalar!(
+            r#"SELECT line_offset + line_count as "line_count!: i64"
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1
+               ORDER BY seq DESC
+               LIMIT 1"#,
+            execution_id
         )
-        .fetch_one(pool)
-        .await
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(line_count.unwrap_or(0))
     }
 
     /// Parse JSONL logs back into Vec<LogMsg>
@@ -93,23 +135,37 @@ impl ExecutionProcessLogs {
         Ok(jsonl)
     }
 
-    /// Append a JSONL line to the logs for an execution process
-    pub async fn append_log_line(
+    /// Append JSONL lines to the logs for an execution process as a new chunk, after the
+    /// chunks already stored
+    pub async fn append_log_lines(
         pool: &SqlitePool,
         execution_id: Uuid,
-        jsonl_line: &str,
+        jsonl_lines: &str,
     ) -> Result<(), sqlx::Error> {
-        let byte_size = jsonl_line.len() as i64;
+        let byte_size = jsonl_lines.len() as i64;
+        let line_count = jsonl_lines.lines().count() as i64;
         sqlx::query!(
-            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at)
-               VALUES ($1, $2, $3, datetime('now', 'subsec'))
-               ON CONFLICT (execution_id) DO UPDATE
-               SET logs = logs || $2,
-                   byte_size = byte_size + $3,
-                   inserted_at = datetime('now', 'subsec')"#,
+            r#"INSERT INTO execution_process_log_chunks
+                (execution_id, seq, byte_offset, byte_size, line_offset, line_count, logs)
+               SELECT $1,
+                      COALESCE(last.seq + 1, 0),
+                      COALESCE(last.byte_offset + last.byte_size, 0),
+                      $3,
+                      COALESCE(last.line_offset + last.line_count, 0),
+                      $4,
+                      $2
+               FROM (SELECT 1)
+               LEFT JOIN (
+                   SELECT seq, byte_offset, byte_size, line_offset, line_count
+                   FROM execution_process_log_chunks
+                   WHERE execution_id = $1
+                   ORDER BY seq DESC
+                   LIMIT 1
+               ) AS last ON TRUE"#,
             execution_id,
-            jsonl_line,
-            byte_size
+            jsonl_lines,
+            byte_size,
+            line_count
         )
         .execute(pool)
         .await?;
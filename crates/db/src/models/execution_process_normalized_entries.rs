@@ -0,0 +1,75 @@
+use std::ops::Range;
+
+use executors::logs::NormalizedEntry;
+use sqlx::{SqlitePool, types::Json};
+use uuid::Uuid;
+
+/// The stored normalized conversation of a finished execution process, one row per entry
+pub struct ExecutionProcessNormalizedEntries;
+
+impl ExecutionProcessNormalizedEntries {
+    /// Number of entries stored for an execution process, 0 if it hasn't been stored
+    pub async fn count(pool: &SqlitePool, execution_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM execution_process_normalized_entries
+               WHERE execution_id = $1"#,
+            execution_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// The stored entries in the given range, in order
+    pub async fn find_range(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        entries: Range<i64>,
+    ) -> Result<Vec<NormalizedEntry>, sqlx::Error> {
+        let rows = sqlx::query_scalar!(
+            r#"SELECT entry as "entry!: Json<NormalizedEntry>"
+               FROM execution_process_normalized_entries
+               WHERE execution_id = $1
+                 AND entry_index >= $2
+                 AND entry_index < $3
+               ORDER BY entry_index"#,
+            execution_id,
+            entries.start,
+            entries.end
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|entry| entry.0).collect())
+    }
+
+    /// Store the whole normalized conversation of an execution process, replacing whatever
+    /// was stored for it before
+    pub async fn replace(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        entries: &[NormalizedEntry],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM execution_process_normalized_entries WHERE execution_id = $1",
+            execution_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        for (index, entry) in entries.iter().enumerate() {
+            let index = index as i64;
+            let entry = Json(entry);
+            sqlx::query!(
+                r#"INSERT INTO execution_process_normalized_entries
+                    (execution_id, entry_index, entry)
+                   VALUES ($1, $2, $3)"#,
+                execution_id,
+                index,
+                entry
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
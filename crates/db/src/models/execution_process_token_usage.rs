@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utils::log_msg::TokenUsage;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionProcessTokenUsage {
+    pub execution_process_id: Uuid,
+    pub model: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_creation_input_tokens: i64,
+    pub cache_read_input_tokens: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Summed token counts for a single model
+#[derive(Debug, Clone, Default, FromRow, Serialize, Deserialize, TS)]
+pub struct TokenUsageTotals {
+    pub model: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_creation_input_tokens: i64,
+    pub cache_read_input_tokens: i64,
+}
+
+/// Token usage for an attempt, task or project, overall and broken down by model
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct TokenUsageSummary {
+    pub total: TokenUsageTotals,
+    pub by_model: Vec<TokenUsageTotals>,
+}
+
+impl TokenUsageSummary {
+    fn from_model_totals(by_model: Vec<TokenUsageTotals>) -> Self {
+        let total = by_model
+            .iter()
+            .fold(TokenUsageTotals::default(), |acc, t| TokenUsageTotals {
+                model: None,
+                input_tokens: acc.input_tokens + t.input_tokens,
+                output_tokens: acc.output_tokens + t.output_tokens,
+                cache_creation_input_tokens: acc.cache_creation_input_tokens
+                    + t.cache_creation_input_tokens,
+                cache_read_input_tokens: acc.cache_read_input_tokens + t.cache_read_input_tokens,
+            });
+        Self { total, by_model }
+    }
+}
+
+impl ExecutionProcessTokenUsage {
+    /// Find token usage by execution process ID
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessTokenUsage,
+            r#"SELECT
+                execution_process_id as "execution_process_id!: Uuid",
+                model,
+                input_tokens,
+                output_tokens,
+                cache_creation_input_tokens,
+                cache_read_input_tokens,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_process_token_usage
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add the reported usage to the running totals for an execution process
+    pub async fn record(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        usage: &TokenUsage,
+    ) -> Result<(), sqlx::Error> {
+        let input_tokens = usage.input_tokens as i64;
+        let output_tokens = usage.output_tokens as i64;
+        let cache_creation_input_tokens = usage.cache_creation_input_tokens as i64;
+        let cache_read_input_tokens = usage.cache_read_input_tokens as i64;
+        sqlx::query!(
+            r#"INSERT INTO execution_process_token_usage (
+                execution_process_id, model, input_tokens, output_tokens,
+                cache_creation_input_tokens, cache_read_input_tokens
+               )
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT (execution_process_id) DO UPDATE
+               SET model = COALESCE(EXCLUDED.model, model),
+                   input_tokens = input_tokens + EXCLUDED.input_tokens,
+                   output_tokens = output_tokens + EXCLUDED.output_tokens,
+                   cache_creation_input_tokens = cache_creation_input_tokens + EXCLUDED.cache_creation_input_tokens,
+                   cache_read_input_tokens = cache_read_input_tokens + EXCLUDED.cache_read_input_tokens,
+                   updated_at = datetime('now', 'subsec')"#,
+            execution_process_id,
+            usage.model,
+            input_tokens,
+            output_tokens,
+            cache_creation_input_tokens,
+            cache_read_input_tokens
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sum token usage across all execution processes of a task attempt
+    pub async fn summary_for_task_attempt(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<TokenUsageSummary, sqlx::Error> {
+        let by_model = sqlx::query_as!(
+            TokenUsageTotals,
+            r#"SELECT
+                u.model,
+                COALESCE(SUM(u.input_tokens), 0) as "input_tokens!: i64",
+                COALESCE(SUM(u.output_tokens), 0) as "output_tokens!: i64",
+                COALESCE(SUM(u.cache_creation_input_tokens), 0) as "cache_creation_input_tokens!: i64",
+                COALESCE(SUM(u.cache_read_input_tokens), 0) as "cache_read_input_tokens!: i64"
+               FROM execution_process_token_usage u
+               JOIN execution_processes ep ON ep.id = u.execution_process_id
+               WHERE ep.task_attempt_id = $1
+               GROUP BY u.model
+               ORDER BY u.model"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(TokenUsageSummary::from_model_totals(by_model))
+    }
+
+    /// Sum token usage across all attempts of a task
+    pub async fn summary_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<TokenUsageSummary, sqlx::Error> {
+        let by_model = sqlx::query_as!(
+            TokenUsageTotals,
+            r#"SELECT
+                u.model,
+                COALESCE(SUM(u.input_tokens), 0) as "input_tokens!: i64",
+                COALESCE(SUM(u.output_tokens), 0) as "output_tokens!: i64",
+                COALESCE(SUM(u.cache_creation_input_tokens), 0) as "cache_creation_input_tokens!: i64",
+                COALESCE(SUM(u.cache_read_input_tokens), 0) as "cache_read_input_tokens!: i64"
+               FROM execution_process_token_usage u
+               JOIN execution_processes ep ON ep.id = u.execution_process_id
+               JOIN task_attempts ta ON ta.id = ep.task_attempt_id
+               WHERE ta.task_id = $1
+               GROUP BY u.model
+               ORDER BY u.model"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(TokenUsageSummary::from_model_totals(by_model))
+    }
+
+    /// Sum token usage across all tasks of a project
+    pub async fn summary_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<TokenUsageSummary, sqlx::Error> {
+        let by_model = sqlx::query_as!(
+            TokenUsageTotals,
+            r#"SELECT
+                u.model,
+                COALESCE(SUM(u.input_tokens), 0) as "input_tokens!: i64",
+                COALESCE(SUM(u.output_tokens), 0) as "output_tokens!: i64",
+                COALESCE(SUM(u.cache_creation_input_tokens), 0) as "cache_creation_input_tokens!: i64",
+                COALESCE(SUM(u.cache_read_input_tokens), 0) as "cache_read_input_tokens!: i64"
+               FROM execution_process_token_usage u
+               JOIN execution_processes ep ON ep.id = u.execution_process_id
+               JOIN task_attempts ta ON ta.id = ep.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE t.project_id = $1
+               GROUP BY u.model
+               ORDER BY u.model"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(TokenUsageSummary::from_model_totals(by_model))
+    }
+}
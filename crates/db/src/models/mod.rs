@@ -1,7 +1,13 @@
 pub mod execution_process;
 pub mod execution_process_logs;
+pub mod execution_process_normalized_entries;
+pub mod execution_process_token_usage;
 pub mod executor_session;
+pub mod plan_review;
 pub mod project;
+pub mod review_comment;
 pub mod task;
 pub mod task_attempt;
+pub mod task_dependency;
 pub mod task_template;
+pub mod tool_approval;
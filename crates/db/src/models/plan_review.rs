@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(type_name = "plan_review_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PlanReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// A plan presented by a plan-mode coding agent, waiting to be approved before any code is
+/// written
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PlanReview {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    pub plan: String, // As presented, or as edited by the reviewer
+    pub status: PlanReviewStatus,
+    pub created_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreatePlanReview {
+    pub execution_process_id: Uuid,
+    pub plan: String,
+}
+
+impl PlanReview {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"SELECT
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                plan,
+                status as "status!: PlanReviewStatus",
+                created_at as "created_at!: DateTime<Utc>",
+                responded_at as "responded_at?: DateTime<Utc>"
+               FROM plan_reviews
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Find the most recent plan presented by the execution processes of a task attempt
+    pub async fn find_latest_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"SELECT
+                r.id as "id!: Uuid",
+                r.execution_process_id as "execution_process_id!: Uuid",
+                r.plan,
+                r.status as "status!: PlanReviewStatus",
+                r.created_at as "created_at!: DateTime<Utc>",
+                r.responded_at as "responded_at?: DateTime<Utc>"
+               FROM plan_reviews r
+               JOIN execution_processes ep ON ep.id = r.execution_process_id
+               WHERE ep.task_attempt_id = $1
+               ORDER BY r.created_at DESC
+               LIMIT 1"#,
+            task_attempt_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreatePlanReview,
+        id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"INSERT INTO plan_reviews (id, execution_process_id, plan)
+               VALUES ($1, $2, $3)
+               RETURNING
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                plan,
+                status as "status!: PlanReviewStatus",
+                created_at as "created_at!: DateTime<Utc>",
+                responded_at as "responded_at?: DateTime<Utc>""#,
+            id,
+            data.execution_process_id,
+            data.plan
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Edit a pending plan. Returns `None` if it was already approved or rejected.
+    pub async fn update_plan(
+        pool: &SqlitePool,
+        id: Uuid,
+        plan: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"UPDATE plan_reviews
+               SET plan = $2
+               WHERE id = $1 AND status = 'pending'
+               RETURNING
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                plan,
+                status as "status!: PlanReviewStatus",
+                created_at as "created_at!: DateTime<Utc>",
+                responded_at as "responded_at?: DateTime<Utc>""#,
+            id,
+            plan
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Approve or reject a pending plan. Returns `None` if it was already answered.
+    pub async fn respond(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: PlanReviewStatus,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanReview,
+            r#"UPDATE plan_reviews
+               SET status = $2, responded_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'pending'
+               RETURNING
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                plan,
+                status as "status!: PlanReviewStatus",
+                created_at as "created_at!: DateTime<Utc>",
+                responded_at as "responded_at?: DateTime<Utc>""#,
+            id,
+            status
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Put an approved plan back up for review, when the follow-up implementing it could not
+    /// be started
+    pub async fn reopen(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE plan_reviews
+               SET status = 'pending', responded_at = NULL
+               WHERE id = $1 AND status = 'approved'"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -1,12 +1,24 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use chrono::{DateTime, Utc};
+use executors::{
+    actions::{
+        dev_server::DevServer,
+        pipeline::PipelineStep,
+        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+    },
+    env::{ExecutionEnv, ResourceLimits, Sandbox},
+    profile::{ExecutionLimits, ProfileVariantLabel},
+};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
+/// Fix iterations for projects with a test script that don't set a limit
+pub const DEFAULT_MAX_FIX_ITERATIONS: u64 = 3;
+
 #[derive(Debug, Error)]
 pub enum ProjectError {
     #[error(transparent)]
@@ -27,9 +39,58 @@ pub struct Project {
     pub name: String,
     pub git_repo_path: PathBuf,
     pub setup_script: Option<String>,
-    pub dev_script: Option<String>,
+    /// Processes started together as the dev server, each restarted if it crashes
+    #[ts(type = "Array<DevServer>")]
+    pub dev_servers: sqlx::types::Json<Vec<DevServer>>,
     pub cleanup_script: Option<String>,
+    /// Runs after each coding agent run; when it fails, the agent is asked to fix it
+    pub test_script: Option<String>,
+    /// Language the setup, cleanup and dev scripts are written in, bash if not set
+    #[ts(type = "ScriptRequestLanguage | null")]
+    pub script_language: Option<sqlx::types::Json<ScriptRequestLanguage>>,
+    /// Directory the setup, cleanup and dev scripts run in, relative to the worktree root
+    pub script_working_dir: Option<String>,
+    /// Seconds the setup and cleanup scripts may run for before they are stopped
+    #[ts(type = "number | null")]
+    pub script_timeout_secs: Option<i64>,
     pub copy_files: Option<String>,
+    /// Execution limits for coding agents, on top of the profile's own limits
+    #[ts(type = "number | null")]
+    pub max_duration_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_idle_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_tool_calls: Option<i64>,
+    /// Resource limits for every process spawned for the project's attempts
+    #[ts(type = "number | null")]
+    pub max_memory_mb: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_cpu_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_processes: Option<i64>,
+    /// Coding agents of the project's attempts that may run at once
+    #[ts(type = "number | null")]
+    pub max_concurrent_agents: Option<i64>,
+    /// Follow-ups the agent gets to fix a failing test script before the attempt is left
+    /// for review
+    #[ts(type = "number | null")]
+    pub max_fix_iterations: Option<i64>,
+    /// Profile variant new attempts start with, instead of the global default
+    #[ts(type = "ProfileVariantLabel | null")]
+    pub default_profile: Option<sqlx::types::Json<ProfileVariantLabel>>,
+    /// Profile variant that reviews the changes whenever a coding agent finishes
+    #[ts(type = "ProfileVariantLabel | null")]
+    pub review_profile: Option<sqlx::types::Json<ProfileVariantLabel>>,
+    /// Environment variables for every process spawned for the project's attempts
+    #[ts(type = "Record<string, string>")]
+    pub env_vars: sqlx::types::Json<BTreeMap<String, String>>,
+    /// Steps run in order after each coding agent run
+    #[ts(type = "Array<PipelineStep>")]
+    pub pipeline: sqlx::types::Json<Vec<PipelineStep>>,
+    /// Run coding agents and scripts in a sandbox (Linux only)
+    pub sandboxed: bool,
+    /// Whether sandboxed processes have network access
+    pub sandbox_network: bool,
 
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
@@ -43,9 +104,36 @@ pub struct CreateProject {
     pub git_repo_path: String,
     pub use_existing_repo: bool,
     pub setup_script: Option<String>,
-    pub dev_script: Option<String>,
+    pub dev_servers: Option<Vec<DevServer>>,
     pub cleanup_script: Option<String>,
+    pub test_script: Option<String>,
+    pub script_language: Option<ScriptRequestLanguage>,
+    pub script_working_dir: Option<String>,
+    #[ts(type = "number | null")]
+    pub script_timeout_secs: Option<i64>,
     pub copy_files: Option<String>,
+    #[ts(type = "number | null")]
+    pub max_duration_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_idle_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_tool_calls: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_memory_mb: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_cpu_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_processes: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_concurrent_agents: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_fix_iterations: Option<i64>,
+    pub default_profile: Option<ProfileVariantLabel>,
+    pub review_profile: Option<ProfileVariantLabel>,
+    pub env_vars: Option<BTreeMap<String, String>>,
+    pub pipeline: Option<Vec<PipelineStep>>,
+    pub sandboxed: Option<bool>,
+    pub sandbox_network: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -53,9 +141,36 @@ pub struct UpdateProject {
     pub name: Option<String>,
     pub git_repo_path: Option<String>,
     pub setup_script: Option<String>,
-    pub dev_script: Option<String>,
+    pub dev_servers: Option<Vec<DevServer>>,
     pub cleanup_script: Option<String>,
+    pub test_script: Option<String>,
+    pub script_language: Option<ScriptRequestLanguage>,
+    pub script_working_dir: Option<String>,
+    #[ts(type = "number | null")]
+    pub script_timeout_secs: Option<i64>,
     pub copy_files: Option<String>,
+    #[ts(type = "number | null")]
+    pub max_duration_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_idle_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_tool_calls: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_memory_mb: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_cpu_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_processes: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_concurrent_agents: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_fix_iterations: Option<i64>,
+    pub default_profile: Option<ProfileVariantLabel>,
+    pub review_profile: Option<ProfileVariantLabel>,
+    pub env_vars: Option<BTreeMap<String, String>>,
+    pub pipeline: Option<Vec<PipelineStep>>,
+    pub sandboxed: Option<bool>,
+    pub sandbox_network: Option<bool>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -64,9 +179,42 @@ pub struct ProjectWithBranch {
     pub name: String,
     pub git_repo_path: PathBuf,
     pub setup_script: Option<String>,
-    pub dev_script: Option<String>,
+    #[ts(type = "Array<DevServer>")]
+    pub dev_servers: sqlx::types::Json<Vec<DevServer>>,
     pub cleanup_script: Option<String>,
+    pub test_script: Option<String>,
+    #[ts(type = "ScriptRequestLanguage | null")]
+    pub script_language: Option<sqlx::types::Json<ScriptRequestLanguage>>,
+    pub script_working_dir: Option<String>,
+    #[ts(type = "number | null")]
+    pub script_timeout_secs: Option<i64>,
     pub copy_files: Option<String>,
+    #[ts(type = "number | null")]
+    pub max_duration_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_idle_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_tool_calls: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_memory_mb: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_cpu_secs: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_processes: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_concurrent_agents: Option<i64>,
+    #[ts(type = "number | null")]
+    pub max_fix_iterations: Option<i64>,
+    #[ts(type = "ProfileVariantLabel | null")]
+    pub default_profile: Option<sqlx::types::Json<ProfileVariantLabel>>,
+    #[ts(type = "ProfileVariantLabel | null")]
+    pub review_profile: Option<sqlx::types::Json<ProfileVariantLabel>>,
+    #[ts(type = "Record<string, string>")]
+    pub env_vars: sqlx::types::Json<BTreeMap<String, String>>,
+    #[ts(type = "Array<PipelineStep>")]
+    pub pipeline: sqlx::types::Json<Vec<PipelineStep>>,
+    pub sandboxed: bool,
+    pub sandbox_network: bool,
     pub current_branch: Option<String>,
 
     #[ts(type = "Date")]
@@ -82,9 +230,27 @@ impl ProjectWithBranch {
             name: project.name,
             git_repo_path: project.git_repo_path,
             setup_script: project.setup_script,
-            dev_script: project.dev_script,
+            dev_servers: project.dev_servers,
             cleanup_script: project.cleanup_script,
+            test_script: project.test_script,
+            script_language: project.script_language,
+            script_working_dir: project.script_working_dir,
+            script_timeout_secs: project.script_timeout_secs,
             copy_files: project.copy_files,
+            max_duration_secs: project.max_duration_secs,
+            max_idle_secs: project.max_idle_secs,
+            max_tool_calls: project.max_tool_calls,
+            max_memory_mb: project.max_memory_mb,
+            max_cpu_secs: project.max_cpu_secs,
+            max_processes: project.max_processes,
+            max_concurrent_agents: project.max_concurrent_agents,
+            max_fix_iterations: project.max_fix_iterations,
+            default_profile: project.default_profile,
+            review_profile: project.review_profile,
+            env_vars: project.env_vars,
+            pipeline: project.pipeline,
+            sandboxed: project.sandboxed,
+            sandbox_network: project.sandbox_network,
             current_branch,
             created_at: project.created_at,
             updated_at: project.updated_at,
@@ -107,10 +273,70 @@ pub enum SearchMatchType {
 }
 
 impl Project {
+    /// The project's execution limits; negative values are treated as unset
+    pub fn execution_limits(&self) -> ExecutionLimits {
+        let limit = |value: Option<i64>| value.and_then(|v| u64::try_from(v).ok());
+        ExecutionLimits {
+            max_duration_secs: limit(self.max_duration_secs),
+            max_idle_secs: limit(self.max_idle_secs),
+            max_tool_calls: limit(self.max_tool_calls),
+        }
+    }
+
+    /// The project's resource limits; negative values are treated as unset
+    pub fn resource_limits(&self) -> ResourceLimits {
+        let limit = |value: Option<i64>| value.and_then(|v| u64::try_from(v).ok());
+        ResourceLimits {
+            max_memory_mb: limit(self.max_memory_mb),
+            max_cpu_secs: limit(self.max_cpu_secs),
+            max_processes: limit(self.max_processes),
+        }
+    }
+
+    /// How many of the project's coding agents may run at once; negative values are
+    /// treated as unset
+    pub fn agent_limit(&self) -> Option<u64> {
+        self.max_concurrent_agents
+            .and_then(|v| u64::try_from(v).ok())
+    }
+
+    /// How many times the agent is asked to fix a failing test script in a row; negative
+    /// values are treated as unset
+    pub fn fix_iteration_limit(&self) -> u64 {
+        self.max_fix_iterations
+            .and_then(|v| u64::try_from(v).ok())
+            .unwrap_or(DEFAULT_MAX_FIX_ITERATIONS)
+    }
+
+    /// The environment every process spawned for the project's attempts runs with
+    pub fn execution_env(&self) -> ExecutionEnv {
+        ExecutionEnv::new(self.env_vars.0.clone())
+            .with_sandbox(self.sandboxed.then(|| Sandbox::new(self.sandbox_network)))
+            .with_resource_limits(self.resource_limits())
+    }
+
+    /// A request to run one of the project's scripts in its script language and working
+    /// directory. The timeout doesn't apply to dev servers, which run until they're stopped.
+    pub fn script_request(&self, script: String, context: ScriptContext) -> ScriptRequest {
+        let language = self
+            .script_language
+            .as_ref()
+            .map(|language| language.0.clone())
+            .unwrap_or(ScriptRequestLanguage::Bash);
+        let timeout_secs = match context {
+            ScriptContext::DevServer => None,
+            _ => self.script_timeout_secs.map(|secs| secs as u64),
+        };
+        let mut request = ScriptRequest::new(script, language, context);
+        request.working_dir = self.script_working_dir.clone();
+        request.timeout_secs = timeout_secs;
+        request
+    }
+
     pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_servers as "dev_servers!: sqlx::types::Json<Vec<DevServer>>", cleanup_script, test_script, copy_files, max_duration_secs, max_idle_secs, max_tool_calls, max_memory_mb, max_cpu_secs, max_processes, max_concurrent_agents, max_fix_iterations, default_profile as "default_profile: sqlx::types::Json<ProfileVariantLabel>", review_profile as "review_profile: sqlx::types::Json<ProfileVariantLabel>", env_vars as "env_vars!: sqlx::types::Json<BTreeMap<String, String>>", pipeline as "pipeline!: sqlx::types::Json<Vec<PipelineStep>>", script_language as "script_language: sqlx::types::Json<ScriptRequestLanguage>", script_working_dir, script_timeout_secs, sandboxed as "sandboxed!: bool", sandbox_network as "sandbox_network!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
         )
         .fetch_all(pool)
         .await
@@ -119,7 +345,7 @@ impl Project {
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_servers as "dev_servers!: sqlx::types::Json<Vec<DevServer>>", cleanup_script, test_script, copy_files, max_duration_secs, max_idle_secs, max_tool_calls, max_memory_mb, max_cpu_secs, max_processes, max_concurrent_agents, max_fix_iterations, default_profile as "default_profile: sqlx::types::Json<ProfileVariantLabel>", review_profile as "review_profile: sqlx::types::Json<ProfileVariantLabel>", env_vars as "env_vars!: sqlx::types::Json<BTreeMap<String, String>>", pipeline as "pipeline!: sqlx::types::Json<Vec<PipelineStep>>", script_language as "script_language: sqlx::types::Json<ScriptRequestLanguage>", script_working_dir, script_timeout_secs, sandboxed as "sandboxed!: bool", sandbox_network as "sandbox_network!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
             id
         )
         .fetch_optional(pool)
@@ -132,7 +358,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_servers as "dev_servers!: sqlx::types::Json<Vec<DevServer>>", cleanup_script, test_script, copy_files, max_duration_secs, max_idle_secs, max_tool_calls, max_memory_mb, max_cpu_secs, max_processes, max_concurrent_agents, max_fix_iterations, default_profile as "default_profile: sqlx::types::Json<ProfileVariantLabel>", review_profile as "review_profile: sqlx::types::Json<ProfileVariantLabel>", env_vars as "env_vars!: sqlx::types::Json<BTreeMap<String, String>>", pipeline as "pipeline!: sqlx::types::Json<Vec<PipelineStep>>", script_language as "script_language: sqlx::types::Json<ScriptRequestLanguage>", script_working_dir, script_timeout_secs, sandboxed as "sandboxed!: bool", sandbox_network as "sandbox_network!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
             git_repo_path
         )
         .fetch_optional(pool)
@@ -146,7 +372,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_servers as "dev_servers!: sqlx::types::Json<Vec<DevServer>>", cleanup_script, test_script, copy_files, max_duration_secs, max_idle_secs, max_tool_calls, max_memory_mb, max_cpu_secs, max_processes, max_concurrent_agents, max_fix_iterations, default_profile as "default_profile: sqlx::types::Json<ProfileVariantLabel>", review_profile as "review_profile: sqlx::types::Json<ProfileVariantLabel>", env_vars as "env_vars!: sqlx::types::Json<BTreeMap<String, String>>", pipeline as "pipeline!: sqlx::types::Json<Vec<PipelineStep>>", script_language as "script_language: sqlx::types::Json<ScriptRequestLanguage>", script_working_dir, script_timeout_secs, sandboxed as "sandboxed!: bool", sandbox_network as "sandbox_network!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
             git_repo_path,
             exclude_id
         )
@@ -159,41 +385,99 @@ impl Project {
         data: &CreateProject,
         project_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
+        let default_profile = data.default_profile.as_ref().map(sqlx::types::Json);
+        let review_profile = data.review_profile.as_ref().map(sqlx::types::Json);
+        let env_vars = sqlx::types::Json(data.env_vars.clone().unwrap_or_default());
+        let dev_servers = sqlx::types::Json(data.dev_servers.clone().unwrap_or_default());
+        let pipeline = sqlx::types::Json(data.pipeline.clone().unwrap_or_default());
+        let script_language = data.script_language.as_ref().map(sqlx::types::Json);
+        let sandboxed = data.sandboxed.unwrap_or(false);
+        let sandbox_network = data.sandbox_network.unwrap_or(false);
         sqlx::query_as!(
             Project,
-            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_servers, cleanup_script, copy_files, max_duration_secs, max_idle_secs, max_tool_calls, default_profile, review_profile, env_vars, sandboxed, sandbox_network, max_memory_mb, max_cpu_secs, max_processes, max_concurrent_agents, test_script, max_fix_iterations, pipeline, script_language, script_working_dir, script_timeout_secs) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_servers as "dev_servers!: sqlx::types::Json<Vec<DevServer>>", cleanup_script, test_script, copy_files, max_duration_secs, max_idle_secs, max_tool_calls, max_memory_mb, max_cpu_secs, max_processes, max_concurrent_agents, max_fix_iterations, default_profile as "default_profile: sqlx::types::Json<ProfileVariantLabel>", review_profile as "review_profile: sqlx::types::Json<ProfileVariantLabel>", env_vars as "env_vars!: sqlx::types::Json<BTreeMap<String, String>>", pipeline as "pipeline!: sqlx::types::Json<Vec<PipelineStep>>", script_language as "script_language: sqlx::types::Json<ScriptRequestLanguage>", script_working_dir, script_timeout_secs, sandboxed as "sandboxed!: bool", sandbox_network as "sandbox_network!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
             data.name,
             data.git_repo_path,
             data.setup_script,
-            data.dev_script,
+            dev_servers,
             data.cleanup_script,
-            data.copy_files
+            data.copy_files,
+            data.max_duration_secs,
+            data.max_idle_secs,
+            data.max_tool_calls,
+            default_profile,
+            review_profile,
+            env_vars,
+            sandboxed,
+            sandbox_network,
+            data.max_memory_mb,
+            data.max_cpu_secs,
+            data.max_processes,
+            data.max_concurrent_agents,
+            data.test_script,
+            data.max_fix_iterations,
+            pipeline,
+            script_language,
+            data.script_working_dir,
+            data.script_timeout_secs
         )
         .fetch_one(pool)
         .await
     }
 
+    /// Update the project from `data`. Fields without a sensible empty value keep their
+    /// current value when unset; the others are cleared, as the frontend sends all fields
+    /// on update.
     pub async fn update(
         pool: &SqlitePool,
-        id: Uuid,
-        name: String,
-        git_repo_path: String,
-        setup_script: Option<String>,
-        dev_script: Option<String>,
-        cleanup_script: Option<String>,
-        copy_files: Option<String>,
+        existing: &Project,
+        data: &UpdateProject,
     ) -> Result<Self, sqlx::Error> {
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let git_repo_path = data
+            .git_repo_path
+            .clone()
+            .unwrap_or_else(|| existing.git_repo_path.to_string_lossy().to_string());
+        let default_profile = data.default_profile.as_ref().map(sqlx::types::Json);
+        let review_profile = data.review_profile.as_ref().map(sqlx::types::Json);
+        let env_vars = data.env_vars.as_ref().unwrap_or(&existing.env_vars.0);
+        let env_vars = sqlx::types::Json(env_vars);
+        let dev_servers = data.dev_servers.as_ref().unwrap_or(&existing.dev_servers.0);
+        let dev_servers = sqlx::types::Json(dev_servers);
+        let pipeline = data.pipeline.as_ref().unwrap_or(&existing.pipeline.0);
+        let pipeline = sqlx::types::Json(pipeline);
+        let script_language = data.script_language.as_ref().map(sqlx::types::Json);
+        let sandboxed = data.sandboxed.unwrap_or(existing.sandboxed);
+        let sandbox_network = data.sandbox_network.unwrap_or(existing.sandbox_network);
         sqlx::query_as!(
             Project,
-            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5, cleanup_script = $6, copy_files = $7 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
-            id,
+            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_servers = $5, cleanup_script = $6, copy_files = $7, max_duration_secs = $8, max_idle_secs = $9, max_tool_calls = $10, default_profile = $11, review_profile = $12, env_vars = $13, sandboxed = $14, sandbox_network = $15, max_memory_mb = $16, max_cpu_secs = $17, max_processes = $18, max_concurrent_agents = $19, test_script = $20, max_fix_iterations = $21, pipeline = $22, script_language = $23, script_working_dir = $24, script_timeout_secs = $25 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_servers as "dev_servers!: sqlx::types::Json<Vec<DevServer>>", cleanup_script, test_script, copy_files, max_duration_secs, max_idle_secs, max_tool_calls, max_memory_mb, max_cpu_secs, max_processes, max_concurrent_agents, max_fix_iterations, default_profile as "default_profile: sqlx::types::Json<ProfileVariantLabel>", review_profile as "review_profile: sqlx::types::Json<ProfileVariantLabel>", env_vars as "env_vars!: sqlx::types::Json<BTreeMap<String, String>>", pipeline as "pipeline!: sqlx::types::Json<Vec<PipelineStep>>", script_language as "script_language: sqlx::types::Json<ScriptRequestLanguage>", script_working_dir, script_timeout_secs, sandboxed as "sandboxed!: bool", sandbox_network as "sandbox_network!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            existing.id,
             name,
             git_repo_path,
-            setup_script,
-            dev_script,
-            cleanup_script,
-            copy_files
+            data.setup_script,
+            dev_servers,
+            data.cleanup_script,
+            data.copy_files,
+            data.max_duration_secs,
+            data.max_idle_secs,
+            data.max_tool_calls,
+            default_profile,
+            review_profile,
+            env_vars,
+            sandboxed,
+            sandbox_network,
+            data.max_memory_mb,
+            data.max_cpu_secs,
+            data.max_processes,
+            data.max_concurrent_agents,
+            data.test_script,
+            data.max_fix_iterations,
+            pipeline,
+            script_language,
+            data.script_working_dir,
+            data.script_timeout_secs
         )
         .fetch_one(pool)
         .await
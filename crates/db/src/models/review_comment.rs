@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(type_name = "review_comment_severity", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewCommentSeverity {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+/// A finding of an automated code review run on an attempt's changes
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ReviewComment {
+    pub id: Uuid,
+    pub execution_process_id: Uuid, // The review run that reported it
+    pub file_path: Option<String>,  // Relative to the repository root
+    #[ts(type = "number | null")]
+    pub line: Option<i64>,
+    pub severity: ReviewCommentSeverity,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateReviewComment {
+    pub file_path: Option<String>,
+    pub line: Option<i64>,
+    pub severity: ReviewCommentSeverity,
+    pub body: String,
+}
+
+impl ReviewComment {
+    /// Find the comments of every review run of a task attempt, oldest first
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT
+                c.id as "id!: Uuid",
+                c.execution_process_id as "execution_process_id!: Uuid",
+                c.file_path,
+                c.line,
+                c.severity as "severity!: ReviewCommentSeverity",
+                c.body,
+                c.created_at as "created_at!: DateTime<Utc>"
+               FROM review_comments c
+               JOIN execution_processes ep ON ep.id = c.execution_process_id
+               WHERE ep.task_attempt_id = $1
+               ORDER BY c.created_at ASC, c.rowid ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        data: &CreateReviewComment,
+        id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"INSERT INTO review_comments (id, execution_process_id, file_path, line, severity, body)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                file_path,
+                line,
+                severity as "severity!: ReviewCommentSeverity",
+                body,
+                created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            execution_process_id,
+            data.file_path,
+            data.line,
+            data.severity,
+            data.body
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
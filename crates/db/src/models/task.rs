@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use executors::profile::ProfileVariantLabel;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use ts_rs::TS;
@@ -25,6 +26,9 @@ pub struct Task {
     pub description: Option<String>,
     pub status: TaskStatus,
     pub parent_task_attempt: Option<Uuid>, // Foreign key to parent TaskAttempt
+    /// Profile variant an attempt is started with once all of the task's blockers are done
+    #[ts(type = "ProfileVariantLabel | null")]
+    pub auto_start_profile: Option<sqlx::types::Json<ProfileVariantLabel>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -37,11 +41,16 @@ pub struct TaskWithAttemptStatus {
     pub description: Option<String>,
     pub status: TaskStatus,
     pub parent_task_attempt: Option<Uuid>,
+    #[ts(type = "ProfileVariantLabel | null")]
+    pub auto_start_profile: Option<sqlx::types::Json<ProfileVariantLabel>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub has_in_progress_attempt: bool,
     pub has_merged_attempt: bool,
     pub last_attempt_failed: bool,
+    pub awaiting_plan_approval: bool,
+    /// Some task this one is blocked by is not done yet
+    pub is_blocked: bool,
     pub profile: String,
 }
 
@@ -51,6 +60,7 @@ pub struct CreateTask {
     pub title: String,
     pub description: Option<String>,
     pub parent_task_attempt: Option<Uuid>,
+    pub auto_start_profile: Option<ProfileVariantLabel>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -86,6 +96,7 @@ impl Task {
   t.description,
   t.status                        AS "status!: TaskStatus",
   t.parent_task_attempt           AS "parent_task_attempt: Uuid",
+  t.auto_start_profile            AS "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>",
   t.created_at                    AS "created_at!: DateTime<Utc>",
   t.updated_at                    AS "updated_at!: DateTime<Utc>",
 
@@ -95,7 +106,7 @@ impl Task {
       JOIN execution_processes ep
         ON ep.task_attempt_id = ta.id
      WHERE ta.task_id       = t.id
-       AND ep.status        = 'running'
+       AND ep.status       IN ('running', 'queued')
        AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
      LIMIT 1
   ) THEN 1 ELSE 0 END            AS "has_in_progress_attempt!: i64",
@@ -120,6 +131,28 @@ impl Task {
   ) IN ('failed','killed') THEN 1 ELSE 0 END
                                  AS "last_attempt_failed!: i64",
 
+  CASE WHEN EXISTS (
+    SELECT 1
+      FROM task_attempts ta
+      JOIN execution_processes ep
+        ON ep.task_attempt_id = ta.id
+      JOIN plan_reviews pr
+        ON pr.execution_process_id = ep.id
+     WHERE ta.task_id       = t.id
+       AND pr.status        = 'pending'
+     LIMIT 1
+  ) THEN 1 ELSE 0 END            AS "awaiting_plan_approval!: i64",
+
+  CASE WHEN EXISTS (
+    SELECT 1
+      FROM task_dependencies td
+      JOIN tasks blocker
+        ON blocker.id = td.blocked_by_task_id
+     WHERE td.task_id       = t.id
+       AND blocker.status  != 'done'
+     LIMIT 1
+  ) THEN 1 ELSE 0 END            AS "is_blocked!: i64",
+
   ( SELECT ta.profile
       FROM task_attempts ta
       WHERE ta.task_id = t.id
@@ -144,11 +177,14 @@ ORDER BY t.created_at DESC"#,
                 description: rec.description,
                 status: rec.status,
                 parent_task_attempt: rec.parent_task_attempt,
+                auto_start_profile: rec.auto_start_profile,
                 created_at: rec.created_at,
                 updated_at: rec.updated_at,
                 has_in_progress_attempt: rec.has_in_progress_attempt != 0,
                 has_merged_attempt: rec.has_merged_attempt != 0,
                 last_attempt_failed: rec.last_attempt_failed != 0,
+                awaiting_plan_approval: rec.awaiting_plan_approval != 0,
+                is_blocked: rec.is_blocked != 0,
                 profile: rec.profile,
             })
             .collect();
@@ -159,7 +195,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", auto_start_profile as "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks 
                WHERE id = $1"#,
             id
@@ -171,7 +207,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", auto_start_profile as "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks 
                WHERE rowid = $1"#,
             rowid
@@ -187,7 +223,7 @@ ORDER BY t.created_at DESC"#,
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", auto_start_profile as "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks 
                WHERE id = $1 AND project_id = $2"#,
             id,
@@ -202,17 +238,19 @@ ORDER BY t.created_at DESC"#,
         data: &CreateTask,
         task_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
+        let auto_start_profile = data.auto_start_profile.clone().map(sqlx::types::Json);
         sqlx::query_as!(
             Task,
-            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_task_attempt) 
-               VALUES ($1, $2, $3, $4, $5, $6) 
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_task_attempt, auto_start_profile) 
+               VALUES ($1, $2, $3, $4, $5, $6, $7) 
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", auto_start_profile as "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             task_id,
             data.project_id,
             data.title,
             data.description,
             TaskStatus::Todo as TaskStatus,
-            data.parent_task_attempt
+            data.parent_task_attempt,
+            auto_start_profile
         )
         .fetch_one(pool)
         .await
@@ -232,7 +270,7 @@ ORDER BY t.created_at DESC"#,
             r#"UPDATE tasks 
                SET title = $3, description = $4, status = $5, parent_task_attempt = $6 
                WHERE id = $1 AND project_id = $2 
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", auto_start_profile as "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             project_id,
             title,
@@ -259,6 +297,36 @@ ORDER BY t.created_at DESC"#,
         Ok(())
     }
 
+    /// Set the profile variant the task starts with once it is unblocked; None turns
+    /// auto-start off
+    pub async fn update_auto_start_profile(
+        pool: &SqlitePool,
+        id: Uuid,
+        auto_start_profile: Option<ProfileVariantLabel>,
+    ) -> Result<(), sqlx::Error> {
+        let auto_start_profile = auto_start_profile.map(sqlx::types::Json);
+        sqlx::query!(
+            "UPDATE tasks SET auto_start_profile = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id,
+            auto_start_profile
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Turn auto-start off as the task is being started automatically. Returns false if
+    /// it was already off, e.g. because the task was started by someone else meanwhile.
+    pub async fn claim_auto_start(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE tasks SET auto_start_profile = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = $1 AND auto_start_profile IS NOT NULL",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!("DELETE FROM tasks WHERE id = $1", id)
             .execute(pool)
@@ -288,7 +356,7 @@ ORDER BY t.created_at DESC"#,
         // Find both children and parent for this attempt
         sqlx::query_as!(
             Task,
-            r#"SELECT DISTINCT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT DISTINCT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.auto_start_profile as "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks t
                WHERE (
                    -- Find children: tasks that have this attempt as parent
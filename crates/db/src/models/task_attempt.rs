@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{FromRow, SqliteExecutor, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -83,6 +83,7 @@ pub struct TaskAttempt {
     pub pr_merged_at: Option<DateTime<Utc>>,       // When PR was merged
     pub worktree_deleted: bool, // Flag indicating if worktree has been cleaned up
     pub setup_completed_at: Option<DateTime<Utc>>, // When setup script was last completed
+    pub fallback_from_attempt_id: Option<Uuid>, // Failed attempt this one automatically replaced
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -121,6 +122,7 @@ pub struct TaskAttemptContext {
 pub struct CreateTaskAttempt {
     pub profile: String,
     pub base_branch: String,
+    pub fallback_from_attempt_id: Option<Uuid>,
 }
 
 impl TaskAttempt {
@@ -149,6 +151,7 @@ impl TaskAttempt {
                               pr_merged_at AS "pr_merged_at: DateTime<Utc>",
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              fallback_from_attempt_id AS "fallback_from_attempt_id: Uuid",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -174,6 +177,7 @@ impl TaskAttempt {
                               pr_merged_at AS "pr_merged_at: DateTime<Utc>",
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              fallback_from_attempt_id AS "fallback_from_attempt_id: Uuid",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -210,6 +214,7 @@ impl TaskAttempt {
                        ta.pr_merged_at      AS "pr_merged_at: DateTime<Utc>",
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       ta.fallback_from_attempt_id AS "fallback_from_attempt_id: Uuid",
                        ta.created_at        AS "created_at!: DateTime<Utc>",
                        ta.updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts ta
@@ -289,6 +294,23 @@ impl TaskAttempt {
         Ok(())
     }
 
+    /// Delete an attempt and its execution history; subtasks created from it are kept but
+    /// no longer point at it
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "UPDATE tasks SET parent_task_attempt = NULL, updated_at = datetime('now') WHERE parent_task_attempt = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query!("DELETE FROM task_attempts WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             TaskAttempt,
@@ -305,6 +327,7 @@ impl TaskAttempt {
                        pr_merged_at      AS "pr_merged_at: DateTime<Utc>",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       fallback_from_attempt_id AS "fallback_from_attempt_id: Uuid",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -331,6 +354,7 @@ impl TaskAttempt {
                        pr_merged_at      AS "pr_merged_at: DateTime<Utc>",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       fallback_from_attempt_id AS "fallback_from_attempt_id: Uuid",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -472,7 +496,7 @@ impl TaskAttempt {
     }
 
     pub async fn create(
-        pool: &SqlitePool,
+        executor: impl SqliteExecutor<'_>,
         data: &CreateTaskAttempt,
         task_id: Uuid,
     ) -> Result<Self, TaskAttemptError> {
@@ -481,9 +505,9 @@ impl TaskAttempt {
         // Insert the record into the database
         Ok(sqlx::query_as!(
             TaskAttempt,
-            r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, base_branch, merge_commit, profile, pr_url, pr_number, pr_status, pr_merged_at, worktree_deleted, setup_completed_at)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, base_branch, merge_commit, profile as "profile!",  pr_url, pr_number, pr_status, pr_merged_at as "pr_merged_at: DateTime<Utc>", worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, base_branch, merge_commit, profile, pr_url, pr_number, pr_status, pr_merged_at, worktree_deleted, setup_completed_at, fallback_from_attempt_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, base_branch, merge_commit, profile as "profile!",  pr_url, pr_number, pr_status, pr_merged_at as "pr_merged_at: DateTime<Utc>", worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", fallback_from_attempt_id as "fallback_from_attempt_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             attempt_id,
             task_id,
             Option::<String>::None, // Container isn't known yet
@@ -496,9 +520,10 @@ impl TaskAttempt {
             Option::<String>::None, // pr_status is None during creation
             Option::<DateTime<Utc>>::None, // pr_merged_at is None during creation
             false, // worktree_deleted is false during creation
-            Option::<DateTime<Utc>>::None // setup_completed_at is None during creation
+            Option::<DateTime<Utc>>::None, // setup_completed_at is None during creation
+            data.fallback_from_attempt_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?)
     }
 
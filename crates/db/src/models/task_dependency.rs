@@ -0,0 +1,121 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ProfileVariantLabel;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqliteExecutor, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskStatus};
+
+/// `task_id` is blocked by `blocked_by_task_id` until that task is done
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskDependency {
+    pub task_id: Uuid,
+    pub blocked_by_task_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskDependency {
+    pub blocked_by_task_id: Uuid,
+}
+
+impl TaskDependency {
+    /// Find the tasks that `task_id` is directly blocked by
+    pub async fn find_blockers(pool: &SqlitePool, task_id: Uuid) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.auto_start_profile as "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.blocked_by_task_id
+               WHERE td.task_id = $1
+               ORDER BY td.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Find tasks that should start on their own now: they have an auto-start profile, are
+    /// still to do without any attempts, and every task they are blocked by is done
+    pub async fn find_unblocked_auto_start_tasks(
+        pool: &SqlitePool,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.auto_start_profile as "auto_start_profile: sqlx::types::Json<ProfileVariantLabel>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               WHERE t.auto_start_profile IS NOT NULL
+               AND t.status = 'todo'
+               AND EXISTS (SELECT 1 FROM task_dependencies td WHERE td.task_id = t.id)
+               AND NOT EXISTS (
+                   SELECT 1 FROM task_dependencies td
+                   JOIN tasks blocker ON blocker.id = td.blocked_by_task_id
+                   WHERE td.task_id = t.id AND blocker.status != 'done'
+               )
+               AND NOT EXISTS (SELECT 1 FROM task_attempts ta WHERE ta.task_id = t.id)
+               ORDER BY t.created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Whether `task_id` is blocked by `blocked_by_task_id`, directly or through other tasks
+    pub async fn is_blocked_by(
+        executor: impl SqliteExecutor<'_>,
+        task_id: Uuid,
+        blocked_by_task_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"WITH RECURSIVE blockers(id) AS (
+                   SELECT blocked_by_task_id FROM task_dependencies WHERE task_id = $1
+                   UNION
+                   SELECT td.blocked_by_task_id
+                   FROM task_dependencies td
+                   JOIN blockers b ON td.task_id = b.id
+               )
+               SELECT EXISTS (SELECT 1 FROM blockers WHERE id = $2) as "blocked!: bool""#,
+            task_id,
+            blocked_by_task_id
+        )
+        .fetch_one(executor)
+        .await?;
+        Ok(result.blocked)
+    }
+
+    pub async fn create(
+        executor: impl SqliteExecutor<'_>,
+        task_id: Uuid,
+        blocked_by_task_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskDependency,
+            r#"INSERT INTO task_dependencies (task_id, blocked_by_task_id)
+               VALUES ($1, $2)
+               ON CONFLICT (task_id, blocked_by_task_id) DO UPDATE SET task_id = excluded.task_id
+               RETURNING
+                task_id as "task_id!: Uuid",
+                blocked_by_task_id as "blocked_by_task_id!: Uuid",
+                created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            blocked_by_task_id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        blocked_by_task_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_dependencies WHERE task_id = $1 AND blocked_by_task_id = $2",
+            task_id,
+            blocked_by_task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool, Type};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ToolApprovalError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Tool approval not found")]
+    NotFound,
+    #[error("Tool use has already been approved or denied")]
+    AlreadyAnswered,
+}
+
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(type_name = "tool_approval_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ToolApprovalStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+/// A tool use the agent asked permission for
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ToolApproval {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    pub tool_name: String,
+    #[ts(type = "JsonValue")]
+    pub tool_input: sqlx::types::Json<Value>,
+    pub status: ToolApprovalStatus,
+    pub reason: Option<String>, // Shown to the agent when the tool use is denied
+    pub created_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateToolApproval {
+    pub execution_process_id: Uuid,
+    pub tool_name: String,
+    pub tool_input: Value,
+}
+
+impl ToolApproval {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ToolApproval,
+            r#"SELECT
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                tool_name,
+                tool_input as "tool_input!: sqlx::types::Json<Value>",
+                status as "status!: ToolApprovalStatus",
+                reason,
+                created_at as "created_at!: DateTime<Utc>",
+                responded_at as "responded_at?: DateTime<Utc>"
+               FROM tool_approvals
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Find all approvals requested by the execution processes of a task attempt
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ToolApproval,
+            r#"SELECT
+                a.id as "id!: Uuid",
+                a.execution_process_id as "execution_process_id!: Uuid",
+                a.tool_name,
+                a.tool_input as "tool_input!: sqlx::types::Json<Value>",
+                a.status as "status!: ToolApprovalStatus",
+                a.reason,
+                a.created_at as "created_at!: DateTime<Utc>",
+                a.responded_at as "responded_at?: DateTime<Utc>"
+               FROM tool_approvals a
+               JOIN execution_processes ep ON ep.id = a.execution_process_id
+               WHERE ep.task_attempt_id = $1
+               ORDER BY a.created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateToolApproval,
+        id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let tool_input = sqlx::types::Json(&data.tool_input);
+        sqlx::query_as!(
+            ToolApproval,
+            r#"INSERT INTO tool_approvals (id, execution_process_id, tool_name, tool_input)
+               VALUES ($1, $2, $3, $4)
+               RETURNING
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                tool_name,
+                tool_input as "tool_input!: sqlx::types::Json<Value>",
+                status as "status!: ToolApprovalStatus",
+                reason,
+                created_at as "created_at!: DateTime<Utc>",
+                responded_at as "responded_at?: DateTime<Utc>""#,
+            id,
+            data.execution_process_id,
+            data.tool_name,
+            tool_input
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Answer a pending approval. Returns `None` if it was already answered.
+    pub async fn respond(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: ToolApprovalStatus,
+        reason: Option<String>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ToolApproval,
+            r#"UPDATE tool_approvals
+               SET status = $2, reason = $3, responded_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'pending'
+               RETURNING
+                id as "id!: Uuid",
+                execution_process_id as "execution_process_id!: Uuid",
+                tool_name,
+                tool_input as "tool_input!: sqlx::types::Json<Value>",
+                status as "status!: ToolApprovalStatus",
+                reason,
+                created_at as "created_at!: DateTime<Utc>",
+                responded_at as "responded_at?: DateTime<Utc>""#,
+            id,
+            status,
+            reason
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -7,6 +7,7 @@ use ts_rs::TS;
 
 use crate::{
     actions::Executable,
+    env::ExecutionEnv,
     executors::{CodingAgent, ExecutorError, StandardCodingAgentExecutor},
     profile::ProfileVariantLabel,
 };
@@ -19,8 +20,13 @@ pub struct CodingAgentInitialRequest {
 
 #[async_trait]
 impl Executable for CodingAgentInitialRequest {
-    async fn spawn(&self, current_dir: &PathBuf) -> Result<AsyncGroupChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &PathBuf,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
         let executor = CodingAgent::from_profile_variant_label(&self.profile_variant_label)?;
-        executor.spawn(current_dir, &self.prompt).await
+        let env = env.with_writable_paths(executor.config_paths());
+        executor.spawn(current_dir, &self.prompt, &env).await
     }
 }
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// One of the named processes a project starts as its dev server, e.g. a frontend and an API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct DevServer {
+    pub name: String,
+    pub script: String,
+    /// How to tell the process is up; without one it counts as healthy while it runs
+    #[serde(default)]
+    pub health_check: Option<DevServerHealthCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[serde(tag = "type")]
+pub enum DevServerHealthCheck {
+    /// Healthy while a GET of the URL succeeds
+    Url { url: String },
+    /// Healthy once a line of the process's output matches the pattern
+    LogRegex { pattern: String },
+}
+
+/// Check that every dev server has a unique name, something to run and a usable health check
+pub fn validate_dev_servers(dev_servers: &[DevServer]) -> Result<(), String> {
+    let mut names = HashSet::new();
+    for (index, dev_server) in dev_servers.iter().enumerate() {
+        let name = dev_server.name.trim();
+        if name.is_empty() {
+            return Err(format!("Dev server {} needs a name", index + 1));
+        }
+        if !names.insert(name) {
+            return Err(format!("There is more than one dev server named '{name}'"));
+        }
+        if dev_server.script.trim().is_empty() {
+            return Err(format!("Dev server '{name}' has nothing to run"));
+        }
+        match &dev_server.health_check {
+            Some(DevServerHealthCheck::Url { url })
+                if !url.starts_with("http://") && !url.starts_with("https://") =>
+            {
+                return Err(format!(
+                    "The health check URL of dev server '{name}' must start with http:// or https://"
+                ));
+            }
+            Some(DevServerHealthCheck::LogRegex { pattern }) if Regex::new(pattern).is_err() => {
+                return Err(format!(
+                    "The health check pattern of dev server '{name}' is not a valid regex"
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dev_server(name: &str, health_check: Option<DevServerHealthCheck>) -> DevServer {
+        DevServer {
+            name: name.to_string(),
+            script: "npm run dev".to_string(),
+            health_check,
+        }
+    }
+
+    #[test]
+    fn dev_servers_need_unique_names_and_valid_health_checks() {
+        let frontend = dev_server(
+            "frontend",
+            Some(DevServerHealthCheck::Url {
+                url: "http://localhost:3000".to_string(),
+            }),
+        );
+        let api = dev_server(
+            "api",
+            Some(DevServerHealthCheck::LogRegex {
+                pattern: r"listening on \d+".to_string(),
+            }),
+        );
+        assert!(validate_dev_servers(&[frontend.clone(), api]).is_ok());
+
+        assert!(validate_dev_servers(&[frontend.clone(), frontend]).is_err());
+        assert!(validate_dev_servers(&[dev_server(" ", None)]).is_err());
+        assert!(
+            validate_dev_servers(&[dev_server(
+                "frontend",
+                Some(DevServerHealthCheck::Url {
+                    url: "localhost:3000".to_string(),
+                }),
+            )])
+            .is_err()
+        );
+        assert!(
+            validate_dev_servers(&[dev_server(
+                "api",
+                Some(DevServerHealthCheck::LogRegex {
+                    pattern: "(".to_string(),
+                }),
+            )])
+            .is_err()
+        );
+    }
+}
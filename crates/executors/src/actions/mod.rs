@@ -9,12 +9,17 @@ use ts_rs::TS;
 use crate::{
     actions::{
         coding_agent_follow_up::CodingAgentFollowUpRequest,
-        coding_agent_initial::CodingAgentInitialRequest, script::ScriptRequest,
+        coding_agent_initial::CodingAgentInitialRequest, dev_server::DevServer,
+        pipeline::PipelineStepInfo, script::ScriptRequest,
     },
+    env::ExecutionEnv,
     executors::ExecutorError,
+    profile::ProfileVariantLabel,
 };
 pub mod coding_agent_follow_up;
 pub mod coding_agent_initial;
+pub mod dev_server;
+pub mod pipeline;
 pub mod script;
 
 #[enum_dispatch]
@@ -26,15 +31,47 @@ pub enum ExecutorActionType {
     ScriptRequest,
 }
 
+impl ExecutorActionType {
+    /// The profile variant used by coding agent actions
+    pub fn profile_variant_label(&self) -> Option<&ProfileVariantLabel> {
+        match self {
+            Self::CodingAgentInitialRequest(request) => Some(&request.profile_variant_label),
+            Self::CodingAgentFollowUpRequest(request) => Some(&request.profile_variant_label),
+            Self::ScriptRequest(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ExecutorAction {
     pub typ: ExecutorActionType,
     pub next_action: Option<Box<ExecutorAction>>,
+    /// Set on actions compiled from a project's pipeline
+    #[serde(default)]
+    pub pipeline_step: Option<PipelineStepInfo>,
+    /// Set on actions that start one of a project's dev servers
+    #[serde(default)]
+    pub dev_server: Option<DevServer>,
 }
 
 impl ExecutorAction {
     pub fn new(typ: ExecutorActionType, next_action: Option<Box<ExecutorAction>>) -> Self {
-        Self { typ, next_action }
+        Self {
+            typ,
+            next_action,
+            pipeline_step: None,
+            dev_server: None,
+        }
+    }
+
+    pub fn with_pipeline_step(mut self, pipeline_step: PipelineStepInfo) -> Self {
+        self.pipeline_step = Some(pipeline_step);
+        self
+    }
+
+    pub fn with_dev_server(mut self, dev_server: DevServer) -> Self {
+        self.dev_server = Some(dev_server);
+        self
     }
 
     pub fn typ(&self) -> &ExecutorActionType {
@@ -44,17 +81,33 @@ impl ExecutorAction {
     pub fn next_action(&self) -> Option<&Box<ExecutorAction>> {
         self.next_action.as_ref()
     }
+
+    pub fn pipeline_step(&self) -> Option<&PipelineStepInfo> {
+        self.pipeline_step.as_ref()
+    }
+
+    pub fn dev_server(&self) -> Option<&DevServer> {
+        self.dev_server.as_ref()
+    }
 }
 
 #[async_trait]
 #[enum_dispatch(ExecutorActionType)]
 pub trait Executable {
-    async fn spawn(&self, current_dir: &PathBuf) -> Result<AsyncGroupChild, ExecutorError>;
+    async fn spawn(
+        &self,
+        current_dir: &PathBuf,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError>;
 }
 
 #[async_trait]
 impl Executable for ExecutorAction {
-    async fn spawn(&self, current_dir: &PathBuf) -> Result<AsyncGroupChild, ExecutorError> {
-        self.typ.spawn(current_dir).await
+    async fn spawn(
+        &self,
+        current_dir: &PathBuf,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        self.typ.spawn(current_dir, env).await
     }
 }
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A named step of a project's post-agent pipeline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct PipelineStep {
+    pub name: String,
+    pub action: PipelineStepAction,
+    /// Whether the pipeline goes on to the next step when this one fails
+    #[serde(default)]
+    pub continue_on_failure: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[serde(tag = "type")]
+pub enum PipelineStepAction {
+    /// Run a bash script in the attempt's worktree
+    Script { script: String },
+    /// Send the coding agent a follow-up in its session
+    AgentFollowUp { prompt: String },
+}
+
+/// The pipeline step an executor action was compiled from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct PipelineStepInfo {
+    pub name: String,
+    pub continue_on_failure: bool,
+}
+
+impl PipelineStep {
+    pub fn info(&self) -> PipelineStepInfo {
+        PipelineStepInfo {
+            name: self.name.clone(),
+            continue_on_failure: self.continue_on_failure,
+        }
+    }
+
+    pub fn is_agent_follow_up(&self) -> bool {
+        matches!(self.action, PipelineStepAction::AgentFollowUp { .. })
+    }
+}
+
+/// Check that every step of a pipeline has a name and something to run
+pub fn validate_pipeline(steps: &[PipelineStep]) -> Result<(), String> {
+    for (index, step) in steps.iter().enumerate() {
+        if step.name.trim().is_empty() {
+            return Err(format!("Pipeline step {} needs a name", index + 1));
+        }
+        let empty = match &step.action {
+            PipelineStepAction::Script { script } => script.trim().is_empty(),
+            PipelineStepAction::AgentFollowUp { prompt } => prompt.trim().is_empty(),
+        };
+        if empty {
+            return Err(format!("Pipeline step '{}' has nothing to run", step.name));
+        }
+    }
+    Ok(())
+}
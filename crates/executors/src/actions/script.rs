@@ -1,17 +1,33 @@
-use std::path::PathBuf;
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
 
 use async_trait::async_trait;
 use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use serde::{Deserialize, Serialize};
-use tokio::process::Command;
 use ts_rs::TS;
 use utils::shell::get_shell_command;
 
-use crate::{actions::Executable, executors::ExecutorError};
+use crate::{actions::Executable, env::ExecutionEnv, executors::ExecutorError};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub enum ScriptRequestLanguage {
     Bash,
+    Python,
+    Node,
+}
+
+impl ScriptRequestLanguage {
+    /// The program, and its argument that takes the script's source
+    fn interpreter(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Bash => get_shell_command(),
+            Self::Python if cfg!(windows) => ("python", "-c"),
+            Self::Python => ("python3", "-c"),
+            Self::Node => ("node", "-e"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -19,6 +35,8 @@ pub enum ScriptContext {
     SetupScript,
     CleanupScript,
     DevServer,
+    TestScript,
+    PipelineStep,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -26,23 +44,84 @@ pub struct ScriptRequest {
     pub script: String,
     pub language: ScriptRequestLanguage,
     pub context: ScriptContext,
+    /// Directory to run the script in, relative to the worktree root
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// Environment variables for the script, on top of the project's
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Seconds the script may run for before it is stopped
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl ScriptRequest {
+    pub fn new(script: String, language: ScriptRequestLanguage, context: ScriptContext) -> Self {
+        Self {
+            script,
+            language,
+            context,
+            working_dir: None,
+            env: BTreeMap::new(),
+            timeout_secs: None,
+        }
+    }
+
+    fn current_dir(&self, worktree: &Path) -> Result<PathBuf, ExecutorError> {
+        match &self.working_dir {
+            Some(dir) if !is_valid_working_dir(dir) => {
+                Err(ExecutorError::InvalidWorkingDir(dir.clone()))
+            }
+            Some(dir) => Ok(worktree.join(dir)),
+            None => Ok(worktree.to_path_buf()),
+        }
+    }
+}
+
+/// Whether a script working directory stays inside the worktree: a relative path without
+/// any `..`
+pub fn is_valid_working_dir(dir: &str) -> bool {
+    Path::new(dir)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
 }
 
 #[async_trait]
 impl Executable for ScriptRequest {
-    async fn spawn(&self, current_dir: &PathBuf) -> Result<AsyncGroupChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
-        let mut command = Command::new(shell_cmd);
+    async fn spawn(
+        &self,
+        current_dir: &PathBuf,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let (program, script_arg) = self.language.interpreter();
+        let mut command = env.command(program);
         command
             .kill_on_drop(true)
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
-            .arg(shell_arg)
+            .arg(script_arg)
             .arg(&self.script)
-            .current_dir(current_dir);
+            .current_dir(self.current_dir(current_dir)?);
+        env.apply_to_command(&mut command);
+        command.envs(&self.env);
 
         let child = command.group_spawn()?;
 
         Ok(child)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn working_dir_stays_in_the_worktree() {
+        assert!(is_valid_working_dir("frontend"));
+        assert!(is_valid_working_dir("./packages/web"));
+        assert!(!is_valid_working_dir("../other-repo"));
+        assert!(!is_valid_working_dir("packages/../../other-repo"));
+        assert!(!is_valid_working_dir("/etc"));
+    }
+}
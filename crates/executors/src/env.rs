@@ -0,0 +1,336 @@
+use std::{collections::BTreeMap, ffi::OsString, path::PathBuf};
+
+use tokio::process::Command;
+use utils::shell::resolve_executable_path;
+use uuid::Uuid;
+
+/// How a spawned process runs: extra environment variables on top of the inherited
+/// environment, an optional sandbox and resource limits
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecutionEnv {
+    pub vars: BTreeMap<String, String>,
+    /// Run the process in a sandbox instead of with the user's full privileges
+    pub sandbox: Option<Sandbox>,
+    pub resource_limits: ResourceLimits,
+    /// A cgroup (v2) directory to start the process in, whose limits then hold for
+    /// everything it starts together
+    pub cgroup: Option<PathBuf>,
+    /// The execution process the command runs for, which names files written for it
+    pub execution_id: Option<Uuid>,
+}
+
+impl ExecutionEnv {
+    pub fn new(vars: BTreeMap<String, String>) -> Self {
+        Self {
+            vars,
+            sandbox: None,
+            resource_limits: ResourceLimits::default(),
+            cgroup: None,
+            execution_id: None,
+        }
+    }
+
+    pub fn with_resource_limits(mut self, resource_limits: ResourceLimits) -> Self {
+        self.resource_limits = resource_limits;
+        self
+    }
+
+    pub fn with_cgroup(mut self, cgroup: Option<PathBuf>) -> Self {
+        self.cgroup = cgroup;
+        self
+    }
+
+    pub fn with_execution_id(mut self, execution_id: Uuid) -> Self {
+        self.execution_id = Some(execution_id);
+        self
+    }
+
+    pub fn with_sandbox(mut self, sandbox: Option<Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Also allow reads of `paths` when sandboxed, where they'd otherwise be hidden
+    pub fn with_readable_paths(&self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let mut env = self.clone();
+        if let Some(sandbox) = &mut env.sandbox {
+            sandbox.readable_paths.extend(paths);
+        }
+        env
+    }
+
+    /// Also allow writes to `paths` when sandboxed
+    pub fn with_writable_paths(&self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let mut env = self.clone();
+        if let Some(sandbox) = &mut env.sandbox {
+            sandbox.writable_paths.extend(paths);
+        }
+        env
+    }
+
+    /// A command running `program`, inside the sandbox if there is one
+    pub fn command(&self, program: &str) -> Command {
+        match &self.sandbox {
+            Some(sandbox) => {
+                let mut command = Command::new(Sandbox::PROGRAM);
+                command.args(sandbox.args()).arg(program);
+                command
+            }
+            None => Command::new(program),
+        }
+    }
+
+    pub fn apply_to_command(&self, command: &mut Command) {
+        command.envs(&self.vars);
+        self.resource_limits.apply_to_command(command);
+        if let Some(cgroup) = &self.cgroup {
+            join_cgroup(command, cgroup);
+        }
+    }
+}
+
+/// Move the spawned process into `cgroup` before it runs, so nothing it starts escapes
+fn join_cgroup(command: &mut Command, cgroup: &std::path::Path) {
+    #[cfg(target_os = "linux")]
+    {
+        use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+        use nix::{fcntl::OFlag, sys::stat::Mode};
+
+        let Ok(procs) = CString::new(cgroup.join("cgroup.procs").as_os_str().as_bytes()) else {
+            return;
+        };
+        // SAFETY: the closure only calls open, write and close, which are
+        // async-signal-safe, and doesn't allocate
+        unsafe {
+            command.pre_exec(move || {
+                let fd = nix::fcntl::open(procs.as_c_str(), OFlag::O_WRONLY, Mode::empty())?;
+                // Writing 0 moves the writing process
+                let written = nix::unistd::write(std::os::fd::BorrowedFd::borrow_raw(fd), b"0");
+                let _ = nix::unistd::close(fd);
+                written?;
+                Ok(())
+            });
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    tracing::warn!(
+        "cgroups are only supported on Linux, ignoring {}",
+        cgroup.display()
+    );
+}
+
+/// Resource limits for an execution, which hold for all of its processes together. Where
+/// the execution gets its own cgroup the kernel enforces the memory and process limits,
+/// otherwise vibe-kanban stops the execution once its process group exceeds them.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResourceLimits {
+    /// Memory of all processes together. Each process also can't grow its data segment
+    /// (heap and private mappings) past it.
+    pub max_memory_mb: Option<u64>,
+    /// CPU time of all processes together. A single process that uses it up gets SIGXCPU
+    /// and then SIGKILL.
+    pub max_cpu_secs: Option<u64>,
+    /// Processes running at once
+    pub max_processes: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn max_memory_bytes(&self) -> Option<u64> {
+        self.max_memory_mb.map(|mb| mb.saturating_mul(1024 * 1024))
+    }
+
+    /// Per-process limits, which back up the execution-wide ones
+    fn apply_to_command(self, command: &mut Command) {
+        if self.max_memory_mb.is_none() && self.max_cpu_secs.is_none() {
+            return;
+        }
+        #[cfg(unix)]
+        {
+            use nix::sys::resource::{Resource, setrlimit};
+
+            let limits = [
+                (Resource::RLIMIT_DATA, self.max_memory_bytes()),
+                (Resource::RLIMIT_CPU, self.max_cpu_secs),
+            ];
+            // SAFETY: the closure only calls setrlimit, which is async-signal-safe
+            unsafe {
+                command.pre_exec(move || {
+                    for (resource, limit) in limits {
+                        if let Some(limit) = limit {
+                            setrlimit(resource, limit, limit)?;
+                        }
+                    }
+                    Ok(())
+                });
+            }
+        }
+        #[cfg(not(unix))]
+        tracing::warn!("Resource limits are only supported on Unix, ignoring {self:?}");
+    }
+}
+
+/// A bubblewrap sandbox: the filesystem is read-only apart from `writable_paths` and a
+/// private `/tmp`, the home directory is hidden apart from `readable_paths` and
+/// `writable_paths`, and the network can be cut off
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sandbox {
+    pub readable_paths: Vec<PathBuf>,
+    pub writable_paths: Vec<PathBuf>,
+    pub network: bool,
+}
+
+impl Sandbox {
+    const PROGRAM: &'static str = "bwrap";
+
+    pub fn new(network: bool) -> Self {
+        Self {
+            readable_paths: Self::home_program_paths(),
+            // npx caches the packages it runs, like most coding agents, in ~/.npm
+            writable_paths: dirs::home_dir()
+                .map(|home| home.join(".npm"))
+                .into_iter()
+                .collect(),
+            network,
+        }
+    }
+
+    /// Directories on `PATH` inside the home directory, so programs installed there (by
+    /// nvm, pipx or cargo, say) still run. Packages linked from an npm `bin` dir live in
+    /// the `lib` dir next to it.
+    fn home_program_paths() -> Vec<PathBuf> {
+        let (Some(home), Some(path)) = (dirs::home_dir(), std::env::var_os("PATH")) else {
+            return vec![];
+        };
+        let mut paths = vec![];
+        for dir in std::env::split_paths(&path) {
+            if !dir.starts_with(&home) || dir == home {
+                continue;
+            }
+            if dir.ends_with("bin")
+                && let Some(prefix) = dir.parent().filter(|prefix| *prefix != home)
+            {
+                paths.push(prefix.join("lib"));
+            }
+            paths.push(dir);
+        }
+        paths
+    }
+
+    /// Whether sandboxes can be created on this machine
+    pub fn is_supported() -> bool {
+        cfg!(target_os = "linux") && resolve_executable_path(Self::PROGRAM).is_some()
+    }
+
+    /// Arguments to `bwrap` that precede the sandboxed program
+    fn args(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = [
+            "--ro-bind",
+            "/",
+            "/",
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+            "--tmpfs",
+            "/tmp",
+            "--setenv",
+            "TMPDIR",
+            "/tmp",
+        ]
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+        // Hide the home directory, with its SSH keys and credentials
+        if let Some(home) = dirs::home_dir().filter(|home| home.parent().is_some()) {
+            args.push("--tmpfs".into());
+            args.push(home.into());
+        }
+        // Bound after the tmpfs mounts, so paths under /tmp (like worktrees) and the home
+        // directory stay visible. Readable paths come first, so writable ones inside them
+        // (like a repository's objects in its git dir) stay writable. Paths that don't
+        // exist yet, like an agent's config dir on first use, are skipped.
+        for path in &self.readable_paths {
+            args.push("--ro-bind-try".into());
+            args.push(path.into());
+            args.push(path.into());
+        }
+        for path in &self.writable_paths {
+            args.push("--bind-try".into());
+            args.push(path.into());
+            args.push(path.into());
+        }
+        if !self.network {
+            args.push("--unshare-net".into());
+        }
+        // Kill the sandboxed program if vibe-kanban exits without stopping it
+        args.push("--die-with-parent".into());
+        args.push("--".into());
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sandbox_binds_writable_paths_after_tmpfs() {
+        let env = ExecutionEnv::default()
+            .with_sandbox(Some(Sandbox::new(false)))
+            .with_writable_paths([PathBuf::from("/tmp/worktrees/attempt")]);
+        let args = env.sandbox.unwrap().args();
+
+        let position = |arg: &str| args.iter().position(|a| a == arg).unwrap();
+        assert!(position("--tmpfs") < position("/tmp/worktrees/attempt"));
+        assert_eq!(args[position("/tmp/worktrees/attempt") - 1], "--bind-try");
+        assert!(args.contains(&"--unshare-net".into()));
+        assert_eq!(args.last().unwrap(), "--");
+    }
+
+    #[test]
+    fn sandbox_hides_home_apart_from_bound_paths() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+        let git_dir = home.join("repo/.git");
+        let env = ExecutionEnv::default()
+            .with_sandbox(Some(Sandbox::new(true)))
+            .with_readable_paths([git_dir.clone()])
+            .with_writable_paths([git_dir.join("objects")]);
+        let args = env.sandbox.unwrap().args();
+
+        let position = |arg: &std::path::Path| args.iter().position(|a| a == arg).unwrap();
+        assert_eq!(args[position(&home) - 1], "--tmpfs");
+        assert!(position(&home) < position(&git_dir));
+        assert_eq!(args[position(&git_dir) - 1], "--ro-bind-try");
+        assert!(position(&git_dir) < position(&git_dir.join("objects")));
+        assert_eq!(args[position(&git_dir.join("objects")) - 1], "--bind-try");
+        assert!(!args.contains(&"--unshare-net".into()));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn resource_limits_apply_to_the_spawned_process() {
+        let env = ExecutionEnv::default().with_resource_limits(ResourceLimits {
+            max_cpu_secs: Some(30),
+            ..Default::default()
+        });
+        let mut command = env.command("sh");
+        command.args(["-c", "ulimit -t"]);
+        env.apply_to_command(&mut command);
+
+        let output = command.output().await.unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "30");
+    }
+
+    #[test]
+    fn writable_paths_are_ignored_without_sandbox() {
+        let env = ExecutionEnv::default().with_writable_paths([PathBuf::from("/home")]);
+        assert_eq!(env, ExecutionEnv::default());
+    }
+}
@@ -0,0 +1,474 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::{fs, task::JoinHandle};
+use ts_rs::TS;
+use utils::{
+    diff::create_unified_diff, msg_store::MsgStore, path::make_path_relative,
+    shell::get_shell_command,
+};
+
+use crate::{
+    command::CommandBuilder,
+    env::ExecutionEnv,
+    executors::{ExecutorError, StandardCodingAgentExecutor},
+    logs::{
+        ActionType, FileChange, NormalizedEntry, NormalizedEntryType,
+        stderr_processor::normalize_stderr_logs,
+        utils::{EntryIndexProvider, patch::ConversationPatch},
+    },
+};
+
+/// An executor that uses Aider to process tasks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct Aider {
+    pub command: CommandBuilder,
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Aider {
+    async fn spawn(
+        &self,
+        current_dir: &PathBuf,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let session_id = Self::session_id(current_dir);
+
+        // A new attempt always starts with a fresh chat history
+        let _ = fs::remove_file(Self::chat_history_path(&session_id)).await;
+
+        self.spawn_with_history(current_dir, prompt, &session_id, false, env)
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &PathBuf,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let chat_history_path = Self::chat_history_path(session_id);
+        if !chat_history_path.exists() {
+            return Err(ExecutorError::FollowUpNotSupported(format!(
+                "No existing Aider chat history found for this worktree at {chat_history_path:?}"
+            )));
+        }
+
+        self.spawn_with_history(current_dir, prompt, session_id, true, env)
+            .await
+    }
+
+    /// Aider prints plain text. Assistant prose is grouped into messages, and
+    /// SEARCH/REPLACE edit blocks are turned into `FileEdit` tool uses.
+    fn normalize_logs(
+        &self,
+        msg_store: Arc<MsgStore>,
+        worktree_path: &PathBuf,
+    ) -> Vec<JoinHandle<()>> {
+        let entry_index_provider = EntryIndexProvider::new();
+        let stderr = normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
+
+        // The chat history file is keyed by worktree, which is what follow-ups resume from
+        msg_store.push_session_id(Self::session_id(worktree_path));
+
+        let worktree_path = worktree_path.to_string_lossy().to_string();
+        let stdout = tokio::spawn(async move {
+            let mut lines = msg_store.stdout_lines_stream();
+            let mut processor = AiderLogProcessor::new(worktree_path);
+
+            while let Some(Ok(line)) = lines.next().await {
+                for entry in processor.process_line(&line) {
+                    let id = entry_index_provider.next();
+                    msg_store.push_patch(ConversationPatch::add_normalized_entry(id, entry));
+                }
+            }
+
+            for entry in processor.flush() {
+                let id = entry_index_provider.next();
+                msg_store.push_patch(ConversationPatch::add_normalized_entry(id, entry));
+            }
+        });
+
+        vec![stderr, stdout]
+    }
+}
+
+impl Aider {
+    async fn spawn_with_history(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        restore_chat_history: bool,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let sessions_dir = Self::get_sessions_base_dir();
+        fs::create_dir_all(&sessions_dir).await?;
+
+        // Aider only takes a one-shot message as an argument or a file, so pass it via a file
+        let message_path = sessions_dir.join(format!("{session_id}.message.md"));
+        fs::write(&message_path, prompt).await?;
+
+        let mut args = vec![
+            "--chat-history-file".to_string(),
+            format!("\"{}\"", Self::chat_history_path(session_id).display()),
+            "--input-history-file".to_string(),
+            format!(
+                "\"{}\"",
+                sessions_dir
+                    .join(format!("{session_id}.input.history"))
+                    .display()
+            ),
+            "--message-file".to_string(),
+            format!("\"{}\"", message_path.display()),
+        ];
+        if restore_chat_history {
+            args.push("--restore-chat-history".to_string());
+        }
+
+        let (shell_cmd, shell_arg) = get_shell_command();
+        let aider_command = self.command.build_follow_up(&args);
+
+        let mut command = env.command(shell_cmd);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .arg(shell_arg)
+            .arg(aider_command)
+            .env("PYTHONUNBUFFERED", "1");
+
+        env.apply_to_command(&mut command);
+        let child = command.group_spawn()?;
+
+        Ok(child)
+    }
+
+    fn session_id(worktree_path: &Path) -> String {
+        worktree_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    fn chat_history_path(session_id: &str) -> PathBuf {
+        Self::get_sessions_base_dir().join(format!("{session_id}.chat.history.md"))
+    }
+
+    pub(crate) fn get_sessions_base_dir() -> PathBuf {
+        utils::path::get_vibe_kanban_temp_dir().join("aider_sessions")
+    }
+}
+
+/// Status lines Aider prints around a response that are not part of the assistant's reply
+const SYSTEM_LINE_PREFIXES: &[&str] = &[
+    "Aider v",
+    "Main model:",
+    "Weak model:",
+    "Editor model:",
+    "Model:",
+    "Git repo:",
+    "Repo-map:",
+    "Tokens:",
+    "Cost:",
+    "Restored previous conversation history",
+];
+
+const ERROR_LINE_PREFIXES: &[&str] = &[
+    "The LLM did not conform to the edit format",
+    "Only 3 reflections allowed",
+    "litellm.",
+];
+
+/// A SEARCH/REPLACE block that is still being read
+struct AiderEditBlock {
+    path: String,
+    search: Vec<String>,
+    replace: Option<Vec<String>>,
+}
+
+/// Handles log processing and interpretation for the Aider executor
+struct AiderLogProcessor {
+    worktree_path: String,
+    assistant_lines: Vec<String>,
+    edit_block: Option<AiderEditBlock>,
+    last_edit_path: Option<String>,
+    skip_closing_fence: bool,
+}
+
+impl AiderLogProcessor {
+    fn new(worktree_path: String) -> Self {
+        Self {
+            worktree_path,
+            assistant_lines: Vec::new(),
+            edit_block: None,
+            last_edit_path: None,
+            skip_closing_fence: false,
+        }
+    }
+
+    /// Feed a single stdout line, returning any entries that are complete
+    fn process_line(&mut self, line: &str) -> Vec<NormalizedEntry> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let skip_closing_fence = std::mem::take(&mut self.skip_closing_fence);
+
+        if let Some(block) = self.edit_block.as_mut() {
+            match block.replace.as_mut() {
+                None if line.trim() == "=======" => block.replace = Some(Vec::new()),
+                None => block.search.push(line.to_string()),
+                Some(_) if line.trim_start().starts_with(">>>>>>> REPLACE") => {
+                    let block = self.edit_block.take().unwrap();
+                    self.skip_closing_fence = true;
+                    return vec![self.edit_block_to_entry(block)];
+                }
+                Some(replace) => replace.push(line.to_string()),
+            }
+            return vec![];
+        }
+
+        if line.trim_start().starts_with("<<<<<<< SEARCH") {
+            let path = self.take_edit_path();
+            self.last_edit_path = Some(path.clone());
+            let entries = self.flush();
+            self.edit_block = Some(AiderEditBlock {
+                path,
+                search: Vec::new(),
+                replace: None,
+            });
+            return entries;
+        }
+
+        if skip_closing_fence && line.trim_start().starts_with("```") {
+            return vec![];
+        }
+
+        // The edit blocks already show what changed
+        if line.starts_with("Applied edit to ") {
+            return vec![];
+        }
+
+        let entry_type = if SYSTEM_LINE_PREFIXES.iter().any(|p| line.starts_with(p)) {
+            NormalizedEntryType::SystemMessage
+        } else if ERROR_LINE_PREFIXES.iter().any(|p| line.starts_with(p)) {
+            NormalizedEntryType::ErrorMessage
+        } else {
+            self.assistant_lines.push(line.to_string());
+            return vec![];
+        };
+
+        let mut entries = self.flush();
+        entries.push(NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: line.to_string(),
+            metadata: None,
+        });
+        entries
+    }
+
+    /// Emit any buffered assistant text as a single message
+    fn flush(&mut self) -> Vec<NormalizedEntry> {
+        let content = self.assistant_lines.join("\n").trim().to_string();
+        self.assistant_lines.clear();
+        if content.is_empty() {
+            return vec![];
+        }
+        vec![NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content,
+            metadata: None,
+        }]
+    }
+
+    /// Aider prints the file name and an opening fence right before `<<<<<<< SEARCH`
+    /// (the fence comes first in the diff-fenced format). Pull those back out of
+    /// the assistant text.
+    fn take_edit_path(&mut self) -> String {
+        let is_fence = |line: &String| line.trim_start().starts_with("```");
+
+        while self.assistant_lines.last().is_some_and(is_fence) {
+            self.assistant_lines.pop();
+        }
+        let path = match self.assistant_lines.last() {
+            Some(line) if !line.trim().is_empty() && !line.contains(' ') => {
+                self.assistant_lines.pop().map(|p| p.trim().to_string())
+            }
+            _ => None,
+        };
+        while self.assistant_lines.last().is_some_and(is_fence) {
+            self.assistant_lines.pop();
+        }
+
+        path.or_else(|| self.last_edit_path.clone())
+            .unwrap_or_default()
+    }
+
+    fn edit_block_to_entry(&self, block: AiderEditBlock) -> NormalizedEntry {
+        let path = make_path_relative(&block.path, &self.worktree_path);
+        let search = block.search.join("\n");
+        let replace = block.replace.unwrap_or_default().join("\n");
+
+        let change = if search.trim().is_empty() {
+            FileChange::Write { content: replace }
+        } else {
+            FileChange::Edit {
+                unified_diff: create_unified_diff(&path, &search, &replace),
+                has_line_numbers: false,
+            }
+        };
+
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ToolUse {
+                tool_name: "edit".to_string(),
+                action_type: ActionType::FileEdit {
+                    path: path.clone(),
+                    changes: vec![change],
+                },
+                result: None,
+            },
+            content: format!("`{path}`"),
+            metadata: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executors::CodingAgent;
+
+    fn process(logs: &str) -> Vec<NormalizedEntry> {
+        let mut processor = AiderLogProcessor::new("/tmp/worktree".to_string());
+        let mut entries = Vec::new();
+        for line in logs.lines() {
+            entries.extend(processor.process_line(line));
+        }
+        entries.extend(processor.flush());
+        entries
+    }
+
+    #[test]
+    fn test_sessions_dir_is_writable_in_sandbox() {
+        let aider = CodingAgent::Aider(Aider {
+            command: CommandBuilder::new("aider"),
+        });
+        assert!(
+            aider
+                .config_paths()
+                .contains(&Aider::get_sessions_base_dir())
+        );
+    }
+
+    #[test]
+    fn test_search_replace_block_becomes_file_edit() {
+        let logs = r#"Aider v0.86.1
+Main model: anthropic/claude-sonnet-4-20250514 with diff edit format
+I'll rename the greeting.
+
+src/main.rs
+```rust
+<<<<<<< SEARCH
+    println!("hello");
+=======
+    println!("hello, world");
+>>>>>>> REPLACE
+```
+
+Applied edit to src/main.rs
+Tokens: 2.1k sent, 150 received. Cost: $0.01 message, $0.01 session."#;
+
+        let entries = process(logs);
+        assert_eq!(entries.len(), 5);
+        assert!(matches!(
+            entries[0].entry_type,
+            NormalizedEntryType::SystemMessage
+        ));
+        assert!(matches!(
+            entries[2].entry_type,
+            NormalizedEntryType::AssistantMessage
+        ));
+        assert_eq!(entries[2].content, "I'll rename the greeting.");
+
+        match &entries[3].entry_type {
+            NormalizedEntryType::ToolUse {
+                tool_name,
+                action_type: ActionType::FileEdit { path, changes },
+                ..
+            } => {
+                assert_eq!(tool_name, "edit");
+                assert_eq!(path, "src/main.rs");
+                match &changes[0] {
+                    FileChange::Edit { unified_diff, .. } => {
+                        assert!(unified_diff.contains("-    println!(\"hello\");"));
+                        assert!(unified_diff.contains("+    println!(\"hello, world\");"));
+                    }
+                    other => panic!("Expected edit change, got {other:?}"),
+                }
+            }
+            other => panic!("Expected FileEdit tool use, got {other:?}"),
+        }
+        assert!(entries[4].content.starts_with("Tokens:"));
+    }
+
+    #[test]
+    fn test_diff_fenced_block_with_new_file() {
+        let logs = r#"```
+notes/todo.md
+<<<<<<< SEARCH
+=======
+- write tests
+>>>>>>> REPLACE
+```"#;
+
+        let entries = process(logs);
+        assert_eq!(entries.len(), 1);
+        match &entries[0].entry_type {
+            NormalizedEntryType::ToolUse {
+                action_type: ActionType::FileEdit { path, changes },
+                ..
+            } => {
+                assert_eq!(path, "notes/todo.md");
+                assert!(matches!(
+                    &changes[0],
+                    FileChange::Write { content } if content == "- write tests"
+                ));
+            }
+            other => panic!("Expected FileEdit tool use, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_consecutive_blocks_reuse_previous_path() {
+        let logs = r#"src/lib.rs
+<<<<<<< SEARCH
+a
+=======
+b
+>>>>>>> REPLACE
+<<<<<<< SEARCH
+c
+=======
+d
+>>>>>>> REPLACE"#;
+
+        let entries = process(logs);
+        assert_eq!(entries.len(), 2);
+        for entry in &entries {
+            assert_eq!(entry.content, "`src/lib.rs`");
+        }
+    }
+}
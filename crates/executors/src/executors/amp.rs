@@ -5,7 +5,7 @@ use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use futures::StreamExt;
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::{io::AsyncWriteExt, task::JoinHandle};
 use ts_rs::TS;
 use utils::{
     diff::create_unified_diff, msg_store::MsgStore, path::make_path_relative,
@@ -14,11 +14,13 @@ use utils::{
 
 use crate::{
     command::CommandBuilder,
+    env::ExecutionEnv,
     executors::{ExecutorError, StandardCodingAgentExecutor},
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryType, TodoItem as LogsTodoItem,
+        ToolResult,
         stderr_processor::normalize_stderr_logs,
-        utils::{EntryIndexProvider, patch::ConversationPatch},
+        utils::{EntryIndexProvider, ToolResultTracker, patch::ConversationPatch},
     },
 };
 
@@ -34,11 +36,12 @@ impl StandardCodingAgentExecutor for Amp {
         &self,
         current_dir: &PathBuf,
         prompt: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let amp_command = self.command.build_initial();
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped()) // <-- open a pipe
@@ -48,6 +51,7 @@ impl StandardCodingAgentExecutor for Amp {
             .arg(shell_arg)
             .arg(amp_command);
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         // feed the prompt in, then close the pipe so `amp` sees EOF
@@ -64,6 +68,7 @@ impl StandardCodingAgentExecutor for Amp {
         current_dir: &PathBuf,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         // Use shell command for cross-platform compatibility
         let (shell_cmd, shell_arg) = get_shell_command();
@@ -73,7 +78,7 @@ impl StandardCodingAgentExecutor for Amp {
             session_id.to_string(),
         ]);
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -83,6 +88,7 @@ impl StandardCodingAgentExecutor for Amp {
             .arg(shell_arg)
             .arg(&amp_command);
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         // Feed the prompt in, then close the pipe so amp sees EOF
@@ -94,18 +100,24 @@ impl StandardCodingAgentExecutor for Amp {
         Ok(child)
     }
 
-    fn normalize_logs(&self, raw_logs_msg_store: Arc<MsgStore>, current_dir: &PathBuf) {
+    fn normalize_logs(
+        &self,
+        raw_logs_msg_store: Arc<MsgStore>,
+        current_dir: &PathBuf,
+    ) -> Vec<JoinHandle<()>> {
         let entry_index_provider = EntryIndexProvider::new();
 
         // Process stderr logs using the standard stderr processor
-        normalize_stderr_logs(raw_logs_msg_store.clone(), entry_index_provider.clone());
+        let stderr =
+            normalize_stderr_logs(raw_logs_msg_store.clone(), entry_index_provider.clone());
 
         // Process stdout logs (Amp's JSON output)
         let current_dir = current_dir.clone();
-        tokio::spawn(async move {
+        let stdout = tokio::spawn(async move {
             let mut s = raw_logs_msg_store.stdout_lines_stream();
 
             let mut seen_amp_message_ids: HashMap<usize, Vec<usize>> = HashMap::new();
+            let mut tool_result_tracker = ToolResultTracker::new();
             while let Some(Ok(line)) = s.next().await {
                 let trimmed = line.trim();
                 match serde_json::from_str(trimmed) {
@@ -123,6 +135,16 @@ impl StandardCodingAgentExecutor for Amp {
                                     let mut has_patch_ids =
                                         seen_amp_message_ids.get_mut(&amp_message_id);
 
+                                    if let Some((tool_use_id, result)) = content_item.tool_result()
+                                    {
+                                        for patch in
+                                            tool_result_tracker.resolve(tool_use_id, result)
+                                        {
+                                            raw_logs_msg_store.push_patch(patch);
+                                        }
+                                        continue;
+                                    }
+
                                     if let Some(entry) = content_item.to_normalized_entry(
                                         role,
                                         &message,
@@ -140,33 +162,42 @@ impl StandardCodingAgentExecutor for Amp {
                                                 );
                                             }
                                             entry_index_provider.reset();
+                                            tool_result_tracker.reset();
                                         }
 
-                                        let patch: Patch = match &mut has_patch_ids {
+                                        let (patch_id, is_new) = match &mut has_patch_ids {
                                             None => {
                                                 let new_id = entry_index_provider.next();
                                                 seen_amp_message_ids
                                                     .entry(amp_message_id)
                                                     .or_default()
                                                     .push(new_id);
-                                                ConversationPatch::add_normalized_entry(
-                                                    new_id, entry,
-                                                )
+                                                (new_id, true)
                                             }
                                             Some(patch_ids) => match patch_ids.get(content_index) {
-                                                Some(patch_id) => {
-                                                    ConversationPatch::replace(*patch_id, entry)
-                                                }
+                                                Some(patch_id) => (*patch_id, false),
                                                 None => {
                                                     let new_id = entry_index_provider.next();
                                                     patch_ids.push(new_id);
-                                                    ConversationPatch::add_normalized_entry(
-                                                        new_id, entry,
-                                                    )
+                                                    (new_id, true)
                                                 }
                                             },
                                         };
 
+                                        // Tool uses are re-emitted while the thread streams, so
+                                        // keep any result already attached to them
+                                        let entry = match content_item {
+                                            AmpContentItem::ToolUse { id, .. } => {
+                                                tool_result_tracker.track(id, patch_id, entry)
+                                            }
+                                            _ => entry,
+                                        };
+                                        let patch: Patch = if is_new {
+                                            ConversationPatch::add_normalized_entry(patch_id, entry)
+                                        } else {
+                                            ConversationPatch::replace(patch_id, entry)
+                                        };
+
                                         raw_logs_msg_store.push_patch(patch);
                                     }
                                 }
@@ -197,6 +228,8 @@ impl StandardCodingAgentExecutor for Amp {
                 };
             }
         });
+
+        vec![stderr, stdout]
     }
 }
 
@@ -392,6 +425,32 @@ pub enum AmpContentItem {
 }
 
 impl AmpContentItem {
+    /// Outcome of a finished tool run, with the id of the tool use it belongs to
+    pub fn tool_result(&self) -> Option<(&str, ToolResult)> {
+        let AmpContentItem::ToolResult { tool_use_id, run } = self else {
+            return None;
+        };
+        let result = match run.get("status")?.as_str()? {
+            "done" => {
+                let result = run.get("result");
+                let exit_code = result
+                    .and_then(|r| r.get("exitCode"))
+                    .and_then(|c| c.as_i64())
+                    .map(|c| c as i32);
+                let output = result.and_then(|r| r.as_str().or_else(|| r.get("output")?.as_str()));
+                ToolResult::new(exit_code.is_none_or(|c| c == 0), exit_code, output)
+            }
+            "error" | "cancelled" | "rejected-by-user" => {
+                let error = run.get("error");
+                let output = error.and_then(|e| e.as_str().or_else(|| e.get("message")?.as_str()));
+                ToolResult::new(false, None, output)
+            }
+            // Still running or waiting for the user
+            _ => return None,
+        };
+        Some((tool_use_id, result))
+    }
+
     pub fn to_normalized_entry(
         &self,
         role: &str,
@@ -434,6 +493,7 @@ impl AmpContentItem {
                     entry_type: NormalizedEntryType::ToolUse {
                         tool_name: name.to_string(),
                         action_type,
+                        result: None,
                     },
                     content,
                     metadata: Some(serde_json::to_value(self).unwrap_or(Value::Null)),
@@ -600,3 +660,38 @@ impl AmpContentItem {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_result_from_run() {
+        let done: AmpContentItem = serde_json::from_str(
+            r#"{"type":"tool_result","toolUseID":"toolu_1","run":{"status":"done","result":{"output":"ok\n","exitCode":0}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            done.tool_result(),
+            Some(("toolu_1", ToolResult::new(true, Some(0), Some("ok"))))
+        );
+
+        let failed: AmpContentItem = serde_json::from_str(
+            r#"{"type":"tool_result","toolUseID":"toolu_2","run":{"status":"error","error":{"message":"file not found"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            failed.tool_result(),
+            Some((
+                "toolu_2",
+                ToolResult::new(false, None, Some("file not found"))
+            ))
+        );
+
+        let running: AmpContentItem = serde_json::from_str(
+            r#"{"type":"tool_result","toolUseID":"toolu_3","run":{"status":"in-progress"}}"#,
+        )
+        .unwrap();
+        assert_eq!(running.tool_result(), None);
+    }
+}
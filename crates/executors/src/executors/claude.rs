@@ -3,12 +3,13 @@ use std::{path::PathBuf, process::Stdio, sync::Arc};
 use async_trait::async_trait;
 use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use futures::StreamExt;
+use json_patch::Patch;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::{io::AsyncWriteExt, task::JoinHandle};
 use ts_rs::TS;
 use utils::{
     diff::{concatenate_diff_hunks, create_unified_diff, create_unified_diff_hunk},
-    log_msg::LogMsg,
+    log_msg::{LogMsg, TokenUsage},
     msg_store::MsgStore,
     path::make_path_relative,
     shell::get_shell_command,
@@ -16,11 +17,12 @@ use utils::{
 
 use crate::{
     command::CommandBuilder,
-    executors::{ExecutorError, StandardCodingAgentExecutor},
+    env::ExecutionEnv,
+    executors::{CodingAgent, ExecutorError, StandardCodingAgentExecutor},
     logs::{
-        ActionType, FileChange, NormalizedEntry, NormalizedEntryType, TodoItem,
+        ActionType, FileChange, NormalizedEntry, NormalizedEntryType, TodoItem, ToolResult,
         stderr_processor::normalize_stderr_logs,
-        utils::{EntryIndexProvider, patch::ConversationPatch},
+        utils::{EntryIndexProvider, ToolResultTracker, patch::ConversationPatch},
     },
 };
 
@@ -29,6 +31,13 @@ use crate::{
 pub struct ClaudeCode {
     pub command: CommandBuilder,
     pub plan: bool,
+    /// Route permission prompts to the vibe-kanban MCP server so risky tool uses wait for
+    /// approval on the board
+    #[serde(default)]
+    pub approvals: bool,
+    /// Keep stdin open and read it as stream-json, so messages can be sent while Claude runs
+    #[serde(default)]
+    pub live_input: bool,
 }
 
 #[async_trait]
@@ -37,55 +46,140 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         &self,
         current_dir: &PathBuf,
         prompt: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
-        let (shell_cmd, shell_arg) = get_shell_command();
-        let claude_command = if self.plan {
-            let base_command = self.command.build_initial();
-            create_watchkill_script(&base_command)
-        } else {
-            self.command.build_initial()
-        };
+        let mut args = self.live_input_args();
+        args.extend(self.approval_args(env).await?);
+        let base_command = self.command.build_follow_up(&args);
+        self.spawn_claude(current_dir, prompt, base_command, env)
+            .await
+    }
 
-        let mut command = Command::new(shell_cmd);
-        command
-            .kill_on_drop(true)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .current_dir(current_dir)
-            .arg(shell_arg)
-            .arg(&claude_command);
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &PathBuf,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        // Build follow-up command with --resume {session_id}
+        let mut args = self.live_input_args();
+        args.extend(self.approval_args(env).await?);
+        args.extend(["--resume".to_string(), session_id.to_string()]);
+        let base_command = self.command.build_follow_up(&args);
+        self.spawn_claude(current_dir, prompt, base_command, env)
+            .await
+    }
 
-        let mut child = command.group_spawn()?;
+    fn normalize_logs(
+        &self,
+        msg_store: Arc<MsgStore>,
+        current_dir: &PathBuf,
+    ) -> Vec<JoinHandle<()>> {
+        let entry_index_provider = EntryIndexProvider::new();
 
-        // Feed the prompt in, then close the pipe so Claude sees EOF
-        if let Some(mut stdin) = child.inner().stdin.take() {
-            stdin.write_all(prompt.as_bytes()).await?;
-            stdin.shutdown().await?;
+        // Process stdout logs (Claude's JSON output)
+        let stdout = ClaudeLogProcessor::process_logs(
+            self,
+            msg_store.clone(),
+            current_dir,
+            entry_index_provider.clone(),
+        );
+
+        // Process stderr logs using the standard stderr processor
+        let stderr = normalize_stderr_logs(msg_store, entry_index_provider);
+
+        vec![stdout, stderr]
+    }
+
+    fn accepts_live_messages(&self) -> bool {
+        self.live_input
+    }
+
+    fn presents_plan(&self) -> bool {
+        self.plan
+    }
+
+    fn encode_live_message(&self, message: &str) -> String {
+        let user_message = serde_json::json!({
+            "type": "user",
+            "message": {
+                "role": "user",
+                "content": [{ "type": "text", "text": message }],
+            },
+        });
+        format!("{user_message}\n")
+    }
+
+    fn is_turn_complete(&self, line: &str) -> bool {
+        matches!(
+            serde_json::from_str::<ClaudeJson>(line.trim()),
+            Ok(ClaudeJson::Result { .. })
+        )
+    }
+}
+
+/// Where the approvals MCP config of an execution is written
+pub fn approvals_mcp_config_path(execution_id: uuid::Uuid) -> PathBuf {
+    utils::path::get_vibe_kanban_temp_dir()
+        .join("approvals_mcp_configs")
+        .join(format!("{execution_id}.json"))
+}
+
+impl ClaudeCode {
+    /// Arguments that hand permission prompts to the vibe-kanban MCP server's approval tool.
+    /// The server is passed through its own config file so approvals work even when it isn't
+    /// installed in the user's Claude config. Each execution gets its own file so concurrent
+    /// agents never read one another's half-written config; it is removed once it exits.
+    async fn approval_args(&self, env: &ExecutionEnv) -> Result<Vec<String>, ExecutorError> {
+        if !self.approvals {
+            return Ok(vec![]);
         }
 
-        Ok(child)
+        let mcp_config = CodingAgent::ClaudeCode(self.clone()).get_mcp_config();
+        let config = serde_json::json!({
+            "mcpServers": { "vibe_kanban": mcp_config.vibe_kanban }
+        });
+        let config_path =
+            approvals_mcp_config_path(env.execution_id.unwrap_or_else(uuid::Uuid::new_v4));
+        if let Some(config_dir) = config_path.parent() {
+            tokio::fs::create_dir_all(config_dir).await?;
+        }
+        tokio::fs::write(&config_path, config.to_string()).await?;
+
+        Ok(vec![
+            format!("--mcp-config={}", config_path.to_string_lossy()),
+            format!("--permission-prompt-tool={APPROVAL_TOOL}"),
+        ])
     }
 
-    async fn spawn_follow_up(
+    /// With live input, read user messages as stream-json from stdin and echo them back so
+    /// they show up in the logs
+    fn live_input_args(&self) -> Vec<String> {
+        if !self.live_input {
+            return vec![];
+        }
+        vec![
+            "--input-format=stream-json".to_string(),
+            "--replay-user-messages".to_string(),
+        ]
+    }
+
+    async fn spawn_claude(
         &self,
         current_dir: &PathBuf,
         prompt: &str,
-        session_id: &str,
+        base_command: String,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
-        // Build follow-up command with --resume {session_id}
         let claude_command = if self.plan {
-            let base_command = self
-                .command
-                .build_follow_up(&["--resume".to_string(), session_id.to_string()]);
             create_watchkill_script(&base_command)
         } else {
-            self.command
-                .build_follow_up(&["--resume".to_string(), session_id.to_string()])
+            base_command
         };
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -95,33 +189,32 @@ impl StandardCodingAgentExecutor for ClaudeCode {
             .arg(shell_arg)
             .arg(&claude_command);
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
-        // Feed the followup prompt in, then close the pipe
-        if let Some(mut stdin) = child.inner().stdin.take() {
+        if self.live_input {
+            // Feed the prompt in as the first stream-json message. The pipe is left open so
+            // more messages can be sent while Claude runs; the container closes it once every
+            // turn is done
+            if let Some(stdin) = child.inner().stdin.as_mut() {
+                stdin
+                    .write_all(self.encode_live_message(prompt).as_bytes())
+                    .await?;
+                stdin.flush().await?;
+            }
+        } else if let Some(mut stdin) = child.inner().stdin.take() {
+            // Feed the prompt in, then close the pipe so Claude sees EOF
             stdin.write_all(prompt.as_bytes()).await?;
             stdin.shutdown().await?;
         }
 
         Ok(child)
     }
-
-    fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &PathBuf) {
-        let entry_index_provider = EntryIndexProvider::new();
-
-        // Process stdout logs (Claude's JSON output)
-        ClaudeLogProcessor::process_logs(
-            self,
-            msg_store.clone(),
-            current_dir,
-            entry_index_provider.clone(),
-        );
-
-        // Process stderr logs using the standard stderr processor
-        normalize_stderr_logs(msg_store, entry_index_provider);
-    }
 }
 
+/// MCP tool Claude asks before running a tool that isn't already allowed
+pub const APPROVAL_TOOL: &str = "mcp__vibe_kanban__approve_tool_use";
+
 fn create_watchkill_script(command: &str) -> String {
     let claude_plan_stop_indicator = concat!("Exit ", "plan mode?"); // Use concat!() as a workaround to avoid killing plan mode when this file is read.
     format!(
@@ -148,32 +241,45 @@ exit "$exit_code"
 /// Handles log processing and interpretation for Claude executor
 struct ClaudeLogProcessor {
     model_name: Option<String>,
+    tool_results: ToolResultTracker,
+    /// With live input Claude echoes the prompt back as its first user message, which is
+    /// left out of the conversation like it is without live input
+    prompt_echo_pending: bool,
 }
 
 impl ClaudeLogProcessor {
     fn new() -> Self {
-        Self { model_name: None }
+        Self {
+            model_name: None,
+            tool_results: ToolResultTracker::new(),
+            prompt_echo_pending: false,
+        }
     }
 
     /// Process raw logs and convert them to normalized entries with patches
     fn process_logs(
-        _executor: &ClaudeCode,
+        executor: &ClaudeCode,
         msg_store: Arc<MsgStore>,
         current_dir: &PathBuf,
         entry_index_provider: EntryIndexProvider,
-    ) {
+    ) -> JoinHandle<()> {
         let current_dir_clone = current_dir.clone();
+        let live_input = executor.live_input;
         tokio::spawn(async move {
             let mut stream = msg_store.history_plus_stream();
             let mut buffer = String::new();
             let worktree_path = current_dir_clone.to_string_lossy().to_string();
             let mut session_id_extracted = false;
             let mut processor = Self::new();
+            processor.prompt_echo_pending = live_input;
 
             while let Some(Ok(msg)) = stream.next().await {
                 let chunk = match msg {
                     LogMsg::Stdout(x) => x,
-                    LogMsg::JsonPatch(_) | LogMsg::SessionId(_) | LogMsg::Stderr(_) => continue,
+                    LogMsg::JsonPatch(_)
+                    | LogMsg::SessionId(_)
+                    | LogMsg::TokenUsage(_)
+                    | LogMsg::Stderr(_) => continue,
                     LogMsg::Finished => break,
                 };
 
@@ -209,13 +315,16 @@ impl ClaudeLogProcessor {
                                 session_id_extracted = true;
                             }
 
+                            if let Some(usage) = processor.extract_token_usage(&claude_json) {
+                                msg_store.push_token_usage(usage);
+                            }
+
                             // Convert to normalized entries and create patches
-                            for entry in
-                                processor.to_normalized_entries(&claude_json, &worktree_path)
-                            {
-                                let patch_id = entry_index_provider.next();
-                                let patch =
-                                    ConversationPatch::add_normalized_entry(patch_id, entry);
+                            for patch in processor.patches_for(
+                                &claude_json,
+                                &worktree_path,
+                                &entry_index_provider,
+                            ) {
                                 msg_store.push_patch(patch);
                             }
                         }
@@ -255,7 +364,7 @@ impl ClaudeLogProcessor {
                 let patch = ConversationPatch::add_normalized_entry(patch_id, entry);
                 msg_store.push_patch(patch);
             }
-        });
+        })
     }
 
     /// Extract session ID from Claude JSON
@@ -271,6 +380,100 @@ impl ClaudeLogProcessor {
         }
     }
 
+    /// Extract token usage from a Claude `result` message
+    fn extract_token_usage(&self, claude_json: &ClaudeJson) -> Option<TokenUsage> {
+        match claude_json {
+            ClaudeJson::Result {
+                usage: Some(usage), ..
+            } => Some(TokenUsage {
+                model: self.model_name.clone(),
+                input_tokens: usage.input_tokens.unwrap_or(0),
+                output_tokens: usage.output_tokens.unwrap_or(0),
+                cache_creation_input_tokens: usage.cache_creation_input_tokens.unwrap_or(0),
+                cache_read_input_tokens: usage.cache_read_input_tokens.unwrap_or(0),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Convert Claude JSON to patches: new entries are added, and tool results are attached to
+    /// the tool use they answer
+    fn patches_for(
+        &mut self,
+        claude_json: &ClaudeJson,
+        worktree_path: &str,
+        entry_index_provider: &EntryIndexProvider,
+    ) -> Vec<Patch> {
+        if self.prompt_echo_pending
+            && let ClaudeJson::User { message, .. } = claude_json
+            && message
+                .content
+                .iter()
+                .any(|item| matches!(item, ClaudeContentItem::Text { .. }))
+        {
+            self.prompt_echo_pending = false;
+            return vec![];
+        }
+
+        let mut patches = Vec::new();
+        for entry in self.to_normalized_entries(claude_json, worktree_path) {
+            let index = entry_index_provider.next();
+            let entry = match Self::tool_use_id(&entry) {
+                Some(id) => self.tool_results.track(&id, index, entry),
+                None => entry,
+            };
+            patches.push(ConversationPatch::add_normalized_entry(index, entry));
+        }
+
+        if let ClaudeJson::User { message, .. } | ClaudeJson::Assistant { message, .. } =
+            claude_json
+        {
+            for content_item in &message.content {
+                if let ClaudeContentItem::ToolResult {
+                    tool_use_id,
+                    content,
+                    is_error,
+                } = content_item
+                {
+                    let result = ToolResult::new(
+                        !is_error.unwrap_or(false),
+                        None,
+                        Self::tool_result_text(content).as_deref(),
+                    );
+                    patches.extend(self.tool_results.resolve(tool_use_id, result));
+                }
+            }
+        }
+        patches
+    }
+
+    /// Tool-use entries keep their content item as metadata, which carries the call id
+    fn tool_use_id(entry: &NormalizedEntry) -> Option<String> {
+        match (&entry.entry_type, &entry.metadata) {
+            (NormalizedEntryType::ToolUse { .. }, Some(metadata))
+                if metadata.get("type").and_then(|t| t.as_str()) == Some("tool_use") =>
+            {
+                metadata.get("id")?.as_str().map(str::to_string)
+            }
+            _ => None,
+        }
+    }
+
+    /// Tool result content is either a string or a list of content blocks
+    fn tool_result_text(content: &serde_json::Value) -> Option<String> {
+        match content {
+            serde_json::Value::String(text) => Some(text.clone()),
+            serde_json::Value::Array(blocks) => Some(
+                blocks
+                    .iter()
+                    .filter_map(|block| block.get("text")?.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            _ => None,
+        }
+    }
+
     /// Convert Claude JSON to normalized entries
     fn to_normalized_entries(
         &mut self,
@@ -346,6 +549,7 @@ impl ClaudeLogProcessor {
                     entry_type: NormalizedEntryType::ToolUse {
                         tool_name: tool_name.to_string(),
                         action_type,
+                        result: None,
                     },
                     content,
                     metadata: Some(
@@ -413,6 +617,7 @@ impl ClaudeLogProcessor {
                     entry_type: NormalizedEntryType::ToolUse {
                         tool_name: name.to_string(),
                         action_type,
+                        result: None,
                     },
                     content,
                     metadata: Some(
@@ -620,6 +825,8 @@ pub enum ClaudeJson {
         is_error: Option<bool>,
         duration_ms: Option<u64>,
         result: Option<serde_json::Value>,
+        #[serde(default)]
+        usage: Option<ClaudeUsage>,
     },
     // Catch-all for unknown message types
     #[serde(other)]
@@ -637,6 +844,14 @@ pub struct ClaudeMessage {
     pub stop_reason: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ClaudeUsage {
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    pub cache_creation_input_tokens: Option<u64>,
+    pub cache_read_input_tokens: Option<u64>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
 pub enum ClaudeContentItem {
@@ -829,6 +1044,52 @@ mod tests {
         assert_eq!(entries.len(), 0); // Should be ignored like in old implementation
     }
 
+    #[test]
+    fn test_result_message_token_usage() {
+        let assistant_json = r#"{"type":"assistant","message":{"role":"assistant","model":"claude-sonnet-4-20250514","content":[{"type":"text","text":"Done"}]}}"#;
+        let result_json = r#"{"type":"result","subtype":"success","is_error":false,"duration_ms":6059,"result":"Final result","usage":{"input_tokens":12,"cache_creation_input_tokens":4096,"cache_read_input_tokens":20480,"output_tokens":512,"service_tier":"standard"}}"#;
+
+        let mut processor = ClaudeLogProcessor::new();
+        let assistant: ClaudeJson = serde_json::from_str(assistant_json).unwrap();
+        processor.to_normalized_entries(&assistant, "");
+        assert!(processor.extract_token_usage(&assistant).is_none());
+
+        let result: ClaudeJson = serde_json::from_str(result_json).unwrap();
+        let usage = processor.extract_token_usage(&result).unwrap();
+        assert_eq!(usage.model.as_deref(), Some("claude-sonnet-4-20250514"));
+        assert_eq!(usage.input_tokens, 12);
+        assert_eq!(usage.output_tokens, 512);
+        assert_eq!(usage.cache_creation_input_tokens, 4096);
+        assert_eq!(usage.cache_read_input_tokens, 20480);
+    }
+
+    #[test]
+    fn test_live_message_round_trip() {
+        let executor = ClaudeCode {
+            command: CommandBuilder::new("claude"),
+            plan: false,
+            approvals: false,
+            live_input: true,
+        };
+
+        // The encoded message is what Claude replays on stdout, which normalizes to a user message
+        let encoded = executor.encode_live_message("Also update the README");
+        assert!(encoded.ends_with('\n'));
+        let replayed: ClaudeJson = serde_json::from_str(encoded.trim()).unwrap();
+        let entries = ClaudeLogProcessor::new().to_normalized_entries(&replayed, "");
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(
+            entries[0].entry_type,
+            NormalizedEntryType::UserMessage
+        ));
+        assert_eq!(entries[0].content, "Also update the README");
+
+        assert!(!executor.is_turn_complete(encoded.trim()));
+        assert!(executor.is_turn_complete(
+            r#"{"type":"result","subtype":"success","is_error":false,"duration_ms":10}"#
+        ));
+    }
+
     #[test]
     fn test_thinking_content() {
         let thinking_json = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"Let me think about this..."}]}}"#;
@@ -934,6 +1195,8 @@ mod tests {
         let executor = ClaudeCode {
             command: CommandBuilder::new(""),
             plan: false,
+            approvals: false,
+            live_input: false,
         };
         let msg_store = Arc::new(MsgStore::new());
         let current_dir = std::path::PathBuf::from("/tmp/test-worktree");
@@ -994,7 +1257,7 @@ mod tests {
             Some("test123".to_string())
         );
 
-        // ToolResult messages should be ignored (produce no entries) until proper support is added
+        // ToolResult messages carry no call id to attach them to, so they produce no entries
         let entries = ClaudeLogProcessor::new().to_normalized_entries(&parsed, "");
         assert_eq!(entries.len(), 0);
     }
@@ -1004,7 +1267,8 @@ mod tests {
         let assistant_with_tool_result = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_result","tool_use_id":"tool_123","content":"Operation completed","is_error":false}]}}"#;
         let parsed: ClaudeJson = serde_json::from_str(assistant_with_tool_result).unwrap();
 
-        // ToolResult content items should be ignored (produce no entries) until proper support is added
+        // ToolResult content items never produce entries of their own; see
+        // test_tool_result_attached_to_tool_use
         let entries = ClaudeLogProcessor::new().to_normalized_entries(&parsed, "");
         assert_eq!(entries.len(), 0);
     }
@@ -1034,4 +1298,53 @@ mod tests {
 
         // ToolResult entry is ignored - no third entry
     }
+
+    #[test]
+    fn test_tool_result_attached_to_tool_use() {
+        let tool_use = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"cargo test"}}]}}"#;
+        let tool_result = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":[{"type":"text","text":"error: 1 test failed"}],"is_error":true}]}}"#;
+
+        let mut processor = ClaudeLogProcessor::new();
+        let index_provider = EntryIndexProvider::new();
+        let patches = processor.patches_for(
+            &serde_json::from_str(tool_use).unwrap(),
+            "",
+            &index_provider,
+        );
+        assert_eq!(patches.len(), 1);
+
+        let patches = processor.patches_for(
+            &serde_json::from_str(tool_result).unwrap(),
+            "",
+            &index_provider,
+        );
+        assert_eq!(patches.len(), 1);
+        let patch = serde_json::to_value(&patches[0]).unwrap();
+        assert_eq!(patch[0]["op"], "replace");
+        assert_eq!(patch[0]["path"], "/entries/0");
+        assert_eq!(
+            patch[0]["value"]["content"]["entry_type"]["result"],
+            serde_json::json!({
+                "success": false,
+                "exit_code": null,
+                "output": "error: 1 test failed"
+            })
+        );
+    }
+
+    #[test]
+    fn test_live_input_prompt_echo_is_skipped() {
+        let echo = r#"{"type":"user","message":{"role":"user","content":[{"type":"text","text":"Add a greeting"}]}}"#;
+        let message = r#"{"type":"user","message":{"role":"user","content":[{"type":"text","text":"Also update the README"}]}}"#;
+
+        let mut processor = ClaudeLogProcessor::new();
+        processor.prompt_echo_pending = true;
+        let index_provider = EntryIndexProvider::new();
+        let patches =
+            processor.patches_for(&serde_json::from_str(echo).unwrap(), "", &index_provider);
+        assert!(patches.is_empty());
+        let patches =
+            processor.patches_for(&serde_json::from_str(message).unwrap(), "", &index_provider);
+        assert_eq!(patches.len(), 1);
+    }
 }
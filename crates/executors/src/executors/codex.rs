@@ -5,10 +5,11 @@ use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use futures::StreamExt;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::{io::AsyncWriteExt, task::JoinHandle};
 use ts_rs::TS;
 use utils::{
     diff::{concatenate_diff_hunks, extract_unified_diff_hunks},
+    log_msg::TokenUsage,
     msg_store::MsgStore,
     path::make_path_relative,
     shell::get_shell_command,
@@ -16,10 +17,11 @@ use utils::{
 
 use crate::{
     command::CommandBuilder,
+    env::ExecutionEnv,
     executors::{ExecutorError, StandardCodingAgentExecutor},
     logs::{
-        ActionType, FileChange, NormalizedEntry, NormalizedEntryType,
-        utils::{EntryIndexProvider, patch::ConversationPatch},
+        ActionType, FileChange, NormalizedEntry, NormalizedEntryType, ToolResult,
+        utils::{EntryIndexProvider, ToolResultTracker, patch::ConversationPatch},
     },
 };
 
@@ -28,7 +30,7 @@ pub struct SessionHandler;
 
 impl SessionHandler {
     /// Start monitoring stderr lines for session ID extraction
-    pub fn start_session_id_extraction(msg_store: Arc<MsgStore>) {
+    pub fn start_session_id_extraction(msg_store: Arc<MsgStore>) -> JoinHandle<()> {
         tokio::spawn(async move {
             let mut stderr_lines_stream = msg_store.stderr_lines_stream();
 
@@ -37,7 +39,7 @@ impl SessionHandler {
                     msg_store.push_session_id(session_id);
                 }
             }
-        });
+        })
     }
 
     /// Extract session ID from codex stderr output
@@ -116,11 +118,12 @@ impl StandardCodingAgentExecutor for Codex {
         &self,
         current_dir: &PathBuf,
         prompt: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let codex_command = self.command.build_initial();
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -132,6 +135,7 @@ impl StandardCodingAgentExecutor for Codex {
             .env("NODE_NO_WARNINGS", "1")
             .env("RUST_LOG", "info");
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         // Feed the prompt in, then close the pipe so codex sees EOF
@@ -148,6 +152,7 @@ impl StandardCodingAgentExecutor for Codex {
         current_dir: &PathBuf,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         // Find the rollout file for the given session_id using SessionHandler
         let rollout_file_path =
@@ -161,7 +166,7 @@ impl StandardCodingAgentExecutor for Codex {
             format!("experimental_resume={}", rollout_file_path.display()),
         ]);
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -173,6 +178,7 @@ impl StandardCodingAgentExecutor for Codex {
             .env("NODE_NO_WARNINGS", "1")
             .env("RUST_LOG", "info");
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         // Feed the prompt in, then close the pipe so codex sees EOF
@@ -184,16 +190,22 @@ impl StandardCodingAgentExecutor for Codex {
         Ok(child)
     }
 
-    fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &PathBuf) {
+    fn normalize_logs(
+        &self,
+        msg_store: Arc<MsgStore>,
+        current_dir: &PathBuf,
+    ) -> Vec<JoinHandle<()>> {
         let entry_index_provider = EntryIndexProvider::new();
 
         // Process stderr logs for session extraction only (errors come through JSONL)
-        SessionHandler::start_session_id_extraction(msg_store.clone());
+        let session_id_extraction = SessionHandler::start_session_id_extraction(msg_store.clone());
 
         // Process stdout logs (Codex's JSONL output)
         let current_dir = current_dir.clone();
-        tokio::spawn(async move {
+        let stdout = tokio::spawn(async move {
             let mut stream = msg_store.stdout_lines_stream();
+            let mut model_name: Option<String> = None;
+            let mut tool_results = ToolResultTracker::new();
 
             while let Some(Ok(line)) = stream.next().await {
                 let trimmed = line.trim();
@@ -201,16 +213,34 @@ impl StandardCodingAgentExecutor for Codex {
                     continue;
                 }
 
-                if let Ok(entries) = serde_json::from_str::<CodexJson>(trimmed).map(|codex_json| {
-                    codex_json
+                if let Ok(codex_json) = serde_json::from_str::<CodexJson>(trimmed) {
+                    if let CodexJson::SystemConfig {
+                        model: Some(model), ..
+                    } = &codex_json
+                    {
+                        model_name = Some(model.clone());
+                    }
+                    if let Some(usage) = codex_json.token_usage(model_name.clone()) {
+                        msg_store.push_token_usage(usage);
+                    }
+
+                    let entries = codex_json
                         .to_normalized_entries(&current_dir)
-                        .unwrap_or_default()
-                }) {
+                        .unwrap_or_default();
                     for entry in entries {
                         let new_id = entry_index_provider.next();
+                        let entry = match codex_json.tool_call_id() {
+                            Some(call_id) => tool_results.track(call_id, new_id, entry),
+                            None => entry,
+                        };
                         let patch = ConversationPatch::add_normalized_entry(new_id, entry);
                         msg_store.push_patch(patch);
                     }
+                    if let Some((call_id, result)) = codex_json.tool_result() {
+                        for patch in tool_results.resolve(call_id, result) {
+                            msg_store.push_patch(patch);
+                        }
+                    }
                 } else {
                     // Handle malformed JSON as raw output
                     let entry = NormalizedEntry {
@@ -226,6 +256,8 @@ impl StandardCodingAgentExecutor for Codex {
                 }
             }
         });
+
+        vec![session_id_extraction, stdout]
     }
 }
 
@@ -321,6 +353,7 @@ pub enum CodexMsgContent {
         stdout: Option<String>,
         stderr: Option<String>,
         // Codex protocol has exit_code + duration; CLI may provide success; keep optional
+        exit_code: Option<i32>,
         success: Option<bool>,
     },
 
@@ -462,6 +495,7 @@ impl CodexJson {
                                 action_type: ActionType::CommandRun {
                                     command: command_str.clone(),
                                 },
+                                result: None,
                             },
                             content: format!("`{command_str}`"),
                             metadata: None,
@@ -520,6 +554,7 @@ impl CodexJson {
                                         path: relative_path.clone(),
                                         changes,
                                     },
+                                    result: None,
                                 },
                                 content: relative_path,
                                 metadata: None,
@@ -542,6 +577,7 @@ impl CodexJson {
                                         invocation.tool, invocation.server
                                     ),
                                 },
+                                result: None,
                             },
                             content,
                             metadata: None,
@@ -617,6 +653,117 @@ impl CodexJson {
         }
     }
 
+    /// Id of the tool call started by a `*_begin` event
+    pub fn tool_call_id(&self) -> Option<&str> {
+        match self {
+            CodexJson::StructuredMessage { msg, .. } => match msg {
+                CodexMsgContent::ExecCommandBegin { call_id, .. }
+                | CodexMsgContent::PatchApplyBegin { call_id, .. } => call_id.as_deref(),
+                CodexMsgContent::McpToolCallBegin { call_id, .. } => Some(call_id),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Outcome reported by a `*_end` event, with the id of the tool call it finishes
+    pub fn tool_result(&self) -> Option<(&str, ToolResult)> {
+        let CodexJson::StructuredMessage { msg, .. } = self else {
+            return None;
+        };
+        let join_output = |stdout: &Option<String>, stderr: &Option<String>| {
+            [stdout, stderr]
+                .into_iter()
+                .flatten()
+                .map(|s| s.trim_end())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        match msg {
+            CodexMsgContent::ExecCommandEnd {
+                call_id: Some(call_id),
+                stdout,
+                stderr,
+                exit_code,
+                success,
+            } => Some((
+                call_id,
+                ToolResult::new(
+                    success.unwrap_or(*exit_code == Some(0)),
+                    *exit_code,
+                    Some(&join_output(stdout, stderr)),
+                ),
+            )),
+            CodexMsgContent::PatchApplyEnd {
+                call_id: Some(call_id),
+                stdout,
+                stderr,
+                success,
+            } => Some((
+                call_id,
+                ToolResult::new(
+                    success.unwrap_or(true),
+                    None,
+                    Some(&join_output(stdout, stderr)),
+                ),
+            )),
+            CodexMsgContent::McpToolCallEnd {
+                call_id, result, ..
+            } => {
+                // The result is serialized as `{"Ok": CallToolResult}` or `{"Err": message}`
+                let (success, output) = if let Some(ok) = result.get("Ok") {
+                    let output = ok.get("content").and_then(|c| c.as_array()).map(|blocks| {
+                        blocks
+                            .iter()
+                            .filter_map(|block| block.get("text")?.as_str())
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    });
+                    let is_error = ok.get("isError").and_then(|e| e.as_bool());
+                    (!is_error.unwrap_or(false), output)
+                } else if let Some(err) = result.get("Err") {
+                    let output = err
+                        .as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| err.to_string());
+                    (false, Some(output))
+                } else {
+                    (true, None)
+                };
+                Some((call_id, ToolResult::new(success, None, output.as_deref())))
+            }
+            _ => None,
+        }
+    }
+
+    /// Extract token usage from a `token_count` event. Codex reports cached
+    /// tokens as part of `input_tokens`, so they are split out here.
+    pub fn token_usage(&self, model: Option<String>) -> Option<TokenUsage> {
+        match self {
+            CodexJson::StructuredMessage {
+                msg:
+                    CodexMsgContent::TokenCount {
+                        input_tokens,
+                        cached_input_tokens,
+                        output_tokens,
+                        ..
+                    },
+                ..
+            } => {
+                let cached = cached_input_tokens.unwrap_or(0);
+                Some(TokenUsage {
+                    model,
+                    input_tokens: input_tokens.unwrap_or(0).saturating_sub(cached),
+                    output_tokens: output_tokens.unwrap_or(0),
+                    cache_creation_input_tokens: 0,
+                    cache_read_input_tokens: cached,
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Format system configuration message for display
     fn format_config_message(&self) -> Option<String> {
         if let CodexJson::SystemConfig {
@@ -742,6 +889,7 @@ mod tests {
         if let NormalizedEntryType::ToolUse {
             tool_name,
             action_type,
+            ..
         } = &entries[1].entry_type
         {
             assert_eq!(tool_name, "bash");
@@ -783,6 +931,23 @@ mod tests {
         assert_eq!(entries.len(), 0);
     }
 
+    #[test]
+    fn test_token_count_usage() {
+        let token_count = r#"{"id":"1","msg":{"type":"token_count","input_tokens":1674,"cached_input_tokens":1627,"output_tokens":384,"reasoning_output_tokens":384,"total_tokens":2058}}"#;
+        let parsed = test_codex_json_parsing(token_count).unwrap();
+
+        let usage = parsed
+            .token_usage(Some("codex-mini-latest".to_string()))
+            .unwrap();
+        assert_eq!(usage.model.as_deref(), Some("codex-mini-latest"));
+        assert_eq!(usage.input_tokens, 47);
+        assert_eq!(usage.cache_read_input_tokens, 1627);
+        assert_eq!(usage.output_tokens, 384);
+
+        let task_started = test_codex_json_parsing(r#"{"id":"1","msg":{"type":"task_started"}}"#);
+        assert!(task_started.unwrap().token_usage(None).is_none());
+    }
+
     #[test]
     fn test_normalize_logs_malformed_json() {
         let logs = r#"{"id":"1","msg":{"type":"task_started"}}
@@ -947,6 +1112,7 @@ invalid json line here
         if let NormalizedEntryType::ToolUse {
             tool_name,
             action_type,
+            ..
         } = &entries[0].entry_type
         {
             assert_eq!(tool_name, "edit");
@@ -993,6 +1159,7 @@ invalid json line here
         if let NormalizedEntryType::ToolUse {
             tool_name,
             action_type,
+            ..
         } = &entries[0].entry_type
         {
             assert_eq!(tool_name, "mcp_list_projects");
@@ -1060,4 +1227,26 @@ invalid json line here
         let entries = parsed.to_normalized_entries(&current_dir);
         assert!(entries.is_none()); // Should return None
     }
+
+    #[test]
+    fn test_tool_results_from_end_events() {
+        let begin = test_codex_json_parsing(r#"{"id":"1","msg":{"type":"exec_command_begin","call_id":"call_1","command":["bash","-lc","cargo test"],"cwd":"/tmp"}}"#).unwrap();
+        assert_eq!(begin.tool_call_id(), Some("call_1"));
+        assert!(begin.tool_result().is_none());
+
+        let end = test_codex_json_parsing(r#"{"id":"1","msg":{"type":"exec_command_end","call_id":"call_1","stdout":"running 1 test\n","stderr":"test failed\n","exit_code":101}}"#).unwrap();
+        assert_eq!(
+            end.tool_result(),
+            Some((
+                "call_1",
+                ToolResult::new(false, Some(101), Some("running 1 test\ntest failed"))
+            ))
+        );
+
+        let mcp_end = test_codex_json_parsing(r#"{"id":"1","msg":{"type":"mcp_tool_call_end","call_id":"call_2","invocation":{"server":"vibe_kanban","tool":"list_tasks","arguments":{}},"result":{"Ok":{"content":[{"text":"Tasks listed"}],"isError":false}}}}"#).unwrap();
+        assert_eq!(
+            mcp_end.tool_result(),
+            Some(("call_2", ToolResult::new(true, None, Some("Tasks listed"))))
+        );
+    }
 }
@@ -5,7 +5,7 @@ use async_trait::async_trait;
 use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::{io::AsyncWriteExt, task::JoinHandle};
 use ts_rs::TS;
 use utils::{
     diff::{
@@ -19,6 +19,7 @@ use utils::{
 
 use crate::{
     command::CommandBuilder,
+    env::ExecutionEnv,
     executors::{ExecutorError, StandardCodingAgentExecutor},
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryType, TodoItem,
@@ -39,11 +40,12 @@ impl StandardCodingAgentExecutor for Cursor {
         &self,
         current_dir: &PathBuf,
         prompt: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let agent_cmd = self.command.build_initial();
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -53,6 +55,7 @@ impl StandardCodingAgentExecutor for Cursor {
             .arg(shell_arg)
             .arg(&agent_cmd);
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         if let Some(mut stdin) = child.inner().stdin.take() {
@@ -68,13 +71,14 @@ impl StandardCodingAgentExecutor for Cursor {
         current_dir: &PathBuf,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let agent_cmd = self
             .command
             .build_follow_up(&["--resume".to_string(), session_id.to_string()]);
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -84,6 +88,7 @@ impl StandardCodingAgentExecutor for Cursor {
             .arg(shell_arg)
             .arg(&agent_cmd);
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         if let Some(mut stdin) = child.inner().stdin.take() {
@@ -94,12 +99,16 @@ impl StandardCodingAgentExecutor for Cursor {
         Ok(child)
     }
 
-    fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &PathBuf) {
+    fn normalize_logs(
+        &self,
+        msg_store: Arc<MsgStore>,
+        worktree_path: &PathBuf,
+    ) -> Vec<JoinHandle<()>> {
         let entry_index_provider = EntryIndexProvider::new();
 
         // Process Cursor stdout JSONL with typed serde models
         let current_dir = worktree_path.clone();
-        tokio::spawn(async move {
+        let stdout = tokio::spawn(async move {
             let mut lines = msg_store.stdout_lines_stream();
 
             // Cursor agent doesn't use STDERR. Everything comes through STDOUT, both JSONL and raw error output.
@@ -232,6 +241,7 @@ impl StandardCodingAgentExecutor for Cursor {
                                 entry_type: NormalizedEntryType::ToolUse {
                                     tool_name,
                                     action_type,
+                                    result: None,
                                 },
                                 content,
                                 metadata: None,
@@ -259,6 +269,8 @@ impl StandardCodingAgentExecutor for Cursor {
                 }
             }
         });
+
+        vec![stdout]
     }
 }
 
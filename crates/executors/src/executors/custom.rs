@@ -0,0 +1,470 @@
+use std::{collections::BTreeMap, path::PathBuf, process::Stdio, sync::Arc};
+
+use async_trait::async_trait;
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{io::AsyncWriteExt, task::JoinHandle};
+use ts_rs::TS;
+use utils::{
+    msg_store::MsgStore,
+    path::make_path_relative,
+    shell::{get_shell_command, quote_arg},
+};
+
+use crate::{
+    command::CommandBuilder,
+    env::ExecutionEnv,
+    executors::{ExecutorError, StandardCodingAgentExecutor},
+    logs::{
+        ActionType, FileChange, NormalizedEntry, NormalizedEntryType,
+        stderr_processor::normalize_stderr_logs,
+        utils::{EntryIndexProvider, patch::ConversationPatch},
+    },
+};
+
+/// Placeholder in `follow_up_params` that is replaced with the session id
+pub const SESSION_ID_PLACEHOLDER: &str = "{session_id}";
+
+/// A JSON-lines agent configured entirely from profiles.json.
+///
+/// The prompt is written to stdin. Each stdout line is parsed as JSON and matched
+/// against `rules` in order; the first matching rule decides the normalized entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct Custom {
+    pub command: CommandBuilder,
+    /// Parameters appended to the command for follow-ups. `{session_id}` is substituted.
+    /// Follow-ups are not supported when unset.
+    #[serde(default)]
+    pub follow_up_params: Option<Vec<String>>,
+    /// JSON pointer (e.g. `/session_id`) to the session id in the agent's output
+    #[serde(default)]
+    pub session_id_field: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<CustomLogRule>,
+}
+
+/// Maps a JSON line to a normalized entry. All fields except `entry_type` and `when`
+/// are JSON pointers into the line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct CustomLogRule {
+    /// JSON pointer -> value pairs that must all match for the rule to apply
+    #[serde(default)]
+    pub when: BTreeMap<String, Value>,
+    pub entry_type: CustomEntryType,
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Only used for `tool_use` entries
+    #[serde(default)]
+    pub tool_name: Option<String>,
+    /// Only used for `tool_use` entries, defaults to `other`
+    #[serde(default)]
+    pub action: Option<CustomAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomEntryType {
+    UserMessage,
+    AssistantMessage,
+    ToolUse,
+    SystemMessage,
+    ErrorMessage,
+    Thinking,
+    /// Drop matching lines
+    Ignore,
+}
+
+/// `ActionType` counterpart whose fields are JSON pointers into the line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CustomAction {
+    FileRead {
+        path: String,
+    },
+    FileEdit {
+        path: String,
+        #[serde(default)]
+        unified_diff: Option<String>,
+        #[serde(default)]
+        content: Option<String>,
+    },
+    CommandRun {
+        command: String,
+    },
+    Search {
+        query: String,
+    },
+    WebFetch {
+        url: String,
+    },
+    TaskCreate {
+        description: String,
+    },
+    PlanPresentation {
+        plan: String,
+    },
+    Other {
+        description: String,
+    },
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Custom {
+    async fn spawn(
+        &self,
+        current_dir: &PathBuf,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let custom_command = self.command.build_initial();
+        Self::spawn_command(current_dir, prompt, custom_command, env).await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &PathBuf,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let custom_command = self.follow_up_command(session_id)?;
+        Self::spawn_command(current_dir, prompt, custom_command, env).await
+    }
+
+    fn normalize_logs(
+        &self,
+        msg_store: Arc<MsgStore>,
+        worktree_path: &PathBuf,
+    ) -> Vec<JoinHandle<()>> {
+        let entry_index_provider = EntryIndexProvider::new();
+        let stderr = normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
+
+        let config = self.clone();
+        let worktree_path = worktree_path.to_string_lossy().to_string();
+        let stdout = tokio::spawn(async move {
+            let mut lines = msg_store.stdout_lines_stream();
+            let mut session_id_extracted = false;
+
+            while let Some(Ok(line)) = lines.next().await {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+
+                let entry = match serde_json::from_str::<Value>(trimmed) {
+                    Ok(json) => {
+                        if !session_id_extracted
+                            && let Some(session_id) = config.extract_session_id(&json)
+                        {
+                            msg_store.push_session_id(session_id);
+                            session_id_extracted = true;
+                        }
+                        config.to_normalized_entry(&json, &worktree_path)
+                    }
+                    Err(_) => Some(NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::SystemMessage,
+                        content: format!("Raw output: {trimmed}"),
+                        metadata: None,
+                    }),
+                };
+
+                if let Some(entry) = entry {
+                    let id = entry_index_provider.next();
+                    msg_store.push_patch(ConversationPatch::add_normalized_entry(id, entry));
+                }
+            }
+        });
+
+        vec![stderr, stdout]
+    }
+}
+
+impl Custom {
+    async fn spawn_command(
+        current_dir: &PathBuf,
+        prompt: &str,
+        custom_command: String,
+        env: &ExecutionEnv,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let (shell_cmd, shell_arg) = get_shell_command();
+
+        let mut command = env.command(shell_cmd);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .arg(shell_arg)
+            .arg(custom_command);
+
+        env.apply_to_command(&mut command);
+        let mut child = command.group_spawn()?;
+
+        if let Some(mut stdin) = child.inner().stdin.take() {
+            stdin.write_all(prompt.as_bytes()).await?;
+            stdin.shutdown().await?;
+        }
+
+        Ok(child)
+    }
+
+    /// The shell command for a follow-up, with the session id substituted and quoted
+    fn follow_up_command(&self, session_id: &str) -> Result<String, ExecutorError> {
+        let follow_up_params = self.follow_up_params.as_ref().ok_or_else(|| {
+            ExecutorError::FollowUpNotSupported(
+                "Custom agent has no follow_up_params configured".to_string(),
+            )
+        })?;
+        let session_id = quote_arg(session_id);
+        let params = follow_up_params
+            .iter()
+            .map(|param| param.replace(SESSION_ID_PLACEHOLDER, &session_id))
+            .collect::<Vec<_>>();
+        Ok(self.command.build_follow_up(&params))
+    }
+
+    fn extract_session_id(&self, json: &Value) -> Option<String> {
+        let pointer = self.session_id_field.as_ref()?;
+        json.pointer(pointer).and_then(value_to_string)
+    }
+
+    /// Apply the first matching rule. Lines that match no rule are dropped.
+    fn to_normalized_entry(&self, json: &Value, worktree_path: &str) -> Option<NormalizedEntry> {
+        let rule = self.rules.iter().find(|rule| {
+            rule.when
+                .iter()
+                .all(|(pointer, expected)| json.pointer(pointer) == Some(expected))
+        })?;
+
+        let lookup = |pointer: &Option<String>| {
+            pointer
+                .as_ref()
+                .and_then(|p| json.pointer(p))
+                .and_then(value_to_string)
+        };
+        let content = lookup(&rule.content);
+
+        let entry_type = match rule.entry_type {
+            CustomEntryType::Ignore => return None,
+            CustomEntryType::UserMessage => NormalizedEntryType::UserMessage,
+            CustomEntryType::AssistantMessage => NormalizedEntryType::AssistantMessage,
+            CustomEntryType::SystemMessage => NormalizedEntryType::SystemMessage,
+            CustomEntryType::ErrorMessage => NormalizedEntryType::ErrorMessage,
+            CustomEntryType::Thinking => NormalizedEntryType::Thinking,
+            CustomEntryType::ToolUse => NormalizedEntryType::ToolUse {
+                tool_name: lookup(&rule.tool_name).unwrap_or_else(|| "tool".to_string()),
+                action_type: Self::to_action_type(
+                    json,
+                    rule.action.as_ref(),
+                    content.as_deref(),
+                    worktree_path,
+                ),
+                result: None,
+            },
+        };
+
+        let content = match (&entry_type, content) {
+            (_, Some(content)) => content,
+            (NormalizedEntryType::ToolUse { tool_name, .. }, None) => tool_name.clone(),
+            // Without content there is nothing to show for a message
+            (_, None) => return None,
+        };
+
+        Some(NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content,
+            metadata: Some(json.clone()),
+        })
+    }
+
+    fn to_action_type(
+        json: &Value,
+        action: Option<&CustomAction>,
+        content: Option<&str>,
+        worktree_path: &str,
+    ) -> ActionType {
+        let lookup = |pointer: &str| {
+            json.pointer(pointer)
+                .and_then(value_to_string)
+                .unwrap_or_default()
+        };
+
+        match action {
+            Some(CustomAction::FileRead { path }) => ActionType::FileRead {
+                path: make_path_relative(&lookup(path), worktree_path),
+            },
+            Some(CustomAction::FileEdit {
+                path,
+                unified_diff,
+                content,
+            }) => {
+                let changes = if let Some(unified_diff) = unified_diff {
+                    vec![FileChange::Edit {
+                        unified_diff: lookup(unified_diff),
+                        has_line_numbers: true,
+                    }]
+                } else if let Some(content) = content {
+                    vec![FileChange::Write {
+                        content: lookup(content),
+                    }]
+                } else {
+                    vec![]
+                };
+                ActionType::FileEdit {
+                    path: make_path_relative(&lookup(path), worktree_path),
+                    changes,
+                }
+            }
+            Some(CustomAction::CommandRun { command }) => ActionType::CommandRun {
+                command: lookup(command),
+            },
+            Some(CustomAction::Search { query }) => ActionType::Search {
+                query: lookup(query),
+            },
+            Some(CustomAction::WebFetch { url }) => ActionType::WebFetch { url: lookup(url) },
+            Some(CustomAction::TaskCreate { description }) => ActionType::TaskCreate {
+                description: lookup(description),
+            },
+            Some(CustomAction::PlanPresentation { plan }) => {
+                ActionType::PlanPresentation { plan: lookup(plan) }
+            }
+            Some(CustomAction::Other { description }) => ActionType::Other {
+                description: lookup(description),
+            },
+            None => ActionType::Other {
+                description: content.unwrap_or_default().to_string(),
+            },
+        }
+    }
+}
+
+/// Strings are used as-is, other values are rendered as JSON
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_agent() -> Custom {
+        serde_json::from_str(
+            r#"{
+                "command": { "base": "my-agent", "params": ["--json"] },
+                "follow_up_params": ["--resume", "{session_id}"],
+                "session_id_field": "/session",
+                "rules": [
+                    { "when": { "/type": "init" }, "entry_type": "ignore" },
+                    { "when": { "/type": "message", "/role": "assistant" }, "entry_type": "assistant_message", "content": "/text" },
+                    {
+                        "when": { "/type": "tool", "/name": "shell" },
+                        "entry_type": "tool_use",
+                        "tool_name": "/name",
+                        "content": "/input/cmd",
+                        "action": { "type": "command_run", "command": "/input/cmd" }
+                    },
+                    {
+                        "when": { "/type": "tool", "/name": "write" },
+                        "entry_type": "tool_use",
+                        "tool_name": "/name",
+                        "action": { "type": "file_edit", "path": "/input/file", "content": "/input/body" }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn normalize(line: &str) -> Option<NormalizedEntry> {
+        let json: Value = serde_json::from_str(line).unwrap();
+        test_agent().to_normalized_entry(&json, "/tmp/worktree")
+    }
+
+    #[test]
+    fn test_assistant_message_rule() {
+        let entry =
+            normalize(r#"{"type":"message","role":"assistant","text":"Looking at the code"}"#)
+                .unwrap();
+        assert!(matches!(
+            entry.entry_type,
+            NormalizedEntryType::AssistantMessage
+        ));
+        assert_eq!(entry.content, "Looking at the code");
+    }
+
+    #[test]
+    fn test_tool_use_rules() {
+        let entry =
+            normalize(r#"{"type":"tool","name":"shell","input":{"cmd":"cargo test"}}"#).unwrap();
+        match entry.entry_type {
+            NormalizedEntryType::ToolUse {
+                tool_name,
+                action_type: ActionType::CommandRun { command },
+                ..
+            } => {
+                assert_eq!(tool_name, "shell");
+                assert_eq!(command, "cargo test");
+            }
+            other => panic!("Expected CommandRun tool use, got {other:?}"),
+        }
+
+        let entry = normalize(
+            r#"{"type":"tool","name":"write","input":{"file":"/tmp/worktree/src/lib.rs","body":"fn main() {}"}}"#,
+        )
+        .unwrap();
+        assert_eq!(entry.content, "write");
+        match entry.entry_type {
+            NormalizedEntryType::ToolUse {
+                action_type: ActionType::FileEdit { path, changes },
+                ..
+            } => {
+                assert_eq!(path, "src/lib.rs");
+                assert!(matches!(
+                    &changes[0],
+                    FileChange::Write { content } if content == "fn main() {}"
+                ));
+            }
+            other => panic!("Expected FileEdit tool use, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unmatched_and_ignored_lines_are_dropped() {
+        assert!(normalize(r#"{"type":"init","session":"abc"}"#).is_none());
+        assert!(normalize(r#"{"type":"message","role":"user","text":"hi"}"#).is_none());
+    }
+
+    #[test]
+    fn test_session_id_and_follow_up_params() {
+        let agent = test_agent();
+        let json: Value = serde_json::from_str(r#"{"type":"init","session":"abc"}"#).unwrap();
+        assert_eq!(agent.extract_session_id(&json), Some("abc".to_string()));
+
+        assert_eq!(
+            agent.follow_up_command("abc").unwrap(),
+            "my-agent --json --resume abc"
+        );
+        assert_eq!(
+            agent.follow_up_command("abc; touch pwned").unwrap(),
+            "my-agent --json --resume 'abc; touch pwned'"
+        );
+
+        let agent = Custom {
+            follow_up_params: None,
+            ..agent
+        };
+        assert!(matches!(
+            agent.follow_up_command("abc"),
+            Err(ExecutorError::FollowUpNotSupported(_))
+        ));
+    }
+}
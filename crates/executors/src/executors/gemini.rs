@@ -7,13 +7,14 @@ use serde::{Deserialize, Serialize};
 use tokio::{
     fs::{self, OpenOptions},
     io::AsyncWriteExt,
-    process::Command,
+    task::JoinHandle,
 };
 use ts_rs::TS;
 use utils::{msg_store::MsgStore, shell::get_shell_command};
 
 use crate::{
     command::CommandBuilder,
+    env::ExecutionEnv,
     executors::{ExecutorError, StandardCodingAgentExecutor},
     logs::{
         NormalizedEntry, NormalizedEntryType, plain_text_processor::PlainTextLogProcessor,
@@ -34,11 +35,12 @@ impl StandardCodingAgentExecutor for Gemini {
         &self,
         current_dir: &PathBuf,
         prompt: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let gemini_command = self.command.build_initial();
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
 
         command
             .kill_on_drop(true)
@@ -50,6 +52,7 @@ impl StandardCodingAgentExecutor for Gemini {
             .arg(gemini_command)
             .env("NODE_NO_WARNINGS", "1");
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         // Write prompt to stdin
@@ -75,6 +78,7 @@ impl StandardCodingAgentExecutor for Gemini {
         current_dir: &PathBuf,
         prompt: &str,
         _session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         // Build comprehensive prompt with session context
         let followup_prompt = Self::build_followup_prompt(current_dir, prompt).await?;
@@ -82,7 +86,7 @@ impl StandardCodingAgentExecutor for Gemini {
         let (shell_cmd, shell_arg) = get_shell_command();
         let gemini_command = self.command.build_follow_up(&[]);
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
 
         command
             .kill_on_drop(true)
@@ -94,6 +98,7 @@ impl StandardCodingAgentExecutor for Gemini {
             .arg(gemini_command)
             .env("NODE_NO_WARNINGS", "1");
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         // Write comprehensive prompt to stdin
@@ -132,9 +137,13 @@ impl StandardCodingAgentExecutor for Gemini {
     /// Sets up log normalization for the Gemini executor:
     /// - stderr via [`normalize_stderr_logs`]
     /// - stdout via [`PlainTextLogProcessor`] with Gemini-specific formatting and default heuristics
-    fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &PathBuf) {
+    fn normalize_logs(
+        &self,
+        msg_store: Arc<MsgStore>,
+        worktree_path: &PathBuf,
+    ) -> Vec<JoinHandle<()>> {
         let entry_index_counter = EntryIndexProvider::new();
-        normalize_stderr_logs(msg_store.clone(), entry_index_counter.clone());
+        let stderr = normalize_stderr_logs(msg_store.clone(), entry_index_counter.clone());
 
         // Send session ID to msg_store to enable follow-ups
         msg_store.push_session_id(
@@ -146,7 +155,7 @@ impl StandardCodingAgentExecutor for Gemini {
         );
 
         // Normalize Agent logs
-        tokio::spawn(async move {
+        let stdout = tokio::spawn(async move {
             let mut stdout = msg_store.stdout_chunked_stream();
 
             // Create a processor with Gemini-specific formatting
@@ -169,6 +178,8 @@ impl StandardCodingAgentExecutor for Gemini {
                 }
             }
         });
+
+        vec![stderr, stdout]
     }
 }
 
@@ -6,22 +6,26 @@ use enum_dispatch::enum_dispatch;
 use futures_io::Error as FuturesIoError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tokio::task::JoinHandle;
 use ts_rs::TS;
 use utils::msg_store::MsgStore;
 
 use crate::{
+    env::ExecutionEnv,
     executors::{
-        amp::Amp, claude::ClaudeCode, codex::Codex, cursor::Cursor, gemini::Gemini,
-        opencode::Opencode,
+        aider::Aider, amp::Amp, claude::ClaudeCode, codex::Codex, cursor::Cursor, custom::Custom,
+        gemini::Gemini, opencode::Opencode,
     },
     mcp_config::McpConfig,
     profile::{ProfileConfigs, ProfileVariantLabel},
 };
 
+pub mod aider;
 pub mod amp;
 pub mod claude;
 pub mod codex;
 pub mod cursor;
+pub mod custom;
 pub mod gemini;
 pub mod opencode;
 
@@ -41,6 +45,8 @@ pub enum ExecutorError {
     TomlSerialize(#[from] toml::ser::Error),
     #[error(transparent)]
     TomlDeserialize(#[from] toml::de::Error),
+    #[error("Script working directory must be inside the worktree: {0}")]
+    InvalidWorkingDir(String),
 }
 
 #[enum_dispatch]
@@ -53,6 +59,8 @@ pub enum CodingAgent {
     Codex,
     Opencode,
     Cursor,
+    Aider,
+    Custom,
 }
 
 impl CodingAgent {
@@ -138,6 +146,25 @@ impl CodingAgent {
         }
     }
 
+    /// Where the agent keeps its settings, credentials and sessions, which it needs to write
+    /// to even when sandboxed
+    pub fn config_paths(&self) -> Vec<PathBuf> {
+        let in_home = |path: &str| dirs::home_dir().map(|home| home.join(path));
+        let in_config = |path: &str| dirs::config_dir().map(|config| config.join(path));
+        let in_data = |path: &str| dirs::data_local_dir().map(|data| data.join(path));
+        let paths = match self {
+            Self::ClaudeCode(_) => vec![in_home(".claude"), in_home(".claude.json")],
+            Self::Amp(_) => vec![in_config("amp"), in_data("amp")],
+            Self::Gemini(_) => vec![in_home(".gemini")],
+            Self::Codex(_) => vec![in_home(".codex")],
+            Self::Opencode(_) => vec![in_config("opencode"), in_data("opencode")],
+            Self::Cursor(_) => vec![in_home(".cursor"), in_config("cursor")],
+            Self::Aider(_) => vec![in_home(".aider"), Some(Aider::get_sessions_base_dir())],
+            Self::Custom(_) => vec![],
+        };
+        paths.into_iter().flatten().collect()
+    }
+
     pub fn default_mcp_config_path(&self) -> Option<PathBuf> {
         match self {
             //ExecutorConfig::CharmOpencode => {
@@ -155,7 +182,8 @@ impl CodingAgent {
                     dirs::config_dir().map(|config| config.join("opencode").join("opencode.json"))
                 }
             }
-            //ExecutorConfig::Aider => None,
+            Self::Aider(_) => None,
+            Self::Custom(_) => None,
             Self::Codex(_) => dirs::home_dir().map(|home| home.join(".codex").join("config.toml")),
             Self::Amp(_) => {
                 dirs::config_dir().map(|config| config.join("amp").join("settings.json"))
@@ -175,12 +203,40 @@ pub trait StandardCodingAgentExecutor {
         &self,
         current_dir: &PathBuf,
         prompt: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError>;
     async fn spawn_follow_up(
         &self,
         current_dir: &PathBuf,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError>;
-    fn normalize_logs(&self, _raw_logs_event_store: Arc<MsgStore>, _worktree_path: &PathBuf);
+    /// Start normalizing the raw logs in the store into patches of the conversation, pushed
+    /// to the same store. Returns the tasks doing so, which end once the raw logs are
+    /// `Finished` and everything before that has been normalized.
+    fn normalize_logs(
+        &self,
+        _raw_logs_event_store: Arc<MsgStore>,
+        _worktree_path: &PathBuf,
+    ) -> Vec<JoinHandle<()>>;
+    /// Whether the agent keeps reading stdin after the prompt, so messages can be sent to it
+    /// while it runs. Such agents leave stdin open in the spawned child.
+    fn accepts_live_messages(&self) -> bool {
+        false
+    }
+    /// Encode a user message to be written to the stdin of a running agent
+    fn encode_live_message(&self, message: &str) -> String {
+        format!("{message}\n")
+    }
+    /// Whether a stdout line marks the end of a turn. Once every message sent to the agent
+    /// has finished its turn, stdin is closed so the agent can exit.
+    fn is_turn_complete(&self, _line: &str) -> bool {
+        false
+    }
+    /// Whether the agent only plans: it stops once its plan is presented, and the plan has
+    /// to be approved before a follow-up implements it
+    fn presents_plan(&self) -> bool {
+        false
+    }
 }
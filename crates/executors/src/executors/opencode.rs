@@ -7,7 +7,7 @@ use futures::{StreamExt, future::ready, stream::BoxStream};
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::{io::AsyncWriteExt, task::JoinHandle};
 use ts_rs::TS;
 use utils::{
     diff::create_unified_diff, msg_store::MsgStore, path::make_path_relative,
@@ -16,6 +16,7 @@ use utils::{
 
 use crate::{
     command::CommandBuilder,
+    env::ExecutionEnv,
     executors::{ExecutorError, StandardCodingAgentExecutor},
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryType, TodoItem,
@@ -36,11 +37,12 @@ impl StandardCodingAgentExecutor for Opencode {
         &self,
         current_dir: &PathBuf,
         prompt: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let opencode_command = self.command.build_initial();
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -51,6 +53,7 @@ impl StandardCodingAgentExecutor for Opencode {
             .arg(opencode_command)
             .env("NODE_NO_WARNINGS", "1");
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         // Write prompt to stdin
@@ -67,13 +70,14 @@ impl StandardCodingAgentExecutor for Opencode {
         current_dir: &PathBuf,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<AsyncGroupChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let opencode_command = self
             .command
             .build_follow_up(&["--session".to_string(), session_id.to_string()]);
 
-        let mut command = Command::new(shell_cmd);
+        let mut command = env.command(shell_cmd);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -84,6 +88,7 @@ impl StandardCodingAgentExecutor for Opencode {
             .arg(&opencode_command)
             .env("NODE_NO_WARNINGS", "1");
 
+        env.apply_to_command(&mut command);
         let mut child = command.group_spawn()?;
 
         // Write prompt to stdin
@@ -102,7 +107,11 @@ impl StandardCodingAgentExecutor for Opencode {
     /// 2. Error log recognition thread: read by line, identify error log lines, store them as error messages.
     /// 3. Main normalizer thread: read stderr by line, filter out log lines, send lines (with '\n' appended) to plain text normalizer,
     ///    then define predicate for split and create appropriate normalized entry (either assistant or tool call).
-    fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &PathBuf) {
+    fn normalize_logs(
+        &self,
+        msg_store: Arc<MsgStore>,
+        worktree_path: &PathBuf,
+    ) -> Vec<JoinHandle<()>> {
         let entry_index_counter = EntryIndexProvider::new();
         let worktree_path = worktree_path.clone();
 
@@ -122,7 +131,7 @@ impl StandardCodingAgentExecutor for Opencode {
             .boxed();
 
         // Process log lines, which contain error messages and session ID
-        tokio::spawn(Self::process_opencode_log_lines(
+        let log_lines = tokio::spawn(Self::process_opencode_log_lines(
             log_lines,
             msg_store.clone(),
             entry_index_counter.clone(),
@@ -139,12 +148,14 @@ impl StandardCodingAgentExecutor for Opencode {
             .boxed();
 
         // Normalize agent logs
-        tokio::spawn(Self::process_agent_logs(
+        let agent_logs = tokio::spawn(Self::process_agent_logs(
             agent_logs,
             worktree_path,
             entry_index_counter,
             msg_store,
         ));
+
+        vec![log_lines, agent_logs]
     }
 }
 impl Opencode {
@@ -221,6 +232,7 @@ impl Opencode {
                 entry_type: NormalizedEntryType::ToolUse {
                     tool_name,
                     action_type,
+                    result: None,
                 },
                 content: tool_content,
                 metadata: None,
@@ -22,12 +22,45 @@ pub enum NormalizedEntryType {
     ToolUse {
         tool_name: String,
         action_type: ActionType,
+        /// Filled in once the agent reports the outcome of the call
+        #[serde(default)]
+        result: Option<ToolResult>,
     },
     SystemMessage,
     ErrorMessage,
     Thinking,
 }
 
+/// Longest tool output kept in a [`ToolResult`]
+const TOOL_RESULT_OUTPUT_CHARS: usize = 2000;
+
+/// Outcome of a tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ToolResult {
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    /// Start of the tool's output, truncated to keep the conversation small
+    pub output: Option<String>,
+}
+
+impl ToolResult {
+    pub fn new(success: bool, exit_code: Option<i32>, output: Option<&str>) -> Self {
+        let output = output
+            .map(str::trim_end)
+            .filter(|o| !o.is_empty())
+            .map(|o| match o.char_indices().nth(TOOL_RESULT_OUTPUT_CHARS) {
+                Some((end, _)) => format!("{}\n…", &o[..end]),
+                None => o.to_string(),
+            });
+        Self {
+            success,
+            exit_code,
+            output,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct NormalizedEntry {
     pub timestamp: Option<String>,
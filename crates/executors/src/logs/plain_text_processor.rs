@@ -413,6 +413,7 @@ mod tests {
                         action_type: super::super::ActionType::Other {
                             description: tool_name.to_string(),
                         },
+                        result: None,
                     },
                     content,
                     metadata: None,
@@ -2,6 +2,8 @@
 
 pub mod entry_index;
 pub mod patch;
+pub mod tool_results;
 
 pub use entry_index::EntryIndexProvider;
 pub use patch::ConversationPatch;
+pub use tool_results::ToolResultTracker;
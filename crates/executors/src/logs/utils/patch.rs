@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
-use serde_json::{from_value, json};
+use serde_json::{Value, from_value, json, to_value};
 use ts_rs::TS;
 use utils::diff::Diff;
 
@@ -102,6 +104,78 @@ impl ConversationPatch {
         .unwrap()
     }
 
+    /// Normalized entries newly added by a patch; replaced entries are not included
+    pub fn added_normalized_entries(patch: &Patch) -> Vec<NormalizedEntry> {
+        patch
+            .0
+            .iter()
+            .filter_map(|op| match op {
+                json_patch::PatchOperation::Add(add)
+                    if add.value.get("type") == Some(&json!("NORMALIZED_ENTRY")) =>
+                {
+                    from_value(add.value.get("content")?.clone()).ok()
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Apply a patch to a conversation keyed by entry index, ignoring non-normalized entries
+    pub fn apply_normalized_entries(patch: &Patch, entries: &mut BTreeMap<usize, NormalizedEntry>) {
+        let index = |path: &str| path.strip_prefix("/entries/")?.parse::<usize>().ok();
+        for op in &patch.0 {
+            match op {
+                json_patch::PatchOperation::Add(json_patch::AddOperation { path, value })
+                | json_patch::PatchOperation::Replace(json_patch::ReplaceOperation {
+                    path,
+                    value,
+                }) => {
+                    if value.get("type") != Some(&json!("NORMALIZED_ENTRY")) {
+                        continue;
+                    }
+                    let entry = value
+                        .get("content")
+                        .and_then(|content| from_value(content.clone()).ok());
+                    if let (Some(index), Some(entry)) = (index(path.as_str()), entry) {
+                        entries.insert(index, entry);
+                    }
+                }
+                json_patch::PatchOperation::Remove(remove) => {
+                    if let Some(index) = index(remove.path.as_str()) {
+                        entries.remove(&index);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Shift the entries a patch touches down by `first_entry`, so a conversation loaded from
+    /// that entry on can be patched on its own. Operations on earlier entries are dropped.
+    pub fn rebase(patch: &Patch, first_entry: usize) -> Option<Patch> {
+        let index = |path: &str| path.strip_prefix("/entries/")?.parse::<usize>().ok();
+        let Ok(Value::Array(ops)) = to_value(patch) else {
+            return None;
+        };
+        let ops: Vec<_> = ops
+            .into_iter()
+            .filter_map(|mut op| {
+                let path = op.get("path")?.as_str()?;
+                if let Some(entry_index) = index(path) {
+                    op["path"] = json!(format!(
+                        "/entries/{}",
+                        entry_index.checked_sub(first_entry)?
+                    ));
+                }
+                Some(op)
+            })
+            .collect();
+        if ops.is_empty() {
+            return None;
+        }
+        from_value(json!(ops)).ok()
+    }
+
     /// Create a REPLACE patch for updating an existing conversation entry at the given index
     pub fn replace(entry_index: usize, entry: NormalizedEntry) -> Patch {
         let patch_entry = PatchEntry {
@@ -113,3 +187,53 @@ impl ConversationPatch {
         from_value(json!([patch_entry])).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::NormalizedEntryType;
+
+    fn entry(content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_normalized_entries() {
+        let mut entries = BTreeMap::new();
+        ConversationPatch::apply_normalized_entries(
+            &ConversationPatch::add_normalized_entry(1, entry("second")),
+            &mut entries,
+        );
+        ConversationPatch::apply_normalized_entries(
+            &ConversationPatch::add_normalized_entry(0, entry("first")),
+            &mut entries,
+        );
+        ConversationPatch::apply_normalized_entries(
+            &ConversationPatch::replace(1, entry("second, updated")),
+            &mut entries,
+        );
+        ConversationPatch::apply_normalized_entries(
+            &ConversationPatch::add_stdout(2, "raw".to_string()),
+            &mut entries,
+        );
+
+        let contents: Vec<_> = entries.values().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["first", "second, updated"]);
+    }
+
+    #[test]
+    fn test_rebase() {
+        let rebased =
+            ConversationPatch::rebase(&ConversationPatch::replace(5, entry("sixth")), 3).unwrap();
+        assert_eq!(rebased, ConversationPatch::replace(2, entry("sixth")));
+
+        assert!(
+            ConversationPatch::rebase(&ConversationPatch::replace(2, entry("third")), 3).is_none()
+        );
+    }
+}
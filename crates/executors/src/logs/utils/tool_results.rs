@@ -0,0 +1,145 @@
+//! Correlates tool results with the tool-use entries they belong to
+
+use std::collections::HashMap;
+
+use json_patch::Patch;
+
+use crate::logs::{NormalizedEntry, NormalizedEntryType, ToolResult, utils::ConversationPatch};
+
+/// Remembers where the entries of each tool call are in the conversation, so a result the
+/// agent reports later can be attached to them
+#[derive(Debug, Default)]
+pub struct ToolResultTracker {
+    tool_uses: HashMap<String, Vec<(usize, NormalizedEntry)>>,
+    results: HashMap<String, ToolResult>,
+}
+
+impl ToolResultTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a tool-use entry added or replaced at `index`. Returns the entry, with the
+    /// result attached if the call has already been reported.
+    pub fn track(
+        &mut self,
+        call_id: &str,
+        index: usize,
+        mut entry: NormalizedEntry,
+    ) -> NormalizedEntry {
+        if let Some(result) = self.results.get(call_id) {
+            attach(&mut entry, result.clone());
+        }
+        let entries = self.tool_uses.entry(call_id.to_string()).or_default();
+        match entries.iter_mut().find(|(i, _)| *i == index) {
+            Some((_, tracked)) => *tracked = entry.clone(),
+            None => entries.push((index, entry.clone())),
+        }
+        entry
+    }
+
+    /// Attach a result to the entries of a tool call, returning the patches that replace them
+    pub fn resolve(&mut self, call_id: &str, result: ToolResult) -> Vec<Patch> {
+        self.results.insert(call_id.to_string(), result.clone());
+        self.tool_uses
+            .get_mut(call_id)
+            .map(|entries| {
+                entries
+                    .iter_mut()
+                    .map(|(index, entry)| {
+                        attach(entry, result.clone());
+                        ConversationPatch::replace(*index, entry.clone())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Forget entry positions after the conversation was cleared; reported results are kept
+    pub fn reset(&mut self) {
+        self.tool_uses.clear();
+    }
+}
+
+fn attach(entry: &mut NormalizedEntry, tool_result: ToolResult) {
+    if let NormalizedEntryType::ToolUse { result, .. } = &mut entry.entry_type {
+        *result = Some(tool_result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::ActionType;
+
+    fn tool_use() -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ToolUse {
+                tool_name: "bash".to_string(),
+                action_type: ActionType::CommandRun {
+                    command: "ls".to_string(),
+                },
+                result: None,
+            },
+            content: "`ls`".to_string(),
+            metadata: None,
+        }
+    }
+
+    fn result_of(patch: &Patch) -> Option<ToolResult> {
+        let entry: NormalizedEntry = serde_json::from_value(
+            serde_json::to_value(patch).unwrap()[0]["value"]["content"].clone(),
+        )
+        .unwrap();
+        match entry.entry_type {
+            NormalizedEntryType::ToolUse { result, .. } => result,
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_result_replaces_tracked_entry() {
+        let mut tracker = ToolResultTracker::new();
+        tracker.track("call_1", 3, tool_use());
+
+        let result = ToolResult::new(false, Some(2), Some("ls: missing\n"));
+        let patches = tracker.resolve("call_1", result.clone());
+        assert_eq!(patches.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&patches[0]).unwrap()[0]["path"],
+            "/entries/3"
+        );
+        assert_eq!(result_of(&patches[0]), Some(result));
+        assert!(
+            tracker
+                .resolve("unknown", ToolResult::new(true, None, None))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_result_survives_re_emitted_entry() {
+        let mut tracker = ToolResultTracker::new();
+        tracker.track("call_1", 0, tool_use());
+        tracker.resolve("call_1", ToolResult::new(true, Some(0), None));
+
+        let entry = tracker.track("call_1", 0, tool_use());
+        assert!(matches!(
+            entry.entry_type,
+            NormalizedEntryType::ToolUse {
+                result: Some(ToolResult { success: true, .. }),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_output_is_truncated() {
+        let result = ToolResult::new(true, None, Some(&"x".repeat(5000)));
+        let output = result.output.unwrap();
+        assert!(output.chars().count() < 2100);
+        assert!(output.ends_with('…'));
+        assert_eq!(ToolResult::new(true, None, Some("  \n")).output, None);
+    }
+}
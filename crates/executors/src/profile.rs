@@ -1,12 +1,9 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-    path::PathBuf,
-    sync::RwLock,
-};
+use std::{collections::HashMap, fs, path::PathBuf, sync::RwLock};
 
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
 use ts_rs::TS;
 
 use crate::executors::CodingAgent;
@@ -27,6 +24,43 @@ pub struct VariantAgentConfig {
     pub agent: CodingAgent,
     /// Optional profile-specific MCP config file path (absolute; supports leading ~). Overrides the default `BaseCodingAgent` config path
     pub mcp_config_path: Option<String>,
+    /// Limits after which a running execution of this variant is stopped
+    #[serde(default, skip_serializing_if = "ExecutionLimits::is_unlimited")]
+    pub limits: ExecutionLimits,
+}
+
+/// Limits on a single coding agent execution. Unset limits are unlimited.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+pub struct ExecutionLimits {
+    /// Maximum wall-clock time the process may run for
+    pub max_duration_secs: Option<u64>,
+    /// Maximum time the process may go without writing to stdout
+    pub max_idle_secs: Option<u64>,
+    /// Maximum number of tool calls in the normalized logs
+    pub max_tool_calls: Option<u64>,
+}
+
+impl ExecutionLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_duration_secs.is_none()
+            && self.max_idle_secs.is_none()
+            && self.max_tool_calls.is_none()
+    }
+
+    /// Combine two sets of limits, keeping the stricter value of each
+    pub fn min(&self, other: &ExecutionLimits) -> ExecutionLimits {
+        fn stricter(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+        ExecutionLimits {
+            max_duration_secs: stricter(self.max_duration_secs, other.max_duration_secs),
+            max_idle_secs: stricter(self.max_idle_secs, other.max_idle_secs),
+            max_tool_calls: stricter(self.max_tool_calls, other.max_tool_calls),
+        }
+    }
 }
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ProfileConfig {
@@ -35,6 +69,9 @@ pub struct ProfileConfig {
     pub default: VariantAgentConfig,
     /// additional variants for this profile, e.g. plan, review, subagent
     pub variants: Vec<VariantAgentConfig>,
+    /// labels of the profiles to retry with, in order, when an attempt with this profile fails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<String>,
 }
 
 impl ProfileConfig {
@@ -76,6 +113,175 @@ pub struct ProfileConfigs {
     pub profiles: Vec<ProfileConfig>,
 }
 
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("Failed to parse profiles.json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Profile is missing a label")]
+    MissingLabel,
+    #[error("Profile '{label}' extends unknown profile '{extends}'")]
+    UnknownBase { label: String, extends: String },
+    #[error("Profile inheritance cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    #[error("Invalid profile '{label}': {source}")]
+    Invalid {
+        label: String,
+        source: serde_json::Error,
+    },
+}
+
+/// profiles.json as written by the user, before `extends` is resolved
+#[derive(Deserialize)]
+struct RawProfiles {
+    profiles: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProfileLayer {
+    /// Profiles that are already loaded, e.g. the defaults
+    Base,
+    /// Profiles from profiles.json
+    User,
+}
+
+/// Resolves `extends` by merging profiles over the raw JSON of the profile they extend.
+/// A user profile extending its own label inherits from the base profile it replaces.
+struct ProfileResolver {
+    base: HashMap<String, Value>,
+    user: HashMap<String, Value>,
+}
+
+impl ProfileResolver {
+    fn get(&self, label: &str, layer: ProfileLayer) -> Option<&Value> {
+        match layer {
+            ProfileLayer::Base => self.base.get(label),
+            ProfileLayer::User => self.user.get(label),
+        }
+    }
+
+    fn resolve(
+        &self,
+        label: &str,
+        layer: ProfileLayer,
+        stack: &mut Vec<(String, ProfileLayer)>,
+    ) -> Result<Value, ProfileError> {
+        if let Some(start) = stack.iter().position(|(l, ly)| l == label && *ly == layer) {
+            let mut cycle: Vec<String> = stack[start..].iter().map(|(l, _)| l.clone()).collect();
+            cycle.push(label.to_string());
+            return Err(ProfileError::Cycle(cycle));
+        }
+        let mut profile = self
+            .get(label, layer)
+            .cloned()
+            .expect("profiles are looked up before they are resolved");
+
+        stack.push((label.to_string(), layer));
+        if let Some(extends) = take_extends(&mut profile) {
+            let base = self.resolve_base(label, layer, &extends, stack)?;
+            profile = merge_profile(base, profile);
+        }
+
+        // Variants inherit the default variant of the profile they extend
+        let mut variants = match profile.get_mut("variants") {
+            Some(Value::Array(variants)) => std::mem::take(variants),
+            _ => Vec::new(),
+        };
+        for variant in &mut variants {
+            let Some(extends) = take_extends(variant) else {
+                continue;
+            };
+            let mut base = if extends == label {
+                profile.clone()
+            } else {
+                self.resolve_base(label, layer, &extends, stack)?
+            };
+            if let Some(base) = base.as_object_mut() {
+                base.remove("variants");
+                base.remove("fallbacks");
+            }
+            *variant = merge_json(base, variant.take());
+        }
+        if let Some(profile) = profile.as_object_mut() {
+            profile.insert("variants".to_string(), Value::Array(variants));
+        }
+        stack.pop();
+
+        Ok(profile)
+    }
+
+    fn resolve_base(
+        &self,
+        label: &str,
+        layer: ProfileLayer,
+        extends: &str,
+        stack: &mut Vec<(String, ProfileLayer)>,
+    ) -> Result<Value, ProfileError> {
+        let base_layer =
+            if layer == ProfileLayer::User && extends != label && self.user.contains_key(extends) {
+                ProfileLayer::User
+            } else {
+                ProfileLayer::Base
+            };
+        if self.get(extends, base_layer).is_none() {
+            return Err(ProfileError::UnknownBase {
+                label: label.to_string(),
+                extends: extends.to_string(),
+            });
+        }
+        self.resolve(extends, base_layer, stack)
+    }
+}
+
+fn take_extends(profile: &mut Value) -> Option<String> {
+    match profile.as_object_mut()?.remove("extends")? {
+        Value::String(extends) => Some(extends),
+        _ => None,
+    }
+}
+
+/// Merge `overlay` into `base`: objects are merged key by key, anything else is replaced
+fn merge_json(base: Value, overlay: Value) -> Value {
+    match (base, overlay) {
+        (Value::Object(mut base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                let merged = match base.remove(&key) {
+                    Some(base_value) => merge_json(base_value, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            Value::Object(base)
+        }
+        (_, overlay) => overlay,
+    }
+}
+
+/// Like `merge_json`, but variants are merged with the base variant of the same label
+fn merge_profile(mut base: Value, mut profile: Value) -> Value {
+    let base_variants = base.as_object_mut().and_then(|b| b.remove("variants"));
+    let variants = profile.as_object_mut().and_then(|p| p.remove("variants"));
+    let variants = match (base_variants, variants) {
+        (Some(Value::Array(mut base_variants)), Some(Value::Array(variants))) => {
+            for variant in variants {
+                match base_variants
+                    .iter_mut()
+                    .find(|v| v.get("label") == variant.get("label"))
+                {
+                    Some(base_variant) => *base_variant = merge_json(base_variant.take(), variant),
+                    None => base_variants.push(variant),
+                }
+            }
+            Value::Array(base_variants)
+        }
+        (base_variants, variants) => variants.or(base_variants).unwrap_or(Value::Array(vec![])),
+    };
+    let mut merged = merge_json(base, profile);
+    if let Some(merged) = merged.as_object_mut() {
+        merged.insert("variants".to_string(), variants);
+    }
+    merged
+}
+
 impl ProfileConfigs {
     pub fn get_cached() -> ProfileConfigs {
         PROFILES_CACHE.read().unwrap().clone()
@@ -87,27 +293,13 @@ impl ProfileConfigs {
     }
 
     fn load() -> Self {
-        let profiles_path = utils::assets::profiles_path();
-
-        // load from profiles.json if it exists, otherwise use defaults
-        let content = match fs::read_to_string(&profiles_path) {
-            Ok(content) => content,
-            Err(e) => {
-                tracing::warn!("Failed to read profiles.json: {}, using defaults", e);
-                return Self::from_defaults();
-            }
-        };
-
-        match serde_json::from_str::<Self>(&content) {
-            Ok(profiles) => {
-                tracing::info!("Loaded all profiles from profiles.json");
-                profiles
-            }
-            Err(e) => {
-                tracing::warn!("Failed to parse profiles.json: {}, using defaults", e);
-                Self::from_defaults()
-            }
+        // overlay profiles.json on the defaults if it exists and is valid
+        let mut profiles = Self::from_defaults();
+        match profiles.extend_from_file() {
+            Ok(()) => tracing::info!("Loaded profiles from profiles.json"),
+            Err(e) => tracing::warn!("{}, using defaults", e),
         }
+        profiles
     }
 
     pub fn from_defaults() -> Self {
@@ -128,31 +320,75 @@ impl ProfileConfigs {
 
         let content = fs::read_to_string(&profiles_path)?;
 
-        let user_profiles: Self = serde_json::from_str(&content).map_err(|e| {
-            std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Failed to parse profiles.json: {e}"),
-            )
-        })?;
+        self.extend_from_json(&content).map_err(|errors| {
+            let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            std::io::Error::new(std::io::ErrorKind::InvalidData, messages.join("; "))
+        })
+    }
 
-        let default_labels: HashSet<String> = self
+    /// Add the profiles of a profiles.json document, replacing loaded profiles with the same
+    /// label. Profiles and variants can `extends` another profile to override only some of
+    /// its fields. Nothing is changed if any profile is invalid.
+    pub fn extend_from_json(&mut self, content: &str) -> Result<(), Vec<ProfileError>> {
+        let raw: RawProfiles = serde_json::from_str(content).map_err(|e| vec![e.into()])?;
+
+        let mut errors = Vec::new();
+        let mut labels = Vec::new();
+        let mut user = HashMap::new();
+        for profile in raw.profiles {
+            match profile.get("label").and_then(Value::as_str) {
+                Some(label) => {
+                    if !labels.iter().any(|l| l == label) {
+                        labels.push(label.to_string());
+                    }
+                    user.insert(label.to_string(), profile);
+                }
+                None => errors.push(ProfileError::MissingLabel),
+            }
+        }
+        let base = self
             .profiles
             .iter()
-            .map(|p| p.default.label.clone())
-            .collect();
-
-        // Only add user profiles with unique labels
-        for user_profile in user_profiles.profiles {
-            if !default_labels.contains(&user_profile.default.label) {
-                self.profiles.push(user_profile);
-            } else {
-                tracing::debug!(
-                    "Skipping user profile '{}' - default with same label exists",
-                    user_profile.default.label
-                );
+            .map(|p| {
+                serde_json::to_value(p)
+                    .map(|value| (p.default.label.clone(), value))
+                    .map_err(ProfileError::from)
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|e| vec![e])?;
+        let resolver = ProfileResolver { base, user };
+
+        let mut resolved = Vec::new();
+        for label in labels {
+            let profile = resolver
+                .resolve(&label, ProfileLayer::User, &mut Vec::new())
+                .and_then(|value| {
+                    serde_json::from_value::<ProfileConfig>(value).map_err(|source| {
+                        ProfileError::Invalid {
+                            label: label.clone(),
+                            source,
+                        }
+                    })
+                });
+            match profile {
+                Ok(profile) => resolved.push(profile),
+                Err(e) => errors.push(e),
             }
         }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
 
+        for profile in resolved {
+            match self
+                .profiles
+                .iter_mut()
+                .find(|p| p.default.label == profile.default.label)
+            {
+                Some(existing) => *existing = profile,
+                None => self.profiles.push(profile),
+            }
+        }
         Ok(())
     }
 
@@ -160,6 +396,48 @@ impl ProfileConfigs {
         self.profiles.iter().find(|p| p.default.label == label)
     }
 
+    /// Check that a label names a known profile and, if it has one, a variant of it
+    pub fn validate_label(&self, label: &ProfileVariantLabel) -> Result<(), String> {
+        let Some(profile) = self.get_profile(&label.profile) else {
+            return Err(format!("Profile not found: {}", label.profile));
+        };
+        if let Some(variant) = &label.variant
+            && profile.get_variant(variant).is_none()
+        {
+            return Err(format!(
+                "Profile '{}' has no variant '{variant}'",
+                label.profile
+            ));
+        }
+        Ok(())
+    }
+
+    /// Execution limits of a profile variant, unlimited if the profile is unknown
+    pub fn get_limits(&self, label: &ProfileVariantLabel) -> ExecutionLimits {
+        let Some(profile) = self.get_profile(&label.profile) else {
+            return ExecutionLimits::default();
+        };
+        let variant = match &label.variant {
+            Some(variant) => profile.get_variant(variant),
+            None => Some(&profile.default),
+        };
+        variant.map(|v| v.limits.clone()).unwrap_or_default()
+    }
+
+    /// The profile to retry with after `failures` failed attempts of a fallback chain that
+    /// started with `profile`
+    pub fn get_fallback(&self, profile: &str, failures: usize) -> Option<&ProfileConfig> {
+        let label = self
+            .get_profile(profile)?
+            .fallbacks
+            .get(failures.checked_sub(1)?)?;
+        let fallback = self.get_profile(label);
+        if fallback.is_none() {
+            tracing::warn!("Unknown fallback profile '{}' for '{}'", label, profile);
+        }
+        fallback
+    }
+
     pub fn to_map(&self) -> HashMap<String, ProfileConfig> {
         self.profiles
             .iter()
@@ -188,18 +466,33 @@ mod tests {
                         CodingAgent::Codex(codex) => codex.command.build_initial(),
                         CodingAgent::Opencode(opencode) => opencode.command.build_initial(),
                         CodingAgent::Cursor(cursor) => cursor.command.build_initial(),
+                        CodingAgent::Aider(aider) => aider.command.build_initial(),
+                        CodingAgent::Custom(custom) => custom.command.build_initial(),
                     }
                 })
                 .unwrap_or_else(|| panic!("Profile not found: {label}"))
         };
         let profiles = ProfileConfigs::from_defaults();
-        assert!(profiles.profiles.len() == 8);
+        assert!(profiles.profiles.len() == 9);
 
         let claude_code_command = get_profile_command("claude-code");
         assert!(claude_code_command.contains("npx -y @anthropic-ai/claude-code@latest"));
         assert!(claude_code_command.contains("-p"));
         assert!(claude_code_command.contains("--dangerously-skip-permissions"));
 
+        let claude_approvals = profiles
+            .get_profile("claude-code")
+            .and_then(|p| p.get_variant("approvals"))
+            .expect("claude-code approvals variant");
+        match &claude_approvals.agent {
+            crate::executors::CodingAgent::ClaudeCode(claude) => {
+                assert!(claude.approvals);
+                let command = claude.command.build_initial();
+                assert!(!command.contains("--dangerously-skip-permissions"));
+            }
+            _ => panic!("Expected ClaudeCode agent"),
+        }
+
         let claude_code_router_command = get_profile_command("claude-code-router");
         assert!(claude_code_router_command.contains("npx -y @musistudio/claude-code-router code"));
         assert!(claude_code_router_command.contains("-p"));
@@ -229,6 +522,11 @@ mod tests {
         assert!(cursor_command.contains("cursor-agent"));
         assert!(cursor_command.contains("-p"));
         assert!(cursor_command.contains("--output-format=stream-json"));
+
+        let aider_command = get_profile_command("aider");
+        assert!(aider_command.contains("aider"));
+        assert!(aider_command.contains("--yes-always"));
+        assert!(aider_command.contains("--no-auto-commits"));
     }
 
     #[test]
@@ -250,6 +548,10 @@ mod tests {
                 {
                     "label": "test-gemini",
                     "mcp_config_path": null,
+                    "limits": {
+                        "max_duration_secs": 600,
+                        "max_tool_calls": 50
+                    },
                     "GEMINI": {
                         "command": {
                             "base": "npx gemini",
@@ -271,6 +573,7 @@ mod tests {
                 assert_eq!(claude.command.base, "npx claude");
                 assert_eq!(claude.command.params.as_ref().unwrap()[0], "--test");
                 assert!(claude.plan);
+                assert!(!claude.approvals);
             }
             _ => panic!("Expected ClaudeCode agent"),
         }
@@ -284,5 +587,169 @@ mod tests {
             }
             _ => panic!("Expected Gemini agent"),
         }
+        assert!(claude_profile.default.limits.is_unlimited());
+        let limits = profiles.get_limits(&ProfileVariantLabel::default("test-gemini".to_string()));
+        assert_eq!(limits.max_duration_secs, Some(600));
+        assert_eq!(limits.max_idle_secs, None);
+        assert_eq!(limits.max_tool_calls, Some(50));
+    }
+
+    #[test]
+    fn test_validate_label() {
+        let profiles = ProfileConfigs::from_defaults();
+        let label = |profile: &str, variant: Option<&str>| ProfileVariantLabel {
+            profile: profile.to_string(),
+            variant: variant.map(str::to_string),
+        };
+
+        assert!(profiles.validate_label(&label("claude-code", None)).is_ok());
+        assert!(
+            profiles
+                .validate_label(&label("claude-code", Some("plan")))
+                .is_ok()
+        );
+        assert!(
+            profiles
+                .validate_label(&label("claude-code", Some("missing")))
+                .is_err()
+        );
+        assert!(profiles.validate_label(&label("missing", None)).is_err());
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        let test_json = r#"{
+            "profiles": [
+                {
+                    "label": "test-claude",
+                    "mcp_config_path": null,
+                    "CLAUDE_CODE": { "command": { "base": "npx claude" }, "plan": false },
+                    "variants": [],
+                    "fallbacks": ["test-gemini", "missing"]
+                },
+                {
+                    "label": "test-gemini",
+                    "mcp_config_path": null,
+                    "GEMINI": { "command": { "base": "npx gemini" } },
+                    "variants": []
+                }
+            ]
+        }"#;
+        let profiles: ProfileConfigs = serde_json::from_str(test_json).expect("Should deserialize");
+
+        let fallback = profiles.get_fallback("test-claude", 1).unwrap();
+        assert_eq!(fallback.default.label, "test-gemini");
+        assert!(profiles.get_fallback("test-claude", 0).is_none());
+        // Unknown labels and the end of the chain stop the fallbacks
+        assert!(profiles.get_fallback("test-claude", 2).is_none());
+        assert!(profiles.get_fallback("test-claude", 3).is_none());
+        assert!(profiles.get_fallback("test-gemini", 1).is_none());
+    }
+
+    #[test]
+    fn test_extends_overrides_only_given_fields() {
+        let test_json = r#"{
+            "profiles": [
+                {
+                    "label": "claude-code",
+                    "extends": "claude-code",
+                    "CLAUDE_CODE": { "plan": true },
+                    "variants": [
+                        { "label": "plan", "mcp_config_path": "~/plan-mcp.json" }
+                    ]
+                },
+                {
+                    "label": "my-claude",
+                    "extends": "claude-code",
+                    "CLAUDE_CODE": { "command": { "params": ["-p", "--verbose"] } },
+                    "variants": [
+                        { "label": "router", "extends": "claude-code-router" }
+                    ]
+                }
+            ]
+        }"#;
+        let mut profiles = ProfileConfigs::from_defaults();
+        let count = profiles.profiles.len();
+        profiles.extend_from_json(test_json).unwrap();
+        assert_eq!(profiles.profiles.len(), count + 1);
+
+        // Overriding a default keeps everything that isn't overridden
+        let claude = profiles.get_profile("claude-code").unwrap();
+        let CodingAgent::ClaudeCode(agent) = &claude.default.agent else {
+            panic!("Expected ClaudeCode agent");
+        };
+        assert!(agent.plan);
+        assert_eq!(
+            agent.command.base,
+            "npx -y @anthropic-ai/claude-code@latest"
+        );
+        let plan = claude.get_variant("plan").unwrap();
+        assert_eq!(plan.mcp_config_path.as_deref(), Some("~/plan-mcp.json"));
+        assert!(claude.get_variant("approvals").is_some());
+
+        // New profiles inherit the overridden default and its variants
+        let my_claude = profiles.get_profile("my-claude").unwrap();
+        let CodingAgent::ClaudeCode(agent) = &my_claude.default.agent else {
+            panic!("Expected ClaudeCode agent");
+        };
+        assert!(agent.plan);
+        assert_eq!(
+            agent.command.params.as_deref(),
+            Some(&["-p".to_string(), "--verbose".to_string()][..])
+        );
+        assert!(my_claude.get_variant("approvals").is_some());
+        let router = my_claude.get_variant("router").unwrap();
+        let CodingAgent::ClaudeCode(agent) = &router.agent else {
+            panic!("Expected ClaudeCode agent");
+        };
+        assert_eq!(
+            agent.command.base,
+            "npx -y @musistudio/claude-code-router code"
+        );
+    }
+
+    #[test]
+    fn test_extends_errors() {
+        let test_json = r#"{
+            "profiles": [
+                { "label": "a", "extends": "b" },
+                { "label": "b", "extends": "a" },
+                { "label": "c", "extends": "missing" },
+                { "label": "d", "extends": "amp", "AMP": { "command": 42 } }
+            ]
+        }"#;
+        let mut profiles = ProfileConfigs::from_defaults();
+        let errors = profiles.extend_from_json(test_json).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0], "Profile inheritance cycle: a -> b -> a");
+        assert_eq!(messages[1], "Profile inheritance cycle: b -> a -> b");
+        assert_eq!(messages[2], "Profile 'c' extends unknown profile 'missing'");
+        assert!(messages[3].starts_with("Invalid profile 'd'"));
+        // Nothing is applied when a profile is invalid
+        assert_eq!(profiles, ProfileConfigs::from_defaults());
+    }
+
+    #[test]
+    fn test_execution_limits_min_keeps_stricter_values() {
+        let profile = ExecutionLimits {
+            max_duration_secs: Some(600),
+            max_idle_secs: None,
+            max_tool_calls: Some(50),
+        };
+        let project = ExecutionLimits {
+            max_duration_secs: Some(1200),
+            max_idle_secs: Some(120),
+            max_tool_calls: None,
+        };
+        assert_eq!(
+            profile.min(&project),
+            ExecutionLimits {
+                max_duration_secs: Some(600),
+                max_idle_secs: Some(120),
+                max_tool_calls: Some(50),
+            }
+        );
+        assert!(ExecutionLimits::default().is_unlimited());
     }
 }
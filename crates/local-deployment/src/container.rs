@@ -1,60 +1,92 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     io,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::anyhow;
 use async_stream::try_stream;
 use async_trait::async_trait;
 use axum::response::sse::Event;
+use chrono::Utc;
 use command_group::AsyncGroupChild;
 use db::{
     DBService,
     models::{
         execution_process::{
-            ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+            DevServerHealth, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
+            ExecutionProcessStatus, ExecutionProcessStopReason,
         },
         executor_session::ExecutorSession,
+        plan_review::{CreatePlanReview, PlanReview},
         project::Project,
+        review_comment::ReviewComment,
         task::{Task, TaskStatus},
         task_attempt::TaskAttempt,
     },
 };
 use deployment::DeploymentError;
 use executors::{
-    actions::{Executable, ExecutorAction},
-    logs::utils::{ConversationPatch, patch::escape_json_pointer_segment},
+    actions::{Executable, ExecutorAction, ExecutorActionType, dev_server::DevServerHealthCheck},
+    env::{ResourceLimits, Sandbox},
+    executors::{CodingAgent, StandardCodingAgentExecutor, claude::approvals_mcp_config_path},
+    logs::{
+        ActionType, NormalizedEntryType,
+        utils::{ConversationPatch, patch::escape_json_pointer_segment},
+    },
+    profile::{ExecutionLimits, ProfileConfigs},
 };
 use futures::{StreamExt, TryStreamExt, stream::select};
 use notify_debouncer_full::DebouncedEvent;
+use regex::Regex;
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
+    code_review,
     config::Config,
     container::{ContainerError, ContainerRef, ContainerService},
-    filesystem_watcher,
+    dev_server, filesystem_watcher,
     git::{DiffTarget, GitService},
     notification::NotificationService,
     worktree_manager::WorktreeManager,
 };
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{
+    io::AsyncWriteExt,
+    process::ChildStdin,
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+};
 use tokio_util::io::ReaderStream;
 use utils::{
+    cgroup::ExecutionCgroup,
     log_msg::LogMsg,
     msg_store::MsgStore,
+    process_stats::{ProcessStats, process_group_stats, process_group_usage},
     text::{git_branch_id, short_uuid},
 };
 use uuid::Uuid;
 
 use crate::command;
 
+/// Open stdin of an agent that accepts messages while it runs
+struct LiveInput {
+    stdin: ChildStdin,
+    /// Turns started (the prompt plus each message sent) that have not finished yet
+    open_turns: usize,
+}
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
+    live_inputs: Arc<RwLock<HashMap<Uuid, Arc<Mutex<LiveInput>>>>>,
+    execution_queue_lock: Arc<Mutex<()>>,
+    /// Restarts in a row of each running dev server that was restarted after crashing
+    dev_server_restarts: Arc<RwLock<HashMap<Uuid, u32>>>,
+    /// Worktree of each running code review, and the commit it was at when the review started
+    review_heads: Arc<RwLock<HashMap<Uuid, (PathBuf, String)>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
     config: Arc<RwLock<Config>>,
     git: GitService,
@@ -74,6 +106,10 @@ impl LocalContainerService {
         LocalContainerService {
             db,
             child_store,
+            live_inputs: Arc::new(RwLock::new(HashMap::new())),
+            execution_queue_lock: Arc::new(Mutex::new(())),
+            dev_server_restarts: Arc::new(RwLock::new(HashMap::new())),
+            review_heads: Arc::new(RwLock::new(HashMap::new())),
             msg_stores,
             config,
             git,
@@ -96,6 +132,428 @@ impl LocalContainerService {
         map.remove(id);
     }
 
+    /// Keep the stdin of an agent that accepts messages mid-execution, and close it once
+    /// every turn has completed so the agent can exit.
+    async fn track_live_input(&self, id: Uuid, executor: CodingAgent, stdin: ChildStdin) {
+        let live_input = Arc::new(Mutex::new(LiveInput {
+            stdin,
+            open_turns: 1,
+        }));
+        self.live_inputs
+            .write()
+            .await
+            .insert(id, live_input.clone());
+
+        let Some(store) = self.get_msg_store_by_id(&id).await else {
+            return;
+        };
+        let live_inputs = self.live_inputs.clone();
+        tokio::spawn(async move {
+            let mut lines = store.stdout_lines_stream();
+            while let Some(Ok(line)) = lines.next().await {
+                if !executor.is_turn_complete(&line) {
+                    continue;
+                }
+                // Hold the map lock so no message can be sent while stdin is closing
+                let mut map = live_inputs.write().await;
+                let mut input = live_input.lock().await;
+                input.open_turns = input.open_turns.saturating_sub(1);
+                if input.open_turns == 0 {
+                    map.remove(&id);
+                    if let Err(e) = input.stdin.shutdown().await {
+                        tracing::debug!("Failed to close stdin for execution {}: {}", id, e);
+                    }
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Stop a coding agent once it runs longer, stays silent on stdout longer, or makes
+    /// more tool calls than its limits allow, or any execution once its processes together
+    /// use more resources than allowed. The execution's cgroup, if it has one, is removed
+    /// once it finishes.
+    async fn spawn_limit_monitor(
+        &self,
+        id: Uuid,
+        limits: ExecutionLimits,
+        resources: ResourceLimits,
+        cgroup: Option<ExecutionCgroup>,
+    ) {
+        if limits.is_unlimited() && resources.is_unlimited() {
+            return;
+        }
+        let Some(store) = self.get_msg_store_by_id(&id).await else {
+            if let Some(cgroup) = cgroup {
+                cgroup.remove();
+            }
+            return;
+        };
+        // The child leads its own process group, which its descendants stay in
+        let pgid = match self.get_child_from_store(&id).await {
+            Some(child) => child.read().await.id(),
+            None => None,
+        };
+        let mut msgs = store.history_plus_stream();
+        let container = self.clone();
+        tokio::spawn(async move {
+            let started_at = Instant::now();
+            let mut last_stdout_at = started_at;
+            let mut tool_calls = 0u64;
+            let mut ticker = tokio::time::interval(Duration::from_secs(1));
+            let exceeded = |limit: Option<u64>, elapsed: Duration| {
+                limit.is_some_and(|secs| elapsed >= Duration::from_secs(secs))
+            };
+
+            let reason = loop {
+                tokio::select! {
+                    msg = msgs.next() => match msg {
+                        Some(Ok(LogMsg::Stdout(_))) => last_stdout_at = Instant::now(),
+                        Some(Ok(LogMsg::JsonPatch(patch))) => {
+                            tool_calls += ConversationPatch::added_normalized_entries(&patch)
+                                .iter()
+                                .filter(|e| matches!(e.entry_type, NormalizedEntryType::ToolUse { .. }))
+                                .count() as u64;
+                            if limits.max_tool_calls.is_some_and(|max| tool_calls > max) {
+                                break Some(ExecutionProcessStopReason::MaxToolCalls);
+                            }
+                        }
+                        Some(Ok(LogMsg::Finished)) | None => break None,
+                        _ => {}
+                    },
+                    _ = ticker.tick() => {
+                        if exceeded(limits.max_duration_secs, started_at.elapsed()) {
+                            break Some(ExecutionProcessStopReason::MaxDuration);
+                        }
+                        if exceeded(limits.max_idle_secs, last_stdout_at.elapsed()) {
+                            break Some(ExecutionProcessStopReason::MaxIdle);
+                        }
+                        if let Some(reason) =
+                            Self::exceeded_resource(&resources, cgroup.as_ref(), pgid)
+                        {
+                            break Some(reason);
+                        }
+                    }
+                }
+            };
+            if let Some(reason) = reason {
+                container.stop_for_limit(id, reason).await;
+            }
+            if let Some(cgroup) = cgroup {
+                cgroup.remove();
+            }
+        });
+    }
+
+    /// The resource limit the processes of an execution together exceed, if any. With a
+    /// cgroup the kernel holds them to their memory and process limits, so only CPU time is
+    /// checked; without one they're measured through their process group.
+    fn exceeded_resource(
+        resources: &ResourceLimits,
+        cgroup: Option<&ExecutionCgroup>,
+        pgid: Option<u32>,
+    ) -> Option<ExecutionProcessStopReason> {
+        if resources.is_unlimited() {
+            return None;
+        }
+        let over = |max: Option<u64>, used: f64| max.is_some_and(|max| used > max as f64);
+        if let Some(cgroup) = cgroup {
+            return over(resources.max_cpu_secs, cgroup.cpu_secs()?)
+                .then_some(ExecutionProcessStopReason::MaxCpu);
+        }
+
+        let usage = process_group_usage(pgid?)?;
+        if over(resources.max_memory_bytes(), usage.rss_bytes as f64) {
+            Some(ExecutionProcessStopReason::MaxMemory)
+        } else if over(resources.max_processes, usage.process_count as f64) {
+            Some(ExecutionProcessStopReason::MaxProcesses)
+        } else if over(resources.max_cpu_secs, usage.cpu_secs) {
+            Some(ExecutionProcessStopReason::MaxCpu)
+        } else {
+            None
+        }
+    }
+
+    /// Record the plan a plan-mode coding agent presents, so it can be reviewed and approved
+    /// once the agent has stopped
+    async fn spawn_plan_monitor(&self, id: Uuid) {
+        let Some(store) = self.get_msg_store_by_id(&id).await else {
+            return;
+        };
+        let mut msgs = store.history_plus_stream();
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            while let Some(Ok(msg)) = msgs.next().await {
+                let patch = match msg {
+                    LogMsg::JsonPatch(patch) => patch,
+                    LogMsg::Finished => return,
+                    _ => continue,
+                };
+                let plan = ConversationPatch::added_normalized_entries(&patch)
+                    .into_iter()
+                    .find_map(|entry| match entry.entry_type {
+                        NormalizedEntryType::ToolUse {
+                            action_type: ActionType::PlanPresentation { plan },
+                            ..
+                        } => Some(plan),
+                        _ => None,
+                    });
+                if let Some(plan) = plan {
+                    let data = CreatePlanReview {
+                        execution_process_id: id,
+                        plan,
+                    };
+                    if let Err(e) = PlanReview::create(&db.pool, &data, Uuid::new_v4()).await {
+                        tracing::error!("Failed to record plan presented by {}: {}", id, e);
+                    }
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Record the first local URL a dev server prints, so it can be previewed through the
+    /// vibe-kanban port, and keep its health up to date from its health check
+    async fn spawn_dev_server_monitor(&self, id: Uuid, health_check: Option<DevServerHealthCheck>) {
+        let Some(store) = self.get_msg_store_by_id(&id).await else {
+            return;
+        };
+        let db = self.db.clone();
+
+        // Without a health check, a dev server counts as healthy while it runs
+        let health = if health_check.is_some() {
+            DevServerHealth::Starting
+        } else {
+            DevServerHealth::Healthy
+        };
+        if let Err(e) = ExecutionProcess::set_dev_server_health(&db.pool, id, health).await {
+            tracing::error!("Failed to record dev server health of {}: {}", id, e);
+        }
+        let mut log_regex = match health_check {
+            Some(DevServerHealthCheck::Url { url }) => {
+                self.spawn_health_check_poller(id, url);
+                None
+            }
+            Some(DevServerHealthCheck::LogRegex { pattern }) => match Regex::new(&pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    tracing::error!("Invalid health check pattern for dev server {}: {}", id, e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let mut msgs = store.history_plus_stream();
+        tokio::spawn(async move {
+            let mut url_found = false;
+            let mut stdout = String::new();
+            let mut stderr = String::new();
+            while let Some(Ok(msg)) = msgs.next().await {
+                let (buffer, content) = match msg {
+                    LogMsg::Stdout(content) => (&mut stdout, content),
+                    LogMsg::Stderr(content) => (&mut stderr, content),
+                    LogMsg::Finished => return,
+                    _ => continue,
+                };
+                buffer.push_str(&content);
+                let Some(end) = buffer.rfind('\n') else {
+                    continue;
+                };
+                let url = buffer[..end].lines().find_map(dev_server::detect_url);
+                let healthy = log_regex
+                    .as_ref()
+                    .is_some_and(|regex| buffer[..end].lines().any(|line| regex.is_match(line)));
+                buffer.drain(..=end);
+
+                if !url_found && let Some(url) = url {
+                    url_found = true;
+                    if let Err(e) = ExecutionProcess::set_dev_server_url(&db.pool, id, &url).await {
+                        tracing::error!("Failed to record dev server URL of {}: {}", id, e);
+                    }
+                }
+                if healthy {
+                    log_regex = None;
+                    if let Err(e) = ExecutionProcess::set_dev_server_health(
+                        &db.pool,
+                        id,
+                        DevServerHealth::Healthy,
+                    )
+                    .await
+                    {
+                        tracing::error!("Failed to record dev server health of {}: {}", id, e);
+                    }
+                }
+                if url_found && log_regex.is_none() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Poll a dev server's health check URL for as long as it runs. It stays starting until
+    /// the first successful check, and is unhealthy whenever a check fails after that.
+    fn spawn_health_check_poller(&self, id: Uuid, url: String) {
+        let db = self.db.clone();
+        let child_store = self.child_store.clone();
+        tokio::spawn(async move {
+            let client = reqwest::Client::new();
+            let mut health = DevServerHealth::Starting;
+            loop {
+                tokio::time::sleep(dev_server::HEALTH_CHECK_INTERVAL).await;
+                if !child_store.read().await.contains_key(&id) {
+                    return;
+                }
+                let next = match (dev_server::check_url(&client, &url).await, &health) {
+                    (true, _) => DevServerHealth::Healthy,
+                    (false, DevServerHealth::Starting) => DevServerHealth::Starting,
+                    (false, _) => DevServerHealth::Unhealthy,
+                };
+                if next == health {
+                    continue;
+                }
+                if let Err(e) =
+                    ExecutionProcess::set_dev_server_health(&db.pool, id, next.clone()).await
+                {
+                    tracing::error!("Failed to record dev server health of {}: {}", id, e);
+                }
+                health = next;
+            }
+        });
+    }
+
+    /// Restart a dev server that exited without being stopped, waiting longer after each
+    /// crash in a row. It is left stopped if another dev server has taken its place in the
+    /// meantime, e.g. because the dev servers were started again.
+    async fn schedule_dev_server_restart(&self, ctx: &ExecutionContext) {
+        let process = &ctx.execution_process;
+        let ran_for = process
+            .completed_at
+            .unwrap_or_else(Utc::now)
+            .signed_duration_since(process.started_at);
+        let restarts = match self.dev_server_restarts.write().await.remove(&process.id) {
+            Some(_) if ran_for.to_std().unwrap_or_default() >= dev_server::STABLE_AFTER => 0,
+            Some(restarts) => restarts,
+            None => 0,
+        };
+        let Some(delay) = dev_server::restart_delay(restarts) else {
+            tracing::warn!(
+                "Dev server {} crashed {} times in a row, leaving it stopped",
+                process.id,
+                restarts + 1
+            );
+            return;
+        };
+        let Ok(action) = process.executor_action().cloned() else {
+            return;
+        };
+
+        let container = self.clone();
+        let task_attempt = ctx.task_attempt.clone();
+        let project_id = ctx.task.project_id;
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+
+            let name = action.dev_server().map(|dev_server| &dev_server.name);
+            let running = match ExecutionProcess::find_running_dev_servers_by_project(
+                &container.db.pool,
+                project_id,
+            )
+            .await
+            {
+                Ok(running) => running,
+                Err(e) => {
+                    tracing::error!("Failed to find running dev servers: {}", e);
+                    return;
+                }
+            };
+            let replaced = running.iter().any(|other| {
+                other.task_attempt_id != task_attempt.id
+                    || other
+                        .executor_action()
+                        .is_ok_and(|other| other.dev_server().map(|d| &d.name) == name)
+            });
+            if replaced {
+                return;
+            }
+
+            tracing::info!(
+                "Restarting dev server after it exited ({}s backoff)",
+                delay.as_secs()
+            );
+            match container
+                .start_execution(
+                    &task_attempt,
+                    &action,
+                    &ExecutionProcessRunReason::DevServer,
+                )
+                .await
+            {
+                Ok(restarted) => {
+                    container
+                        .dev_server_restarts
+                        .write()
+                        .await
+                        .insert(restarted.id, restarts + 1);
+                }
+                Err(e) => tracing::error!("Failed to restart dev server: {}", e),
+            }
+        });
+    }
+
+    /// Store the findings of a code review run, read from the reviewer's final message once
+    /// it has stopped
+    async fn spawn_review_monitor(&self, id: Uuid) {
+        let Some(store) = self.get_msg_store_by_id(&id).await else {
+            return;
+        };
+        let mut msgs = store.history_plus_stream();
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            let mut entries = BTreeMap::new();
+            while let Some(Ok(msg)) = msgs.next().await {
+                match msg {
+                    LogMsg::JsonPatch(patch) => {
+                        ConversationPatch::apply_normalized_entries(&patch, &mut entries)
+                    }
+                    LogMsg::Finished => break,
+                    _ => {}
+                }
+            }
+
+            let Some(message) = entries.into_values().rev().find_map(|entry| {
+                matches!(entry.entry_type, NormalizedEntryType::AssistantMessage)
+                    .then_some(entry.content)
+            }) else {
+                return;
+            };
+            for comment in code_review::parse_review_comments(&message) {
+                if let Err(e) = ReviewComment::create(&db.pool, id, &comment, Uuid::new_v4()).await
+                {
+                    tracing::error!("Failed to store review comment of {}: {}", id, e);
+                }
+            }
+        });
+    }
+
+    async fn stop_for_limit(&self, id: Uuid, reason: ExecutionProcessStopReason) {
+        let execution_process = match ExecutionProcess::find_by_id(&self.db.pool, id).await {
+            Ok(Some(ep)) if ep.status == ExecutionProcessStatus::Running => ep,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::error!("Failed to load execution process {}: {}", id, e);
+                return;
+            }
+        };
+        tracing::info!("Stopping execution process {} ({:?})", id, reason);
+        if let Err(e) = ExecutionProcess::set_stop_reason(&self.db.pool, id, reason).await {
+            tracing::error!("Failed to record stop reason for {}: {}", id, e);
+        }
+        if let Err(e) = self.stop_execution(&execution_process).await {
+            tracing::error!("Failed to stop execution process {}: {}", id, e);
+        }
+    }
+
     /// A context is finalized when
     /// - The next action is None (no follow-up actions)
     /// - The run reason is not DevServer
@@ -264,6 +722,21 @@ impl LocalContainerService {
         });
     }
 
+    /// Spawn a background task that starts tasks set to auto-start once all the tasks
+    /// they are blocked by are done, however they got there
+    pub fn spawn_dependency_monitor(&self) {
+        let container = self.clone();
+        let mut check_interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+        tokio::spawn(async move {
+            loop {
+                check_interval.tick().await;
+                if let Err(e) = container.start_unblocked_tasks().await {
+                    tracing::error!("Failed to start unblocked tasks: {}", e);
+                }
+            }
+        });
+    }
+
     /// Spawn a background task that polls the child process for completion and
     /// cleans up the execution entry when it exits.
     pub fn spawn_exit_monitor(&self, exec_id: &Uuid) -> JoinHandle<()> {
@@ -295,6 +768,16 @@ impl LocalContainerService {
 
                 // Update execution process and cleanup if exit
                 if let Some(status_result) = status_opt {
+                    Self::remove_approvals_mcp_config(exec_id).await;
+                    // A review only comments on the changes, so whatever it wrote or
+                    // committed is dropped before anything else can commit it
+                    if let Some((worktree, head)) =
+                        container.review_heads.write().await.remove(&exec_id)
+                        && let Err(e) = container.git.restore_worktree(&worktree, &head)
+                    {
+                        tracing::error!("Failed to undo the changes of review {}: {}", exec_id, e);
+                    }
+
                     // Update execution process record with completion info
                     let (exit_code, status) = match status_result {
                         Ok(exit_status) => {
@@ -322,6 +805,9 @@ impl LocalContainerService {
                     }
 
                     if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                        let mut started_pipeline = false;
+                        let mut started_tests = false;
+                        let mut started_review = false;
                         if matches!(
                             ctx.execution_process.status,
                             ExecutionProcessStatus::Completed
@@ -331,8 +817,39 @@ impl LocalContainerService {
                                 tracing::error!("Failed to commit changes after execution: {}", e);
                             }
 
+                            // The pipeline, tests, then a review, run before the next
+                            // action, which they take over
+                            started_pipeline = match container.try_start_pipeline(&ctx).await {
+                                Ok(started) => started,
+                                Err(e) => {
+                                    tracing::error!("Failed to start pipeline: {}", e);
+                                    false
+                                }
+                            };
+                            started_tests = !started_pipeline
+                                && match container.try_start_tests(&ctx).await {
+                                    Ok(started) => started,
+                                    Err(e) => {
+                                        tracing::error!("Failed to start tests: {}", e);
+                                        false
+                                    }
+                                };
+                            started_review = !started_pipeline
+                                && !started_tests
+                                && match container.try_start_review(&ctx).await {
+                                    Ok(started) => started,
+                                    Err(e) => {
+                                        tracing::error!("Failed to start code review: {}", e);
+                                        false
+                                    }
+                                };
+
                             // If the process exited successfully, start the next action
-                            if let Err(e) = container.try_start_next_action(&ctx).await {
+                            if !started_pipeline
+                                && !started_tests
+                                && !started_review
+                                && let Err(e) = container.try_start_next_action(&ctx).await
+                            {
                                 tracing::error!(
                                     "Failed to start next action after completion: {}",
                                     e
@@ -340,7 +857,74 @@ impl LocalContainerService {
                             }
                         }
 
-                        if Self::should_finalize(&ctx) {
+                        // Retry a failed coding agent with the next profile of its fallback chain
+                        let fell_back =
+                            matches!(ctx.execution_process.status, ExecutionProcessStatus::Failed)
+                                && matches!(
+                                    ctx.execution_process.run_reason,
+                                    ExecutionProcessRunReason::CodingAgent
+                                )
+                                && match container.try_start_fallback(&ctx).await {
+                                    Ok(task_attempt) => task_attempt.is_some(),
+                                    Err(e) => {
+                                        tracing::error!("Failed to start fallback attempt: {}", e);
+                                        false
+                                    }
+                                };
+
+                        // Have the agent fix failing tests
+                        let tests_failed =
+                            matches!(ctx.execution_process.status, ExecutionProcessStatus::Failed)
+                                && matches!(
+                                    ctx.execution_process.run_reason,
+                                    ExecutionProcessRunReason::TestScript
+                                );
+                        let fixing = tests_failed
+                            && match container.try_start_test_fix(&ctx).await {
+                                Ok(started) => started,
+                                Err(e) => {
+                                    tracing::error!("Failed to start test fix: {}", e);
+                                    false
+                                }
+                            };
+
+                        // A failed pipeline step stops the pipeline, unless the step may fail
+                        let failed_step = if matches!(
+                            ctx.execution_process.status,
+                            ExecutionProcessStatus::Failed
+                        ) {
+                            ctx.execution_process
+                                .executor_action()
+                                .ok()
+                                .and_then(|action| action.pipeline_step().cloned())
+                        } else {
+                            None
+                        };
+                        let continued = failed_step
+                            .as_ref()
+                            .is_some_and(|step| step.continue_on_failure)
+                            && !Self::should_finalize(&ctx)
+                            && match container.try_start_next_action(&ctx).await {
+                                Ok(()) => true,
+                                Err(e) => {
+                                    tracing::error!(
+                                        "Failed to continue pipeline after failed step: {}",
+                                        e
+                                    );
+                                    false
+                                }
+                            };
+
+                        if !fell_back
+                            && !fixing
+                            && !continued
+                            && !started_pipeline
+                            && !started_tests
+                            && !started_review
+                            && (Self::should_finalize(&ctx)
+                                || tests_failed
+                                || failed_step.is_some())
+                        {
                             if let Err(e) =
                                 Task::update_status(&db.pool, ctx.task.id, TaskStatus::InReview)
                                     .await
@@ -351,6 +935,17 @@ impl LocalContainerService {
                             NotificationService::notify_execution_halted(notify_cfg, &ctx).await;
                         }
 
+                        // Bring back a dev server that crashed or exited by itself
+                        if matches!(
+                            ctx.execution_process.run_reason,
+                            ExecutionProcessRunReason::DevServer
+                        ) && !matches!(
+                            ctx.execution_process.status,
+                            ExecutionProcessStatus::Killed
+                        ) {
+                            container.schedule_dev_server_restart(&ctx).await;
+                        }
+
                         // Fire event when CodingAgent execution has finished
                         if config.read().await.analytics_enabled == Some(true)
                             && matches!(
@@ -385,6 +980,12 @@ impl LocalContainerService {
 
                     // Cleanup child handle
                     child_store.write().await.remove(&exec_id);
+                    container.live_inputs.write().await.remove(&exec_id);
+
+                    // Let queued coding agents take the freed slot
+                    if let Err(e) = container.start_queued_executions().await {
+                        tracing::error!("Failed to start queued executions: {}", e);
+                    }
                     break;
                 }
 
@@ -626,6 +1227,19 @@ impl LocalContainerService {
 
         Ok(events)
     }
+
+    /// Remove the approvals MCP config written for an execution, if it has one
+    async fn remove_approvals_mcp_config(exec_id: Uuid) {
+        if let Err(e) = tokio::fs::remove_file(approvals_mcp_config_path(exec_id)).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(
+                "Failed to remove approvals MCP config of {}: {}",
+                exec_id,
+                e
+            );
+        }
+    }
 }
 
 #[async_trait]
@@ -642,6 +1256,14 @@ impl ContainerService for LocalContainerService {
         &self.git
     }
 
+    fn execution_queue_lock(&self) -> &Arc<Mutex<()>> {
+        &self.execution_queue_lock
+    }
+
+    async fn max_concurrent_agents(&self) -> Option<u32> {
+        self.config.read().await.max_concurrent_agents
+    }
+
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf {
         PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default())
     }
@@ -774,16 +1396,132 @@ impl ContainerService for LocalContainerService {
             )))?;
         let current_dir = PathBuf::from(container_ref);
 
-        // Create the child and stream, add to execution tracker
-        let mut child = executor_action.spawn(&current_dir).await?;
+        let project = match task_attempt.parent_task(&self.db.pool).await? {
+            Some(task) => Project::find_by_id(&self.db.pool, task.project_id).await?,
+            None => None,
+        };
+
+        // Coding agents are stopped once they exceed the stricter of their profile and
+        // project limits
+        let mut limits = ExecutionLimits::default();
+        if let Some(profile_variant_label) = executor_action.typ().profile_variant_label() {
+            limits = ProfileConfigs::get_cached().get_limits(profile_variant_label);
+            if let Some(project) = &project {
+                limits = limits.min(&project.execution_limits());
+            }
+        }
+        // Scripts are stopped once they run longer than their timeout
+        if let ExecutorActionType::ScriptRequest(request) = executor_action.typ() {
+            limits.max_duration_secs = request.timeout_secs;
+        }
+
+        // Create the child and stream, add to execution tracker. When sandboxed, the worktree
+        // stays writable, and so do its own git dir and the objects and refs of the common
+        // dir, so the agent can stage and commit. The common dir's hooks and config stay
+        // read-only, as writing them would run code outside the sandbox.
+        let mut readable_paths = vec![];
+        let mut writable_paths = vec![current_dir.clone()];
+        match (
+            self.git.get_common_dir(&current_dir),
+            self.git.get_worktree_git_dir(&current_dir),
+        ) {
+            (Ok(common_dir), Ok(git_dir)) => {
+                writable_paths.extend(
+                    ["objects", "refs", "logs", "packed-refs"].map(|path| common_dir.join(path)),
+                );
+                if git_dir != common_dir {
+                    writable_paths.push(git_dir);
+                }
+                readable_paths.push(common_dir);
+            }
+            (Err(e), _) | (_, Err(e)) => tracing::warn!(
+                "Failed to find the git dir of worktree {}: {}",
+                current_dir.display(),
+                e
+            ),
+        }
+        let env = project
+            .as_ref()
+            .map(Project::execution_env)
+            .unwrap_or_default()
+            .with_readable_paths(readable_paths)
+            .with_writable_paths(writable_paths);
+        if env.sandbox.is_some() && !Sandbox::is_supported() {
+            return Err(ContainerError::Other(anyhow!(
+                "The project runs in a sandbox, which needs Linux with bubblewrap (bwrap) installed"
+            )));
+        }
+        // Where cgroups can be created, the kernel holds the execution's processes together
+        // to its memory and process limits. Elsewhere the limit monitor enforces them.
+        let resources = env.resource_limits;
+        let cgroup = if resources.max_memory_mb.is_some() || resources.max_processes.is_some() {
+            ExecutionCgroup::create(
+                &format!("vibe-kanban-{}", execution_process.id),
+                resources.max_memory_bytes(),
+                resources.max_processes,
+            )
+        } else {
+            None
+        };
+        let env = env
+            .with_cgroup(cgroup.as_ref().map(|cgroup| cgroup.path().to_path_buf()))
+            .with_execution_id(execution_process.id);
+        if execution_process.run_reason == ExecutionProcessRunReason::CodeReview {
+            let head = self.git.get_head_commit(&current_dir)?;
+            self.review_heads
+                .write()
+                .await
+                .insert(execution_process.id, (current_dir.clone(), head));
+        }
+        let mut child = match executor_action.spawn(&current_dir, &env).await {
+            Ok(child) => child,
+            Err(e) => {
+                self.review_heads
+                    .write()
+                    .await
+                    .remove(&execution_process.id);
+                if let Some(cgroup) = cgroup {
+                    cgroup.remove();
+                }
+                Self::remove_approvals_mcp_config(execution_process.id).await;
+                return Err(e.into());
+            }
+        };
 
         self.track_child_msgs_in_store(execution_process.id, &mut child)
             .await;
 
+        let mut presents_plan = false;
+        if let Some(profile_variant_label) = executor_action.typ().profile_variant_label() {
+            let executor = CodingAgent::from_profile_variant_label(profile_variant_label)?;
+            presents_plan = executor.presents_plan();
+            if executor.accepts_live_messages()
+                && let Some(stdin) = child.inner().stdin.take()
+            {
+                self.track_live_input(execution_process.id, executor, stdin)
+                    .await;
+            }
+        }
+
         self.add_child_to_store(execution_process.id, child).await;
 
         // Spawn exit monitor
         let _hn = self.spawn_exit_monitor(&execution_process.id);
+        self.spawn_limit_monitor(execution_process.id, limits, resources, cgroup)
+            .await;
+        if presents_plan {
+            self.spawn_plan_monitor(execution_process.id).await;
+        }
+        if execution_process.run_reason == ExecutionProcessRunReason::CodeReview {
+            self.spawn_review_monitor(execution_process.id).await;
+        }
+        if execution_process.run_reason == ExecutionProcessRunReason::DevServer {
+            let health_check = executor_action
+                .dev_server()
+                .and_then(|dev_server| dev_server.health_check.clone());
+            self.spawn_dev_server_monitor(execution_process.id, health_check)
+                .await;
+        }
 
         Ok(())
     }
@@ -792,6 +1530,19 @@ impl ContainerService for LocalContainerService {
         &self,
         execution_process: &ExecutionProcess,
     ) -> Result<(), ContainerError> {
+        // A queued process has nothing to kill yet
+        if ExecutionProcess::cancel_queued(&self.db.pool, execution_process.id).await? {
+            if let Ok(ctx) =
+                ExecutionProcess::load_context(&self.db.pool, execution_process.id).await
+                && let Err(e) =
+                    Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await
+            {
+                tracing::error!("Failed to update task status to InReview: {e}");
+            }
+            // Moves the processes behind it up the queue
+            return self.start_queued_executions().await;
+        }
+
         let child = self
             .get_child_from_store(&execution_process.id)
             .await
@@ -819,6 +1570,12 @@ impl ContainerService for LocalContainerService {
             }
         }
         self.remove_child_from_store(&execution_process.id).await;
+        self.live_inputs.write().await.remove(&execution_process.id);
+        // A stopped dev server starts over when it is started again
+        self.dev_server_restarts
+            .write()
+            .await
+            .remove(&execution_process.id);
 
         // Mark the process finished in the MsgStore
         if let Some(msg) = self.msg_stores.write().await.remove(&execution_process.id) {
@@ -845,6 +1602,41 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    async fn process_stats(&self, execution_process: &ExecutionProcess) -> Option<ProcessStats> {
+        let child = self.get_child_from_store(&execution_process.id).await?;
+        // The child leads its own process group, which its descendants stay in
+        let pgid = child.read().await.id()?;
+        process_group_stats(pgid).await
+    }
+
+    async fn send_message(
+        &self,
+        execution_process: &ExecutionProcess,
+        message: &str,
+    ) -> Result<(), ContainerError> {
+        let executor_action = execution_process.executor_action()?;
+        let profile_variant_label = executor_action.typ().profile_variant_label().ok_or(
+            ContainerError::NotAcceptingMessages("Only coding agent processes accept messages"),
+        )?;
+        let executor = CodingAgent::from_profile_variant_label(profile_variant_label)?;
+
+        let map = self.live_inputs.read().await;
+        let live_input =
+            map.get(&execution_process.id)
+                .ok_or(ContainerError::NotAcceptingMessages(
+                    "Execution process is not accepting messages",
+                ))?;
+        let mut input = live_input.lock().await;
+        input
+            .stdin
+            .write_all(executor.encode_live_message(message).as_bytes())
+            .await?;
+        input.stdin.flush().await?;
+        input.open_turns += 1;
+
+        Ok(())
+    }
+
     async fn get_diff(
         &self,
         task_attempt: &TaskAttempt,
@@ -881,7 +1673,9 @@ impl ContainerService for LocalContainerService {
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
         if !matches!(
             ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::CleanupScript,
+            ExecutionProcessRunReason::CodingAgent
+                | ExecutionProcessRunReason::CleanupScript
+                | ExecutionProcessRunReason::PipelineStep,
         ) {
             return Ok(());
         }
@@ -926,6 +1720,14 @@ impl ContainerService for LocalContainerService {
                     ctx.task_attempt.id
                 )
             }
+            ExecutionProcessRunReason::PipelineStep => {
+                let step = ctx.execution_process.executor_action()?.pipeline_step();
+                format!(
+                    "Pipeline step '{}' changes for task attempt {}",
+                    step.map(|step| step.name.as_str()).unwrap_or_default(),
+                    ctx.task_attempt.id
+                )
+            }
             _ => Err(ContainerError::Other(anyhow::anyhow!(
                 "Invalid run reason for commit"
             )))?,
@@ -81,6 +81,7 @@ impl Deployment for LocalDeployment {
             analytics_ctx,
         );
         container.spawn_worktree_cleanup().await;
+        container.spawn_dependency_monitor();
 
         let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
 
@@ -0,0 +1,88 @@
+//! Deterministic stand-in for a coding agent CLI, used by the integration tests to run the
+//! full container flow without an LLM. It is only built with the `fake-agent` feature, which
+//! the server's dev-dependencies turn on for tests, so it is never part of a release.
+//!
+//! Usage: `fake_agent <fixture.jsonl> [agent args...]`. Agent args and stdin are ignored.
+//!
+//! Every fixture line is a JSON object. Lines with a `"fake"` key are directives, all other
+//! lines are replayed on stdout as-is, e.g. Claude Code `stream-json` output:
+//! - `{"fake": "write_file", "path": "src/lib.rs", "content": "..."}` writes a file relative
+//!   to the working directory, creating parent directories
+//! - `{"fake": "sleep", "ms": 100}` pauses before the next line
+//! - `{"fake": "exit", "code": 1}` exits immediately with the given code
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+    thread,
+    time::Duration,
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+#[serde(tag = "fake", rename_all = "snake_case")]
+enum Directive {
+    WriteFile { path: String, content: String },
+    Sleep { ms: u64 },
+    Exit { code: u8 },
+}
+
+fn main() -> ExitCode {
+    let Some(fixture_path) = std::env::args().nth(1) else {
+        eprintln!("Usage: fake_agent <fixture.jsonl> [agent args...]");
+        return ExitCode::from(2);
+    };
+    let fixture = match fs::read_to_string(&fixture_path) {
+        Ok(fixture) => fixture,
+        Err(e) => {
+            eprintln!("Failed to read fixture {fixture_path}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut stdout = io::stdout().lock();
+    for (index, line) in fixture.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("Invalid JSON on fixture line {}: {e}", index + 1);
+                return ExitCode::from(2);
+            }
+        };
+
+        if value.get("fake").is_none() {
+            let _ = writeln!(stdout, "{line}");
+            let _ = stdout.flush();
+            continue;
+        }
+
+        match serde_json::from_value::<Directive>(value) {
+            Ok(Directive::WriteFile { path, content }) => {
+                let path = Path::new(&path);
+                if let Some(parent) = path.parent() {
+                    let _ = fs::create_dir_all(parent);
+                }
+                if let Err(e) = fs::write(path, content) {
+                    eprintln!("Failed to write {}: {e}", path.display());
+                    return ExitCode::FAILURE;
+                }
+            }
+            Ok(Directive::Sleep { ms }) => thread::sleep(Duration::from_millis(ms)),
+            Ok(Directive::Exit { code }) => return ExitCode::from(code),
+            Err(e) => {
+                eprintln!("Invalid directive on fixture line {}: {e}", index + 1);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -23,12 +23,19 @@ fn generate_types_content() -> String {
         executors::actions::script::ScriptContext::decl(),
         executors::actions::script::ScriptRequest::decl(),
         executors::actions::script::ScriptRequestLanguage::decl(),
+        executors::actions::pipeline::PipelineStep::decl(),
+        executors::actions::pipeline::PipelineStepAction::decl(),
+        executors::actions::pipeline::PipelineStepInfo::decl(),
+        executors::actions::dev_server::DevServer::decl(),
+        executors::actions::dev_server::DevServerHealthCheck::decl(),
         db::models::task_template::TaskTemplate::decl(),
         db::models::task_template::CreateTaskTemplate::decl(),
         db::models::task_template::UpdateTaskTemplate::decl(),
         db::models::task::TaskStatus::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
+        db::models::task_dependency::TaskDependency::decl(),
+        db::models::task_dependency::CreateTaskDependency::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
         utils::response::ApiResponse::<()>::decl(),
@@ -38,6 +45,16 @@ fn generate_types_content() -> String {
         server::routes::config::UpdateMcpServersBody::decl(),
         server::routes::config::GetMcpServerResponse::decl(),
         server::routes::task_attempts::CreateFollowUpAttempt::decl(),
+        server::routes::execution_processes::SendMessageRequest::decl(),
+        server::routes::approvals::RespondToApprovalRequest::decl(),
+        db::models::tool_approval::ToolApproval::decl(),
+        db::models::tool_approval::ToolApprovalStatus::decl(),
+        server::routes::plan_reviews::UpdatePlanReviewRequest::decl(),
+        server::routes::plan_reviews::RespondToPlanReviewRequest::decl(),
+        db::models::plan_review::PlanReview::decl(),
+        db::models::plan_review::PlanReviewStatus::decl(),
+        db::models::review_comment::ReviewComment::decl(),
+        db::models::review_comment::ReviewCommentSeverity::decl(),
         server::routes::task_attempts::CreateGitHubPrRequest::decl(),
         services::services::github_service::GitHubServiceError::decl(),
         services::services::config::Config::decl(),
@@ -52,6 +69,11 @@ fn generate_types_content() -> String {
         server::routes::auth::CheckTokenResponse::decl(),
         services::services::git::GitBranch::decl(),
         services::services::git::BranchStatus::decl(),
+        services::services::git::DiffStats::decl(),
+        utils::process_stats::ProcessStats::decl(),
+        services::services::transcript::TranscriptFormat::decl(),
+        services::services::transcript::AttemptTranscript::decl(),
+        services::services::transcript::AgentRunTranscript::decl(),
         utils::diff::Diff::decl(),
         utils::diff::FileDiffDetails::decl(),
         services::services::github_service::RepositoryInfo::decl(),
@@ -59,6 +81,7 @@ fn generate_types_content() -> String {
         executors::profile::ProfileVariantLabel::decl(),
         executors::profile::ProfileConfig::decl(),
         executors::profile::VariantAgentConfig::decl(),
+        executors::profile::ExecutionLimits::decl(),
         executors::profile::ProfileConfigs::decl(),
         executors::executors::claude::ClaudeCode::decl(),
         executors::executors::gemini::Gemini::decl(),
@@ -66,14 +89,28 @@ fn generate_types_content() -> String {
         executors::executors::codex::Codex::decl(),
         executors::executors::cursor::Cursor::decl(),
         executors::executors::opencode::Opencode::decl(),
+        executors::executors::aider::Aider::decl(),
+        executors::executors::custom::Custom::decl(),
+        executors::executors::custom::CustomLogRule::decl(),
+        executors::executors::custom::CustomEntryType::decl(),
+        executors::executors::custom::CustomAction::decl(),
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
+        server::routes::task_attempts::CreateTaskAttemptsBody::decl(),
+        server::routes::tasks::AttemptComparison::decl(),
+        server::routes::tasks::PickTaskAttemptRequest::decl(),
+        server::routes::tasks::UpdateAutoStartRequest::decl(),
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
         db::models::task_attempt::TaskAttempt::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
+        db::models::execution_process::ExecutionProcessStopReason::decl(),
+        db::models::execution_process::DevServerHealth::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
+        db::models::execution_process_token_usage::ExecutionProcessTokenUsage::decl(),
+        db::models::execution_process_token_usage::TokenUsageTotals::decl(),
+        db::models::execution_process_token_usage::TokenUsageSummary::decl(),
         services::services::events::EventPatch::decl(),
         services::services::events::EventPatchInner::decl(),
         services::services::events::RecordTypes::decl(),
@@ -83,6 +120,7 @@ fn generate_types_content() -> String {
         executors::logs::FileChange::decl(),
         executors::logs::ActionType::decl(),
         executors::logs::TodoItem::decl(),
+        executors::logs::ToolResult::decl(),
         executors::logs::utils::patch::PatchType::decl(),
         serde_json::Value::decl(),
     ];
@@ -114,7 +152,8 @@ fn main() {
     println!("Generating TypeScript types…");
 
     // 2. Let ts-rs write its per-type files here (handy for debugging)
-    env::set_var("TS_RS_EXPORT_DIR", shared_path.to_str().unwrap());
+    // SAFETY: single-threaded at this point
+    unsafe { env::set_var("TS_RS_EXPORT_DIR", shared_path.to_str().unwrap()) };
 
     let generated = generate_types_content();
     let types_path = shared_path.join("types.ts");
@@ -3,7 +3,9 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use db::models::{project::ProjectError, task_attempt::TaskAttemptError};
+use db::models::{
+    project::ProjectError, task_attempt::TaskAttemptError, tool_approval::ToolApprovalError,
+};
 use deployment::DeploymentError;
 use executors::executors::ExecutorError;
 use git2::Error as Git2Error;
@@ -22,6 +24,8 @@ pub enum ApiError {
     #[error(transparent)]
     TaskAttempt(#[from] TaskAttemptError),
     #[error(transparent)]
+    ToolApproval(#[from] ToolApprovalError),
+    #[error(transparent)]
     GitService(#[from] GitServiceError),
     #[error(transparent)]
     GitHubService(#[from] GitHubServiceError),
@@ -51,11 +55,24 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status_code, error_type) = match &self {
             ApiError::Project(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ProjectError"),
+            ApiError::TaskAttempt(TaskAttemptError::ValidationError(_)) => {
+                (StatusCode::BAD_REQUEST, "TaskAttemptError")
+            }
             ApiError::TaskAttempt(_) => (StatusCode::INTERNAL_SERVER_ERROR, "TaskAttemptError"),
+            ApiError::ToolApproval(ToolApprovalError::NotFound) => {
+                (StatusCode::NOT_FOUND, "ToolApprovalError")
+            }
+            ApiError::ToolApproval(ToolApprovalError::AlreadyAnswered) => {
+                (StatusCode::CONFLICT, "ToolApprovalError")
+            }
+            ApiError::ToolApproval(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ToolApprovalError"),
             ApiError::GitService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
             ApiError::Auth(_) => (StatusCode::INTERNAL_SERVER_ERROR, "AuthError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
+            ApiError::Container(ContainerError::NotAcceptingMessages(_)) => {
+                (StatusCode::CONFLICT, "ContainerError")
+            }
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
@@ -1,6 +1,7 @@
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
 use server::{routes, DeploymentImpl};
+use services::services::container::ContainerService;
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
 use thiserror::Error;
@@ -42,6 +43,12 @@ async fn main() -> Result<(), VibeKanbanError> {
     let deployment = DeploymentImpl::new().await?;
     deployment.update_sentry_scope().await?;
     deployment.cleanup_orphan_executions().await?;
+    // Coding agents still queued from the last run wait for slots again
+    deployment
+        .container()
+        .start_queued_executions()
+        .await
+        .map_err(DeploymentError::from)?;
     deployment.spawn_pr_monitor_service().await;
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
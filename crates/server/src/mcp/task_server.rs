@@ -1,8 +1,15 @@
-use std::{future::Future, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
 
 use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
     project::Project,
     task::{CreateTask, Task, TaskStatus},
+    tool_approval::{CreateToolApproval, ToolApproval, ToolApprovalStatus},
 };
 use rmcp::{
     handler::server::tool::{Parameters, ToolRouter},
@@ -14,6 +21,7 @@ use rmcp::{
 use serde::{Deserialize, Serialize};
 use serde_json;
 use sqlx::SqlitePool;
+use utils::path::normalize_macos_private_alias;
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -45,8 +53,8 @@ pub struct ProjectSummary {
     pub setup_script: Option<String>,
     #[schemars(description = "Optional cleanup script for the project")]
     pub cleanup_script: Option<String>,
-    #[schemars(description = "Optional development script for the project")]
-    pub dev_script: Option<String>,
+    #[schemars(description = "The script each of the project's dev servers runs, by name")]
+    pub dev_servers: BTreeMap<String, String>,
     #[schemars(description = "When the project was created")]
     pub created_at: String,
     #[schemars(description = "When the project was last updated")]
@@ -167,6 +175,68 @@ pub struct DeleteTaskResponse {
     pub deleted_task_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ApproveToolUseRequest {
+    #[schemars(description = "The name of the tool the agent wants to use")]
+    pub tool_name: String,
+    #[schemars(description = "The input the tool will be called with")]
+    pub input: serde_json::Value,
+}
+
+/// How often a pending approval is checked for a response
+const APPROVAL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Shell commands and file writes outside the worktree need a human's approval
+fn requires_approval(tool_name: &str, input: &serde_json::Value, worktree: &Path) -> bool {
+    match tool_name {
+        "Bash" => true,
+        "Write" | "Edit" | "MultiEdit" | "NotebookEdit" => {
+            match input
+                .get("file_path")
+                .or_else(|| input.get("notebook_path"))
+                .and_then(|p| p.as_str())
+            {
+                Some(path) => !is_within(worktree, Path::new(path)),
+                None => true,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Lexically resolve `path` against `dir` and check it stays inside `dir`
+fn is_within(dir: &Path, path: &Path) -> bool {
+    let normalize = |path: &Path| {
+        let mut normalized = PathBuf::new();
+        for component in normalize_macos_private_alias(path).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                other => normalized.push(other),
+            }
+        }
+        normalized
+    };
+    let dir = normalize(dir);
+    normalize(&dir.join(path)).starts_with(&dir)
+}
+
+fn permission_allow(input: serde_json::Value) -> Result<CallToolResult, ErrorData> {
+    let response = serde_json::json!({ "behavior": "allow", "updatedInput": input });
+    Ok(CallToolResult::success(vec![Content::text(
+        response.to_string(),
+    )]))
+}
+
+fn permission_deny(message: &str) -> Result<CallToolResult, ErrorData> {
+    let response = serde_json::json!({ "behavior": "deny", "message": message });
+    Ok(CallToolResult::success(vec![Content::text(
+        response.to_string(),
+    )]))
+}
+
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct SimpleTaskResponse {
     pub success: bool,
@@ -204,6 +274,58 @@ impl TaskServer {
             tool_router: Self::tool_router(),
         }
     }
+
+    /// The running coding agent process whose worktree is the current directory. Agents start
+    /// their MCP servers inside the worktree they work in.
+    async fn find_calling_execution_process(
+        &self,
+    ) -> Result<Option<(ExecutionProcess, PathBuf)>, sqlx::Error> {
+        let Ok(cwd) = std::env::current_dir() else {
+            return Ok(None);
+        };
+        for worktree in [cwd.clone(), normalize_macos_private_alias(&cwd)] {
+            if let Some(execution_process) =
+                ExecutionProcess::find_running_coding_agent_by_container_ref(
+                    &self.pool,
+                    &worktree.to_string_lossy(),
+                )
+                .await?
+            {
+                return Ok(Some((execution_process, worktree)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Block until the approval is answered on the board, or its execution process stops
+    async fn wait_for_approval(&self, approval_id: Uuid) -> Result<ToolApproval, sqlx::Error> {
+        loop {
+            tokio::time::sleep(APPROVAL_POLL_INTERVAL).await;
+
+            let approval = ToolApproval::find_by_id(&self.pool, approval_id)
+                .await?
+                .ok_or(sqlx::Error::RowNotFound)?;
+            if approval.status != ToolApprovalStatus::Pending {
+                return Ok(approval);
+            }
+
+            let still_running =
+                ExecutionProcess::find_by_id(&self.pool, approval.execution_process_id)
+                    .await?
+                    .is_some_and(|ep| ep.status == ExecutionProcessStatus::Running);
+            if !still_running
+                && let Some(approval) = ToolApproval::respond(
+                    &self.pool,
+                    approval_id,
+                    ToolApprovalStatus::Denied,
+                    Some("Execution process is no longer running".to_string()),
+                )
+                .await?
+            {
+                return Ok(approval);
+            }
+        }
+    }
 }
 
 #[tool_router]
@@ -269,6 +391,7 @@ impl TaskServer {
             title: title.clone(),
             description: description.clone(),
             parent_task_attempt: None,
+            auto_start_profile: None,
         };
 
         match Task::create(&self.pool, &create_task_data, task_id).await {
@@ -312,7 +435,12 @@ impl TaskServer {
                         git_repo_path: project.git_repo_path,
                         setup_script: project.setup_script,
                         cleanup_script: project.cleanup_script,
-                        dev_script: project.dev_script,
+                        dev_servers: project
+                            .dev_servers
+                            .0
+                            .into_iter()
+                            .map(|dev_server| (dev_server.name, dev_server.script))
+                            .collect(),
                         created_at: project.created_at.to_rfc3339(),
                         updated_at: project.updated_at.to_rfc3339(),
                     })
@@ -798,6 +926,49 @@ impl TaskServer {
             }
         }
     }
+
+    #[tool(
+        description = "Permission prompt used by coding agents started from vibe-kanban. Shell commands and file writes outside the worktree wait until a human approves or denies them on the board. Do not call this tool directly."
+    )]
+    async fn approve_tool_use(
+        &self,
+        Parameters(ApproveToolUseRequest { tool_name, input }): Parameters<ApproveToolUseRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let (execution_process, worktree) = match self.find_calling_execution_process().await {
+            Ok(Some(found)) => found,
+            Ok(None) => {
+                return permission_deny(
+                    "No running vibe-kanban coding agent was found for this directory",
+                )
+            }
+            Err(e) => return permission_deny(&format!("Failed to look up execution process: {e}")),
+        };
+
+        if !requires_approval(&tool_name, &input, &worktree) {
+            return permission_allow(input);
+        }
+
+        let create = CreateToolApproval {
+            execution_process_id: execution_process.id,
+            tool_name,
+            tool_input: input.clone(),
+        };
+        let approval = match ToolApproval::create(&self.pool, &create, Uuid::new_v4()).await {
+            Ok(approval) => approval,
+            Err(e) => return permission_deny(&format!("Failed to request approval: {e}")),
+        };
+
+        match self.wait_for_approval(approval.id).await {
+            Ok(ToolApproval {
+                status: ToolApprovalStatus::Approved,
+                ..
+            }) => permission_allow(input),
+            Ok(ToolApproval { reason, .. }) => {
+                permission_deny(reason.as_deref().unwrap_or("The user denied this tool use"))
+            }
+            Err(e) => permission_deny(&format!("Failed to wait for approval: {e}")),
+        }
+    }
 }
 
 #[tool_handler]
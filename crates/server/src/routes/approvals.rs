@@ -0,0 +1,48 @@
+use axum::{
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::post,
+    Json, Router,
+};
+use db::models::tool_approval::{ToolApproval, ToolApprovalError, ToolApprovalStatus};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{error::ApiError, DeploymentImpl};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RespondToApprovalRequest {
+    pub approved: bool,
+    /// Passed back to the agent when the tool use is denied
+    pub reason: Option<String>,
+}
+
+/// Approve or deny a pending tool use. The agent waiting on it resumes on its next poll.
+pub async fn respond_to_approval(
+    State(deployment): State<DeploymentImpl>,
+    Path(approval_id): Path<Uuid>,
+    Json(payload): Json<RespondToApprovalRequest>,
+) -> Result<ResponseJson<ApiResponse<ToolApproval>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ToolApproval::find_by_id(pool, approval_id)
+        .await?
+        .ok_or(ToolApprovalError::NotFound)?;
+
+    let status = if payload.approved {
+        ToolApprovalStatus::Approved
+    } else {
+        ToolApprovalStatus::Denied
+    };
+    let approval = ToolApproval::respond(pool, approval_id, status, payload.reason)
+        .await?
+        .ok_or(ToolApprovalError::AlreadyAnswered)?;
+
+    Ok(ResponseJson(ApiResponse::success(approval)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new().route("/approvals/{id}/respond", post(respond_to_approval))
+}
@@ -15,7 +15,10 @@ use executors::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use services::services::config::{save_config_to_file, Config, ConfigError, SoundFile};
+use services::services::{
+    config::{save_config_to_file, Config, ConfigError, SoundFile},
+    container::ContainerService,
+};
 use tokio::fs;
 use ts_rs::TS;
 use utils::{assets::config_path, response::ApiResponse};
@@ -93,6 +96,11 @@ async fn update_config(
             *config = new_config.clone();
             drop(config);
 
+            // A raised agent limit may let queued executions start
+            if let Err(e) = deployment.container().start_queued_executions().await {
+                tracing::error!("Failed to start queued executions: {}", e);
+            }
+
             ResponseJson(ApiResponse::success(new_config))
         }
         Err(e) => ResponseJson(ApiResponse::error(&format!("Failed to save config: {}", e))),
@@ -304,6 +312,16 @@ fn set_mcp_servers_in_config_path(
 pub struct ProfilesContent {
     pub content: String,
     pub path: String,
+    /// Problems that keep profiles.json from being used, in which case the defaults apply
+    pub errors: Vec<String>,
+}
+
+/// Resolve user profiles against the defaults, returning why they can't be used
+fn validate_profiles(content: &str) -> Vec<String> {
+    match ProfileConfigs::from_defaults().extend_from_json(content) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.to_string()).collect(),
+    }
 }
 
 async fn get_profiles(
@@ -311,38 +329,26 @@ async fn get_profiles(
 ) -> ResponseJson<ApiResponse<ProfilesContent>> {
     let profiles_path = utils::assets::profiles_path();
 
-    let mut profiles = ProfileConfigs::from_defaults();
-    if let Ok(user_content) = std::fs::read_to_string(&profiles_path) {
-        match serde_json::from_str::<ProfileConfigs>(&user_content) {
-            Ok(user_profiles) => {
-                // Override defaults with user profiles that have the same label
-                for user_profile in user_profiles.profiles {
-                    if let Some(default_profile) = profiles
-                        .profiles
-                        .iter_mut()
-                        .find(|p| p.default.label == user_profile.default.label)
-                    {
-                        *default_profile = user_profile;
-                    } else {
-                        profiles.profiles.push(user_profile);
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::error!("Failed to parse profiles.json: {}", e);
-            }
+    // profiles.json is shown as written, so inherited profiles keep their `extends`
+    let (content, errors) = match std::fs::read_to_string(&profiles_path) {
+        Ok(content) => {
+            let errors = validate_profiles(&content);
+            (content, errors)
         }
-    }
-
-    let content = serde_json::to_string_pretty(&profiles).unwrap_or_else(|e| {
-        tracing::error!("Failed to serialize profiles to JSON: {}", e);
-        serde_json::to_string_pretty(&ProfileConfigs::from_defaults())
-            .unwrap_or_else(|_| "{}".to_string())
-    });
+        Err(_) => {
+            let content = serde_json::to_string_pretty(&ProfileConfigs::from_defaults())
+                .unwrap_or_else(|e| {
+                    tracing::error!("Failed to serialize profiles to JSON: {}", e);
+                    "{}".to_string()
+                });
+            (content, Vec::new())
+        }
+    };
 
     ResponseJson(ApiResponse::success(ProfilesContent {
         content,
         path: profiles_path.display().to_string(),
+        errors,
     }))
 }
 
@@ -350,21 +356,18 @@ async fn update_profiles(
     State(_deployment): State<DeploymentImpl>,
     body: String,
 ) -> ResponseJson<ApiResponse<String>> {
-    let profiles: ProfileConfigs = match serde_json::from_str(&body) {
-        Ok(p) => p,
-        Err(e) => {
-            return ResponseJson(ApiResponse::error(&format!(
-                "Invalid profiles format: {}",
-                e
-            )))
-        }
-    };
+    let errors = validate_profiles(&body);
+    if !errors.is_empty() {
+        return ResponseJson(ApiResponse::error(&format!(
+            "Invalid profiles: {}",
+            errors.join("; ")
+        )));
+    }
 
     let profiles_path = utils::assets::profiles_path();
 
-    // Simply save all profiles as provided by the user
-    let formatted = serde_json::to_string_pretty(&profiles).unwrap();
-    match fs::write(&profiles_path, formatted).await {
+    // Save the profiles as written, so inherited profiles keep their `extends`
+    match fs::write(&profiles_path, body).await {
         Ok(_) => {
             tracing::info!("All profiles saved to {:?}", profiles_path);
             // Reload the cached profiles
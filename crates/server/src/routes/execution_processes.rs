@@ -6,14 +6,17 @@ use axum::{
         Json as ResponseJson, Sse,
     },
     routing::{get, post},
-    BoxError, Extension, Router,
+    BoxError, Extension, Json, Router,
+};
+use db::models::{
+    execution_process::ExecutionProcess, execution_process_token_usage::ExecutionProcessTokenUsage,
 };
-use db::models::execution_process::ExecutionProcess;
 use deployment::Deployment;
 use futures_util::TryStreamExt;
 use serde::Deserialize;
-use services::services::container::ContainerService;
-use utils::response::ApiResponse;
+use services::services::container::{ContainerService, LogRange};
+use ts_rs::TS;
+use utils::{process_stats::ProcessStats, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{error::ApiError, middleware::load_execution_process_middleware, DeploymentImpl};
@@ -41,15 +44,39 @@ pub async fn get_execution_process_by_id(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+pub async fn get_execution_process_token_usage(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ExecutionProcessTokenUsage>>>, ApiError> {
+    let usage = ExecutionProcessTokenUsage::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(usage)))
+}
+
+pub async fn get_execution_process_stats(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProcessStats>>>, ApiError> {
+    let stats = deployment
+        .container()
+        .process_stats(&execution_process)
+        .await;
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
 pub async fn stream_raw_logs(
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    Query(range): Query<LogRange>,
 ) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, BoxError>>>, axum::http::StatusCode>
 {
     // Ask the container service for a combined "history + live" stream
     let stream = deployment
         .container()
-        .stream_raw_logs(&exec_id)
+        .stream_raw_logs(&exec_id, range)
         .await
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
@@ -59,12 +86,13 @@ pub async fn stream_raw_logs(
 pub async fn stream_normalized_logs(
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    Query(range): Query<LogRange>,
 ) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, BoxError>>>, axum::http::StatusCode>
 {
     // Ask the container service for a combined "history + live" stream
     let stream = deployment
         .container()
-        .stream_normalized_logs(&exec_id)
+        .stream_normalized_logs(&exec_id, range)
         .await
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
@@ -83,12 +111,33 @@ pub async fn stop_execution_process(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct SendMessageRequest {
+    pub message: String,
+}
+
+pub async fn send_message(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SendMessageRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment
+        .container()
+        .send_message(&execution_process, &payload.message)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
+        .route("/message", post(send_message))
         .route("/raw-logs", get(stream_raw_logs))
         .route("/normalized-logs", get(stream_normalized_logs))
+        .route("/token-usage", get(get_execution_process_token_usage))
+        .route("/stats", get(get_execution_process_stats))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_execution_process_middleware,
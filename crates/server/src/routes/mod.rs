@@ -5,6 +5,7 @@ use axum::{
 
 use crate::DeploymentImpl;
 
+pub mod approvals;
 pub mod auth;
 pub mod config;
 pub mod containers;
@@ -14,6 +15,8 @@ pub mod events;
 pub mod execution_processes;
 pub mod frontend;
 pub mod health;
+pub mod plan_reviews;
+pub mod preview;
 pub mod projects;
 pub mod task_attempts;
 pub mod task_templates;
@@ -29,6 +32,9 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(tasks::router(&deployment))
         .merge(task_attempts::router(&deployment))
         .merge(execution_processes::router(&deployment))
+        .merge(approvals::router(&deployment))
+        .merge(plan_reviews::router(&deployment))
+        .merge(preview::router(&deployment))
         .merge(task_templates::router(&deployment))
         .merge(auth::router(&deployment))
         .merge(filesystem::router())
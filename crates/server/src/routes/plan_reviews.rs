@@ -0,0 +1,111 @@
+use axum::{
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{post, put},
+    Json, Router,
+};
+use db::models::{
+    execution_process::ExecutionProcess,
+    plan_review::{PlanReview, PlanReviewStatus},
+    task_attempt::{TaskAttempt, TaskAttemptError},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{error::ApiError, routes::task_attempts::start_follow_up, DeploymentImpl};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdatePlanReviewRequest {
+    pub plan: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RespondToPlanReviewRequest {
+    pub approved: bool,
+    /// Replaces the presented plan before it is approved
+    pub plan: Option<String>,
+}
+
+fn already_answered() -> ApiError {
+    ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+        "Plan has already been approved or rejected".to_string(),
+    ))
+}
+
+/// Edit a pending plan before approving it
+pub async fn update_plan_review(
+    State(deployment): State<DeploymentImpl>,
+    Path(plan_review_id): Path<Uuid>,
+    Json(payload): Json<UpdatePlanReviewRequest>,
+) -> Result<ResponseJson<ApiResponse<PlanReview>>, ApiError> {
+    let pool = &deployment.db().pool;
+    PlanReview::find_by_id(pool, plan_review_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let plan_review = PlanReview::update_plan(pool, plan_review_id, &payload.plan)
+        .await?
+        .ok_or_else(already_answered)?;
+
+    Ok(ResponseJson(ApiResponse::success(plan_review)))
+}
+
+/// Approve or reject a pending plan. Approving it starts a follow-up with the profile's
+/// default variant, using the approved plan as the prompt.
+pub async fn respond_to_plan_review(
+    State(deployment): State<DeploymentImpl>,
+    Path(plan_review_id): Path<Uuid>,
+    Json(payload): Json<RespondToPlanReviewRequest>,
+) -> Result<ResponseJson<ApiResponse<PlanReview>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let plan_review = PlanReview::find_by_id(pool, plan_review_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    if plan_review.status != PlanReviewStatus::Pending {
+        return Err(already_answered());
+    }
+
+    if !payload.approved {
+        let plan_review = PlanReview::respond(pool, plan_review_id, PlanReviewStatus::Rejected)
+            .await?
+            .ok_or_else(already_answered)?;
+        return Ok(ResponseJson(ApiResponse::success(plan_review)));
+    }
+
+    let execution_process = ExecutionProcess::find_by_id(pool, plan_review.execution_process_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let task_attempt = TaskAttempt::find_by_id(pool, execution_process.task_attempt_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    if let Some(plan) = &payload.plan {
+        PlanReview::update_plan(pool, plan_review_id, plan)
+            .await?
+            .ok_or_else(already_answered)?;
+    }
+    // Approving claims the plan, so a second approval can't start another follow-up
+    let plan_review = PlanReview::respond(pool, plan_review_id, PlanReviewStatus::Approved)
+        .await?
+        .ok_or_else(already_answered)?;
+
+    if let Err(e) =
+        start_follow_up(&deployment, &task_attempt, plan_review.plan.clone(), None).await
+    {
+        // Leave the plan waiting for approval so it can be approved again
+        PlanReview::reopen(pool, plan_review_id).await?;
+        return Err(e);
+    }
+
+    Ok(ResponseJson(ApiResponse::success(plan_review)))
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/plan-reviews/{id}", put(update_plan_review))
+        .route("/plan-reviews/{id}/respond", post(respond_to_plan_review))
+}
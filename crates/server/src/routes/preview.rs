@@ -0,0 +1,171 @@
+use std::sync::OnceLock;
+
+use axum::{
+    body::Body,
+    extract::{Path, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::any,
+    Router,
+};
+use db::models::execution_process::ExecutionProcess;
+use deployment::Deployment;
+use hyper_util::rt::TokioIo;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{error::ApiError, DeploymentImpl};
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewPath {
+    pub attempt_id: Uuid,
+    pub dev_server: String,
+}
+
+/// Headers that only apply to a single connection and must not be forwarded
+const HOP_BY_HOP_HEADERS: [header::HeaderName; 8] = [
+    header::CONNECTION,
+    header::HOST,
+    header::PROXY_AUTHENTICATE,
+    header::PROXY_AUTHORIZATION,
+    header::TE,
+    header::TRAILER,
+    header::TRANSFER_ENCODING,
+    header::UPGRADE,
+];
+
+fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            // Redirects are passed back to the browser rather than followed here
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build preview proxy client")
+    })
+}
+
+fn forwarded_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+    for name in HOP_BY_HOP_HEADERS {
+        headers.remove(name);
+    }
+    headers
+}
+
+/// Proxy a request to one of a task attempt's dev servers, at the URL it reported on
+/// startup. The path after the dev server's name is forwarded along with the query, so pages
+/// load their assets through the preview as long as they link to them relatively. Bodies are
+/// streamed, and connection upgrades (websockets, like hot reloading) are passed through.
+pub async fn proxy_preview(
+    State(deployment): State<DeploymentImpl>,
+    Path(params): Path<PreviewPath>,
+    mut request: Request,
+) -> Result<Response, ApiError> {
+    let Some(dev_server_url) = ExecutionProcess::find_running_dev_server_url(
+        &deployment.db().pool,
+        params.attempt_id,
+        &params.dev_server,
+    )
+    .await?
+    else {
+        return Ok((
+            StatusCode::NOT_FOUND,
+            "This dev server isn't running or hasn't reported a URL",
+        )
+            .into_response());
+    };
+    // The rest of the path as the browser sent it, still percent-encoded
+    let path = request
+        .uri()
+        .path()
+        .splitn(5, '/')
+        .nth(4)
+        .unwrap_or_default();
+    let mut url = format!("{}/{}", dev_server_url.trim_end_matches('/'), path);
+    if let Some(query) = request.uri().query() {
+        url.push('?');
+        url.push_str(query);
+    }
+
+    let mut headers = forwarded_headers(request.headers());
+    let upgrade = request.headers().get(header::UPGRADE).cloned();
+    let client_upgrade = upgrade.as_ref().map(|upgrade| {
+        headers.insert(
+            header::CONNECTION,
+            header::HeaderValue::from_static("upgrade"),
+        );
+        headers.insert(header::UPGRADE, upgrade.clone());
+        hyper::upgrade::on(&mut request)
+    });
+    let mut upstream = client()
+        .request(request.method().clone(), &url)
+        .headers(headers);
+    // Requests without a body, like upgrades, are sent without one
+    if request.headers().contains_key(header::CONTENT_LENGTH)
+        || request.headers().contains_key(header::TRANSFER_ENCODING)
+    {
+        upstream = upstream.body(reqwest::Body::wrap_stream(
+            request.into_body().into_data_stream(),
+        ));
+    }
+    let upstream = match upstream.send().await {
+        Ok(upstream) => upstream,
+        Err(e) => {
+            tracing::warn!("Failed to proxy preview request to {}: {}", url, e);
+            return Ok((
+                StatusCode::BAD_GATEWAY,
+                format!("Failed to reach the dev server at {dev_server_url}"),
+            )
+                .into_response());
+        }
+    };
+
+    let status = upstream.status();
+    let mut headers = forwarded_headers(upstream.headers());
+    if status == StatusCode::SWITCHING_PROTOCOLS
+        && let Some(client_upgrade) = client_upgrade
+    {
+        for name in [header::CONNECTION, header::UPGRADE] {
+            if let Some(value) = upstream.headers().get(&name) {
+                headers.insert(name, value.clone());
+            }
+        }
+        tokio::spawn(async move {
+            let (client, mut upstream) = match (client_upgrade.await, upstream.upgrade().await) {
+                (Ok(client), Ok(upstream)) => (client, upstream),
+                (Err(e), _) => {
+                    tracing::warn!("Failed to upgrade preview connection: {}", e);
+                    return;
+                }
+                (_, Err(e)) => {
+                    tracing::warn!("Failed to upgrade dev server connection: {}", e);
+                    return;
+                }
+            };
+            let mut client = TokioIo::new(client);
+            if let Err(e) = tokio::io::copy_bidirectional(&mut client, &mut upstream).await {
+                tracing::debug!("Upgraded preview connection closed: {}", e);
+            }
+        });
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = status;
+        *response.headers_mut() = headers;
+        return Ok(response);
+    }
+
+    let mut response = Response::new(Body::from_stream(upstream.bytes_stream()));
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+    Ok(response)
+}
+
+pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/preview/{attempt_id}/{dev_server}", any(proxy_preview))
+        .route("/preview/{attempt_id}/{dev_server}/", any(proxy_preview))
+        .route(
+            "/preview/{attempt_id}/{dev_server}/{*path}",
+            any(proxy_preview),
+        )
+}
@@ -8,12 +8,19 @@ use axum::{
     routing::{get, post},
     Extension, Json, Router,
 };
-use db::models::project::{
-    CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject,
+use db::models::{
+    execution_process_token_usage::{ExecutionProcessTokenUsage, TokenUsageSummary},
+    project::{CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject},
 };
 use deployment::Deployment;
+use executors::{
+    actions::{
+        dev_server::validate_dev_servers, pipeline::validate_pipeline, script::is_valid_working_dir,
+    },
+    profile::{ProfileConfigs, ProfileVariantLabel},
+};
 use ignore::WalkBuilder;
-use services::services::git::GitBranch;
+use services::services::{container::ContainerService, git::GitBranch};
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
@@ -40,6 +47,15 @@ pub async fn get_project_branches(
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+pub async fn get_project_token_usage(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TokenUsageSummary>>, ApiError> {
+    let summary =
+        ExecutionProcessTokenUsage::summary_for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
 pub async fn create_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateProject>,
@@ -63,6 +79,30 @@ pub async fn create_project(
         }
     }
 
+    if let Some(pipeline) = &payload.pipeline
+        && let Err(message) = validate_pipeline(pipeline)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+    if let Some(dev_servers) = &payload.dev_servers
+        && let Err(message) = validate_dev_servers(dev_servers)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+    if let Some(dir) = &payload.script_working_dir
+        && !is_valid_working_dir(dir)
+    {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The script working directory must be a relative path inside the repository",
+        )));
+    }
+    if let Err(message) = validate_profile_label(payload.default_profile.as_ref()) {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+    if let Err(message) = validate_profile_label(payload.review_profile.as_ref()) {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
     // Validate and setup git repository
     let path = std::path::Path::new(&payload.git_repo_path);
 
@@ -130,7 +170,7 @@ pub async fn create_project(
                         "project_id": project.id.to_string(),
                         "use_existing_repo": payload.use_existing_repo,
                         "has_setup_script": payload.setup_script.is_some(),
-                        "has_dev_script": payload.dev_script.is_some(),
+                        "has_dev_script": payload.dev_servers.as_ref().is_some_and(|d| !d.is_empty()),
                     }),
                 )
                 .await;
@@ -141,6 +181,13 @@ pub async fn create_project(
     }
 }
 
+/// Check that a profile a project is set to use exists
+fn validate_profile_label(label: Option<&ProfileVariantLabel>) -> Result<(), String> {
+    label.map_or(Ok(()), |label| {
+        ProfileConfigs::get_cached().validate_label(label)
+    })
+}
+
 pub async fn update_project(
     Extension(existing_project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -172,35 +219,38 @@ pub async fn update_project(
         }
     }
 
-    // Destructure payload to handle field updates.
-    // This allows us to treat `None` from the payload as an explicit `null` to clear a field,
-    // as the frontend currently sends all fields on update.
-    let UpdateProject {
-        name,
-        git_repo_path,
-        setup_script,
-        dev_script,
-        cleanup_script,
-        copy_files,
-    } = payload;
-
-    let name = name.unwrap_or(existing_project.name);
-    let git_repo_path =
-        git_repo_path.unwrap_or(existing_project.git_repo_path.to_string_lossy().to_string());
-
-    match Project::update(
-        &deployment.db().pool,
-        existing_project.id,
-        name,
-        git_repo_path,
-        setup_script,
-        dev_script,
-        cleanup_script,
-        copy_files,
-    )
-    .await
+    if let Some(pipeline) = &payload.pipeline
+        && let Err(message) = validate_pipeline(pipeline)
+    {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+    if let Some(dev_servers) = &payload.dev_servers
+        && let Err(message) = validate_dev_servers(dev_servers)
     {
-        Ok(project) => Ok(ResponseJson(ApiResponse::success(project))),
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+    if let Some(dir) = &payload.script_working_dir
+        && !is_valid_working_dir(dir)
+    {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The script working directory must be a relative path inside the repository",
+        )));
+    }
+    if let Err(message) = validate_profile_label(payload.default_profile.as_ref()) {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+    if let Err(message) = validate_profile_label(payload.review_profile.as_ref()) {
+        return Ok(ResponseJson(ApiResponse::error(&message)));
+    }
+
+    match Project::update(&deployment.db().pool, &existing_project, &payload).await {
+        Ok(project) => {
+            // A raised agent limit may let queued executions start
+            if let Err(e) = deployment.container().start_queued_executions().await {
+                tracing::error!("Failed to start queued executions: {}", e);
+            }
+            Ok(ResponseJson(ApiResponse::success(project)))
+        }
         Err(e) => {
             tracing::error!("Failed to update project: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -382,6 +432,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/branches", get(get_project_branches))
         .route("/search", get(search_project_files))
+        .route("/token-usage", get(get_project_token_usage))
         .route("/open-editor", post(open_project_in_editor))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -1,34 +1,38 @@
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     middleware::from_fn_with_state,
     response::{
         sse::{Event, KeepAlive},
-        Json as ResponseJson, Sse,
+        IntoResponse, Json as ResponseJson, Response, Sse,
     },
     routing::{get, post},
     BoxError, Extension, Json, Router,
 };
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process_token_usage::{ExecutionProcessTokenUsage, TokenUsageSummary},
+    plan_review::PlanReview,
+    review_comment::ReviewComment,
     task::{Task, TaskStatus},
     task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
+    tool_approval::ToolApproval,
 };
 use deployment::Deployment;
 use executors::{
     actions::{
-        coding_agent_follow_up::CodingAgentFollowUpRequest,
-        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
-        ExecutorAction, ExecutorActionType,
+        coding_agent_follow_up::CodingAgentFollowUpRequest, script::ScriptContext, ExecutorAction,
+        ExecutorActionType,
     },
-    profile::{ProfileConfigs, ProfileVariantLabel},
+    profile::{ProfileConfig, ProfileConfigs, ProfileVariantLabel},
 };
 use futures_util::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService,
+    container::{ContainerError, ContainerService},
     git::BranchStatus,
     github_service::{CreatePrRequest, GitHubRepoInfo, GitHubService, GitHubServiceError},
+    transcript::TranscriptFormat,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -84,38 +88,163 @@ pub struct CreateTaskAttemptBody {
     pub base_branch: String,
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskAttemptsBody {
+    pub task_id: Uuid,
+    /// One attempt is started per label; repeat a label to run the same profile several times
+    pub profile_variant_labels: Vec<ProfileVariantLabel>,
+    pub base_branch: String,
+}
+
 #[axum::debug_handler]
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
-    let profile_variant_label = payload
-        .profile_variant_label
-        .unwrap_or(deployment.config().read().await.profile.clone());
+    let profile_variant_label = match payload.profile_variant_label {
+        Some(profile_variant_label) => profile_variant_label,
+        None => {
+            let pool = &deployment.db().pool;
+            let task = Task::find_by_id(pool, payload.task_id)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+            match task
+                .parent_project(pool)
+                .await?
+                .and_then(|p| p.default_profile)
+            {
+                Some(default_profile) => default_profile.0,
+                None => deployment.config().read().await.profile.clone(),
+            }
+        }
+    };
+
+    let task_attempt = start_task_attempt(
+        &deployment,
+        payload.task_id,
+        profile_variant_label,
+        payload.base_branch,
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
 
+/// Most attempts one batch can start, each of which gets its own worktree and agent
+const MAX_BATCH_ATTEMPTS: usize = 8;
+
+/// Start several attempts for the same task at once, so their results can be compared
+pub async fn create_task_attempts(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskAttemptsBody>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttempt>>>, ApiError> {
+    if payload.profile_variant_labels.is_empty() {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "At least one profile is required".to_string(),
+        )));
+    }
+    if payload.profile_variant_labels.len() > MAX_BATCH_ATTEMPTS {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            format!("At most {MAX_BATCH_ATTEMPTS} attempts can be started at once"),
+        )));
+    }
+    // Check every profile up front so a typo doesn't leave a partial batch behind
     let profiles = ProfileConfigs::get_cached();
-    let profile = profiles
-        .get_profile(&profile_variant_label.profile)
-        .ok_or_else(|| {
-            ApiError::TaskAttempt(TaskAttemptError::ValidationError(format!(
-                "Profile not found: {}",
-                profile_variant_label.profile
-            )))
-        })?;
+    for label in &payload.profile_variant_labels {
+        find_profile(&profiles, label)?;
+    }
+
+    // Create every attempt before starting any, so a failed insert leaves none behind
+    let pool = &deployment.db().pool;
+    let mut tx = pool.begin().await?;
+    let mut task_attempts = Vec::with_capacity(payload.profile_variant_labels.len());
+    for label in &payload.profile_variant_labels {
+        task_attempts.push(
+            TaskAttempt::create(
+                &mut *tx,
+                &CreateTaskAttempt {
+                    profile: find_profile(&profiles, label)?.default.label.clone(),
+                    base_branch: payload.base_branch.clone(),
+                    fallback_from_attempt_id: None,
+                },
+                payload.task_id,
+            )
+            .await?,
+        );
+    }
+    tx.commit().await?;
+
+    // If one fails to start, the whole batch is discarded with the worktrees already made
+    for (task_attempt, label) in task_attempts.iter().zip(payload.profile_variant_labels) {
+        if let Err(e) = start_created_attempt(&deployment, task_attempt, label).await {
+            for task_attempt in &task_attempts {
+                let discarded = match TaskAttempt::find_by_id(pool, task_attempt.id).await {
+                    Ok(Some(task_attempt)) => {
+                        discard_task_attempt(&deployment, &task_attempt).await
+                    }
+                    Ok(None) => Ok(()),
+                    Err(e) => Err(e.into()),
+                };
+                if let Err(e) = discarded {
+                    tracing::error!(
+                        "Failed to discard task attempt {} of a failed batch: {}",
+                        task_attempt.id,
+                        e
+                    );
+                }
+            }
+            return Err(e);
+        }
+    }
+
+    Ok(ResponseJson(ApiResponse::success(task_attempts)))
+}
+
+fn find_profile<'a>(
+    profiles: &'a ProfileConfigs,
+    label: &ProfileVariantLabel,
+) -> Result<&'a ProfileConfig, ApiError> {
+    profiles.get_profile(&label.profile).ok_or_else(|| {
+        ApiError::TaskAttempt(TaskAttemptError::ValidationError(format!(
+            "Profile not found: {}",
+            label.profile
+        )))
+    })
+}
+
+async fn start_task_attempt(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    profile_variant_label: ProfileVariantLabel,
+    base_branch: String,
+) -> Result<TaskAttempt, ApiError> {
+    let profiles = ProfileConfigs::get_cached();
+    let profile = find_profile(&profiles, &profile_variant_label)?;
 
     let task_attempt = TaskAttempt::create(
         &deployment.db().pool,
         &CreateTaskAttempt {
             profile: profile.default.label.clone(),
-            base_branch: payload.base_branch,
+            base_branch,
+            fallback_from_attempt_id: None,
         },
-        payload.task_id,
+        task_id,
     )
     .await?;
+    start_created_attempt(deployment, &task_attempt, profile_variant_label).await?;
+
+    Ok(task_attempt)
+}
 
+/// Start the first coding agent of an attempt that has just been created
+async fn start_created_attempt(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    profile_variant_label: ProfileVariantLabel,
+) -> Result<(), ApiError> {
     let execution_process = deployment
         .container()
-        .start_attempt(&task_attempt, profile_variant_label.clone())
+        .start_attempt(task_attempt, profile_variant_label.clone())
         .await?;
 
     deployment
@@ -124,7 +253,7 @@ pub async fn create_task_attempt(
             serde_json::json!({
                 "task_id": task_attempt.task_id.to_string(),
                 "variant": &profile_variant_label.variant,
-                "profile": profile.default.label,
+                "profile": &task_attempt.profile,
                 "attempt_id": task_attempt.id.to_string(),
             }),
         )
@@ -132,7 +261,47 @@ pub async fn create_task_attempt(
 
     tracing::info!("Started execution process {}", execution_process.id);
 
-    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+    Ok(())
+}
+
+/// Stop an attempt and remove its worktree, branch and history
+pub async fn discard_task_attempt(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+    deployment.container().delete(task_attempt).await?;
+
+    if let Some(branch) = &task_attempt.branch {
+        let task = task_attempt
+            .parent_task(pool)
+            .await?
+            .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+        if let Some(project) = task.parent_project(pool).await? {
+            deployment
+                .git()
+                .delete_branch(&project.git_repo_path, branch)
+                .unwrap_or_else(|e| {
+                    tracing::warn!(
+                        "Failed to delete branch {} of task attempt {}: {}",
+                        branch,
+                        task_attempt.id,
+                        e
+                    );
+                });
+        }
+    }
+
+    TaskAttempt::delete(pool, task_attempt.id).await?;
+    Ok(())
+}
+
+pub async fn delete_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    discard_task_attempt(&deployment, &task_attempt).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -148,6 +317,20 @@ pub async fn follow_up(
 ) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
     tracing::info!("{:?}", task_attempt);
 
+    let execution_process =
+        start_follow_up(&deployment, &task_attempt, payload.prompt, payload.variant).await?;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
+
+/// Continue the session of the attempt's latest coding agent run with a new prompt, using
+/// the given variant of the same profile
+pub async fn start_follow_up(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    prompt: String,
+    variant: Option<String>,
+) -> Result<ExecutionProcess, ApiError> {
     // Get session_id with simple query
     let session_id = ExecutionProcess::find_latest_session_id_by_task_attempt(
         &deployment.db().pool,
@@ -186,7 +369,7 @@ pub async fn follow_up(
 
     let profile_variant_label = ProfileVariantLabel {
         profile: initial_profile_variant_label.profile,
-        variant: payload.variant,
+        variant,
     };
 
     // Get parent task
@@ -201,20 +384,18 @@ pub async fn follow_up(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
-    let cleanup_action = project.cleanup_script.map(|script| {
+    let cleanup_action = project.cleanup_script.clone().map(|script| {
         Box::new(ExecutorAction::new(
-            ExecutorActionType::ScriptRequest(ScriptRequest {
-                script,
-                language: ScriptRequestLanguage::Bash,
-                context: ScriptContext::CleanupScript,
-            }),
+            ExecutorActionType::ScriptRequest(
+                project.script_request(script, ScriptContext::CleanupScript),
+            ),
             None,
         ))
     });
 
     let follow_up_action = ExecutorAction::new(
         ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-            prompt: payload.prompt,
+            prompt,
             session_id,
             profile_variant_label,
         }),
@@ -224,13 +405,13 @@ pub async fn follow_up(
     let execution_process = deployment
         .container()
         .start_execution(
-            &task_attempt,
+            task_attempt,
             &follow_up_action,
             &ExecutionProcessRunReason::CodingAgent,
         )
         .await?;
 
-    Ok(ResponseJson(ApiResponse::success(execution_process)))
+    Ok(execution_process)
 }
 
 pub async fn get_task_attempt_diff(
@@ -707,30 +888,45 @@ pub async fn start_dev_server(
         }
     }
 
-    if let Some(dev_server) = project.dev_script {
-        // TODO: Derive script language from system config
+    if project.dev_servers.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "No dev servers configured for this project",
+        )));
+    }
+
+    // Each of the project's dev servers runs as its own process. If one fails to start,
+    // the ones started before it are stopped again rather than left running on their own.
+    let mut started = Vec::new();
+    for dev_server in project.dev_servers.iter() {
         let executor_action = ExecutorAction::new(
-            ExecutorActionType::ScriptRequest(ScriptRequest {
-                script: dev_server,
-                language: ScriptRequestLanguage::Bash,
-                context: ScriptContext::DevServer,
-            }),
+            ExecutorActionType::ScriptRequest(
+                project.script_request(dev_server.script.clone(), ScriptContext::DevServer),
+            ),
             None,
-        );
+        )
+        .with_dev_server(dev_server.clone());
 
-        deployment
+        match deployment
             .container()
             .start_execution(
                 &task_attempt,
                 &executor_action,
                 &ExecutionProcessRunReason::DevServer,
             )
-            .await?
-    } else {
-        return Ok(ResponseJson(ApiResponse::error(
-            "No dev server script configured for this project",
-        )));
-    };
+            .await
+        {
+            Ok(process) => started.push(process),
+            Err(e) => {
+                tracing::error!("Failed to start dev server {}: {}", dev_server.name, e);
+                for process in &started {
+                    if let Err(e) = deployment.container().stop_execution(process).await {
+                        tracing::error!("Failed to stop dev server {}: {}", process.id, e);
+                    }
+                }
+                return Err(e.into());
+            }
+        }
+    }
 
     Ok(ResponseJson(ApiResponse::success(())))
 }
@@ -752,6 +948,83 @@ pub async fn get_task_attempt_children(
     }
 }
 
+pub async fn get_task_attempt_token_usage(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TokenUsageSummary>>, ApiError> {
+    let summary = ExecutionProcessTokenUsage::summary_for_task_attempt(
+        &deployment.db().pool,
+        task_attempt.id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+pub async fn get_task_attempt_approvals(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ToolApproval>>>, ApiError> {
+    let approvals =
+        ToolApproval::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(approvals)))
+}
+
+pub async fn get_task_attempt_plan_review(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<PlanReview>>>, ApiError> {
+    let plan_review =
+        PlanReview::find_latest_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(plan_review)))
+}
+
+pub async fn get_task_attempt_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewComment>>>, ApiError> {
+    let comments =
+        ReviewComment::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TranscriptQuery {
+    #[serde(default)]
+    pub format: TranscriptFormat,
+}
+
+pub async fn export_task_attempt_transcript(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TranscriptQuery>,
+) -> Result<Response, ApiError> {
+    let transcript = deployment
+        .container()
+        .attempt_transcript(&task_attempt)
+        .await?;
+    let body = transcript
+        .render(query.format)
+        .map_err(|e| ContainerError::Other(e.into()))?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}\"",
+                    transcript.file_name(query.format)
+                ),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
 pub async fn stop_task_attempt_execution(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
@@ -762,7 +1035,7 @@ pub async fn stop_task_attempt_execution(
 
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
-        .route("/", get(get_task_attempt))
+        .route("/", get(get_task_attempt).delete(delete_task_attempt))
         .route("/follow-up", post(follow_up))
         .route("/start-dev-server", post(start_dev_server))
         .route("/branch-status", get(get_task_attempt_branch_status))
@@ -774,6 +1047,11 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/delete-file", post(delete_task_attempt_file))
         .route("/children", get(get_task_attempt_children))
+        .route("/token-usage", get(get_task_attempt_token_usage))
+        .route("/approvals", get(get_task_attempt_approvals))
+        .route("/plan-review", get(get_task_attempt_plan_review))
+        .route("/review-comments", get(get_task_attempt_review_comments))
+        .route("/transcript", get(export_task_attempt_transcript))
         .route("/stop", post(stop_task_attempt_execution))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -782,6 +1060,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let task_attempts_router = Router::new()
         .route("/", get(get_task_attempts).post(create_task_attempt))
+        .route("/batch", post(create_task_attempts))
         .nest("/{id}", task_attempt_id_router);
 
     Router::new().nest("/task-attempts", task_attempts_router)
@@ -1,23 +1,38 @@
+use std::path::Path;
+
 use axum::{
-    extract::{Query, State},
+    extract::{Path as AxumPath, Query, State},
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
-    routing::{get, post},
+    routing::{delete, get, post, put},
     Extension, Json, Router,
 };
 use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process_token_usage::{
+        ExecutionProcessTokenUsage, TokenUsageSummary, TokenUsageTotals,
+    },
     project::Project,
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
     task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
+    task_dependency::{CreateTaskDependency, TaskDependency},
 };
 use deployment::Deployment;
-use serde::Deserialize;
-use services::services::container::ContainerService;
+use executors::profile::{ProfileConfigs, ProfileVariantLabel};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    git::{DiffStats, DiffTarget, GitService},
+};
 use sqlx::Error as SqlxError;
+use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{error::ApiError, middleware::load_task_middleware, DeploymentImpl};
+use crate::{
+    error::ApiError, middleware::load_task_middleware, routes::task_attempts::discard_task_attempt,
+    DeploymentImpl,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct TaskQuery {
@@ -54,6 +69,7 @@ pub async fn create_task(
         payload.project_id
     );
 
+    validate_auto_start_profile(payload.auto_start_profile.as_ref())?;
     let task = Task::create(&deployment.db().pool, &payload, id).await?;
 
     // Track task creation event
@@ -76,6 +92,7 @@ pub async fn create_task_and_start(
     Json(payload): Json<CreateTask>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
     // create the task first
+    validate_auto_start_profile(payload.auto_start_profile.as_ref())?;
     let task_id = Uuid::new_v4();
     let task = Task::create(&deployment.db().pool, &payload, task_id).await?;
     deployment
@@ -89,15 +106,19 @@ pub async fn create_task_and_start(
         )
         .await;
 
-    // use the default executor profile and the current branch for the task attempt
-    let default_profile_variant = deployment.config().read().await.profile.clone();
+    // use the project's (or else the global) default executor profile and the current branch
+    // for the task attempt
     let project = Project::find_by_id(&deployment.db().pool, payload.project_id)
         .await?
         .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    let default_profile_variant = match &project.default_profile {
+        Some(default_profile) => default_profile.0.clone(),
+        None => deployment.config().read().await.profile.clone(),
+    };
     let branch = deployment
         .git()
         .get_current_branch(&project.git_repo_path)?;
-    let profile_label = executors::profile::ProfileConfigs::get_cached()
+    let profile_label = ProfileConfigs::get_cached()
         .get_profile(&default_profile_variant.profile)
         .map(|profile| profile.default.label.clone())
         .ok_or_else(|| {
@@ -112,6 +133,7 @@ pub async fn create_task_and_start(
         &CreateTaskAttempt {
             profile: profile_label.clone(),
             base_branch: branch,
+            fallback_from_attempt_id: None,
         },
         task.id,
     )
@@ -144,11 +166,14 @@ pub async fn create_task_and_start(
         project_id: task.project_id,
         status: task.status,
         parent_task_attempt: task.parent_task_attempt,
+        auto_start_profile: task.auto_start_profile,
         created_at: task.created_at,
         updated_at: task.updated_at,
         has_in_progress_attempt: true,
         has_merged_attempt: false,
         last_attempt_failed: false,
+        awaiting_plan_approval: false,
+        is_blocked: false,
         profile: task_attempt.profile,
     })))
 }
@@ -211,9 +236,285 @@ pub async fn delete_task(
     }
 }
 
+pub async fn get_task_token_usage(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TokenUsageSummary>>, ApiError> {
+    let summary =
+        ExecutionProcessTokenUsage::summary_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+/// How one of a task's attempts did, for picking the best of several parallel attempts
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptComparison {
+    pub attempt: TaskAttempt,
+    /// Status of the attempt's latest coding agent run
+    pub status: Option<ExecutionProcessStatus>,
+    /// Wall-clock time from the first process starting to the last one finishing; None
+    /// while anything is still running
+    pub duration_secs: Option<i64>,
+    /// None until the attempt has a branch, or when its diff can't be computed
+    pub diff_stats: Option<DiffStats>,
+    pub token_usage: TokenUsageTotals,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PickTaskAttemptRequest {
+    pub attempt_id: Uuid,
+}
+
+pub async fn compare_task_attempts(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptComparison>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let mut comparisons = Vec::new();
+    for attempt in TaskAttempt::fetch_all(pool, Some(task.id)).await? {
+        let processes = ExecutionProcess::find_by_task_attempt_id(pool, attempt.id).await?;
+        let status = processes
+            .iter()
+            .filter(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+            .max_by_key(|p| p.started_at)
+            .map(|p| p.status.clone());
+        let duration_secs = processes
+            .iter()
+            .map(|p| p.completed_at)
+            .collect::<Option<Vec<_>>>()
+            .and_then(|ends| {
+                let start = processes.iter().map(|p| p.started_at).min()?;
+                let end = ends.into_iter().max()?;
+                Some((end - start).num_seconds())
+            });
+        let diff_stats = attempt_diff_stats(deployment.git(), &project.git_repo_path, &attempt);
+        let token_usage = ExecutionProcessTokenUsage::summary_for_task_attempt(pool, attempt.id)
+            .await?
+            .total;
+
+        comparisons.push(AttemptComparison {
+            attempt,
+            status,
+            duration_secs,
+            diff_stats,
+            token_usage,
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(comparisons)))
+}
+
+fn attempt_diff_stats(
+    git: &GitService,
+    repo_path: &Path,
+    attempt: &TaskAttempt,
+) -> Option<DiffStats> {
+    let target = if let Some(merge_commit) = &attempt.merge_commit {
+        DiffTarget::Commit {
+            repo_path,
+            commit_sha: merge_commit,
+        }
+    } else {
+        let branch_name = attempt.branch.as_deref()?;
+        // The worktree also has changes the agent hasn't committed yet
+        match attempt
+            .container_ref
+            .as_deref()
+            .map(Path::new)
+            .filter(|path| !attempt.worktree_deleted && path.exists())
+        {
+            Some(worktree_path) => DiffTarget::Worktree {
+                worktree_path,
+                branch_name,
+                base_branch: &attempt.base_branch,
+            },
+            None => DiffTarget::Branch {
+                repo_path,
+                branch_name,
+                base_branch: &attempt.base_branch,
+            },
+        }
+    };
+
+    git.get_diff_stats(target)
+        .map_err(|e| {
+            tracing::warn!(
+                "Failed to compute diff stats for task attempt {}: {}",
+                attempt.id,
+                e
+            );
+        })
+        .ok()
+}
+
+/// Keep one attempt and discard all the others, including their worktrees and branches
+pub async fn pick_task_attempt(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<PickTaskAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let attempts = TaskAttempt::fetch_all(&deployment.db().pool, Some(task.id)).await?;
+    let (picked, discarded): (Vec<_>, Vec<_>) = attempts
+        .into_iter()
+        .partition(|attempt| attempt.id == payload.attempt_id);
+    let picked = picked.into_iter().next().ok_or_else(|| {
+        ApiError::TaskAttempt(TaskAttemptError::ValidationError(format!(
+            "Task attempt {} does not belong to task {}",
+            payload.attempt_id, task.id
+        )))
+    })?;
+
+    // Check every attempt before discarding any, so a refusal leaves all of them in place
+    let pool = &deployment.db().pool;
+    for attempt in &discarded {
+        let branch = attempt.branch.as_deref().unwrap_or("(no branch)");
+        if attempt.merge_commit.is_some() || attempt.pr_merged_at.is_some() {
+            return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+                format!("Attempt on {branch} has been merged and can't be discarded"),
+            )));
+        }
+        let running = ExecutionProcess::find_by_task_attempt_id(pool, attempt.id)
+            .await?
+            .into_iter()
+            .any(|p| {
+                p.run_reason != ExecutionProcessRunReason::DevServer
+                    && matches!(
+                        p.status,
+                        ExecutionProcessStatus::Running | ExecutionProcessStatus::Queued
+                    )
+            });
+        if running {
+            return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+                format!(
+                    "Attempt on {branch} is still running, stop it before picking another attempt"
+                ),
+            )));
+        }
+    }
+
+    for attempt in &discarded {
+        discard_task_attempt(&deployment, attempt).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_picked",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "attempt_id": picked.id.to_string(),
+                "profile": &picked.profile,
+                "discarded_attempts": discarded.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(picked)))
+}
+
+pub async fn get_task_dependencies(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let blockers = TaskDependency::find_blockers(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(blockers)))
+}
+
+pub async fn create_task_dependency(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskDependency>,
+) -> Result<ResponseJson<ApiResponse<TaskDependency>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(blocker) = Task::find_by_id(pool, payload.blocked_by_task_id).await? else {
+        return Ok(ResponseJson(ApiResponse::error("Blocking task not found")));
+    };
+    if blocker.id == task.id {
+        return Ok(ResponseJson(ApiResponse::error(
+            "A task cannot be blocked by itself",
+        )));
+    }
+    if blocker.project_id != task.project_id {
+        return Ok(ResponseJson(ApiResponse::error(
+            "A task can only be blocked by tasks of the same project",
+        )));
+    }
+    // Inserting first takes the database's write lock, so no other dependency can close a
+    // cycle between the check and the commit. Returning early rolls the insert back.
+    let mut tx = pool.begin().await?;
+    let dependency = TaskDependency::create(&mut *tx, task.id, blocker.id).await?;
+    if TaskDependency::is_blocked_by(&mut *tx, blocker.id, task.id).await? {
+        return Ok(ResponseJson(ApiResponse::error(&format!(
+            "'{}' is already blocked by '{}', this would make them wait on each other",
+            blocker.title, task.title
+        ))));
+    }
+    tx.commit().await?;
+    Ok(ResponseJson(ApiResponse::success(dependency)))
+}
+
+pub async fn delete_task_dependency(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    AxumPath((_, blocked_by_task_id)): AxumPath<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected =
+        TaskDependency::delete(&deployment.db().pool, task.id, blocked_by_task_id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(SqlxError::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateAutoStartRequest {
+    /// Profile variant to start the task with once all its blockers are done; None turns
+    /// auto-start off
+    pub profile_variant_label: Option<ProfileVariantLabel>,
+}
+
+/// Check that the profile a task is set to auto-start with exists
+fn validate_auto_start_profile(label: Option<&ProfileVariantLabel>) -> Result<(), ApiError> {
+    label.map_or(Ok(()), |label| {
+        ProfileConfigs::get_cached()
+            .validate_label(label)
+            .map_err(|e| ApiError::TaskAttempt(TaskAttemptError::ValidationError(e)))
+    })
+}
+
+pub async fn update_task_auto_start(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateAutoStartRequest>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    validate_auto_start_profile(payload.profile_variant_label.as_ref())?;
+    let pool = &deployment.db().pool;
+    Task::update_auto_start_profile(pool, task.id, payload.profile_variant_label).await?;
+    let task = Task::find_by_id(pool, task.id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_id_router = Router::new()
         .route("/", get(get_task).put(update_task).delete(delete_task))
+        .route("/token-usage", get(get_task_token_usage))
+        .route("/attempts/compare", get(compare_task_attempts))
+        .route("/attempts/pick", post(pick_task_attempt))
+        .route(
+            "/dependencies",
+            get(get_task_dependencies).post(create_task_dependency),
+        )
+        .route(
+            "/dependencies/{blocked_by_task_id}",
+            delete(delete_task_dependency),
+        )
+        .route("/auto-start", put(update_task_auto_start))
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
     let inner = Router::new()
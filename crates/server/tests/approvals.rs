@@ -0,0 +1,66 @@
+//! Answering the tool uses an agent asked permission for
+
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::TestApp;
+use db::models::tool_approval::{CreateToolApproval, ToolApproval};
+use deployment::Deployment;
+use executors::executors::claude::approvals_mcp_config_path;
+use serde_json::json;
+use uuid::Uuid;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn approval_can_be_answered_once() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+    let agent = app.coding_agent(attempt.id).await;
+    let approval = ToolApproval::create(
+        &app.deployment.db().pool,
+        &CreateToolApproval {
+            execution_process_id: agent.id,
+            tool_name: "Bash".to_string(),
+            tool_input: json!({ "command": "rm -rf build" }),
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+
+    let uri = format!("/api/approvals/{}/respond", approval.id);
+    let answered = app
+        .request(Method::POST, &uri, Some(json!({ "approved": true })))
+        .await;
+    assert_eq!(answered["status"], "approved");
+
+    let (status, _) = app
+        .send(Method::POST, &uri, Some(json!({ "approved": false })))
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unknown_approval_is_not_found() {
+    let app = TestApp::new().await;
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &format!("/api/approvals/{}/respond", Uuid::new_v4()),
+            Some(json!({ "approved": true })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn approvals_mcp_config_is_removed_when_the_agent_exits() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempt = app.start_attempt(task_id, "fake-approvals").await;
+    let agent = app.coding_agent(attempt.id).await;
+
+    let config_path = approvals_mcp_config_path(agent.id);
+    assert!(config_path.parent().unwrap().is_dir());
+    assert!(!config_path.exists());
+}
@@ -0,0 +1,371 @@
+//! Attempts end to end: the agent runs in a worktree, its changes are committed and
+//! merged, and failed, stopped and best-of-N attempts are handled
+
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use axum::http::{Method, StatusCode};
+use common::TestApp;
+use db::models::{
+    execution_process::{ExecutionProcessRunReason, ExecutionProcessStatus},
+    executor_session::ExecutorSession,
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use serde_json::{json, Value};
+use services::services::container::ContainerService;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn successful_attempt_commits_and_merges() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(Some("echo ready > setup.txt")).await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+
+    let processes = app.wait_for_attempt(attempt.id).await;
+    let run_reasons: Vec<_> = processes.iter().map(|p| p.run_reason.clone()).collect();
+    assert_eq!(
+        run_reasons,
+        vec![
+            ExecutionProcessRunReason::SetupScript,
+            ExecutionProcessRunReason::CodingAgent
+        ]
+    );
+    assert!(processes
+        .iter()
+        .all(|p| p.status == ExecutionProcessStatus::Completed));
+
+    // The session id from the replayed output is recorded for follow-ups
+    let agent = &processes[1];
+    let session =
+        ExecutorSession::find_by_execution_process_id(&app.deployment.db().pool, agent.id)
+            .await
+            .unwrap()
+            .unwrap();
+    assert_eq!(session.session_id.as_deref(), Some("fake-session-1"));
+
+    // The agent's edit was committed on the attempt branch
+    let attempt = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let worktree = PathBuf::from(attempt.container_ref.clone().unwrap());
+    assert_eq!(
+        fs::read_to_string(worktree.join("hello.txt")).unwrap(),
+        "Hello from the fake agent\n"
+    );
+    let branch_status = app
+        .request(
+            Method::GET,
+            &format!("/api/task-attempts/{}/branch-status", attempt.id),
+            None,
+        )
+        .await;
+    assert_eq!(branch_status["commits_ahead"], 1);
+    assert_eq!(branch_status["has_uncommitted_changes"], false);
+
+    // The transcript is rebuilt from the stored logs once the agent has exited
+    let transcript: Value = serde_json::from_slice(
+        &app.request_raw(
+            Method::GET,
+            &format!("/api/task-attempts/{}/transcript?format=json", attempt.id),
+            None,
+        )
+        .await,
+    )
+    .unwrap();
+    let conversation = &transcript["runs"][0]["conversation"];
+    assert_eq!(conversation["session_id"], "fake-session-1");
+    assert_eq!(conversation["executor_type"], "fake-success");
+    assert!(conversation["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|entry| entry["content"] == "Done."));
+    let markdown = app
+        .request_raw(
+            Method::GET,
+            &format!("/api/task-attempts/{}/transcript", attempt.id),
+            None,
+        )
+        .await;
+    assert!(String::from_utf8_lossy(&markdown).starts_with("# Add a greeting\n"));
+
+    app.request(
+        Method::POST,
+        &format!("/api/task-attempts/{}/merge", attempt.id),
+        None,
+    )
+    .await;
+    assert_eq!(
+        fs::read_to_string(app.repo.path().join("hello.txt")).unwrap(),
+        "Hello from the fake agent\n"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failing_agent_marks_process_failed() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempt = app.start_attempt(task_id, "fake-failure").await;
+
+    let agent = app.coding_agent(attempt.id).await;
+    assert_eq!(agent.status, ExecutionProcessStatus::Failed);
+    assert_eq!(agent.exit_code, Some(1));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn finished_agent_does_not_accept_messages() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+    let agent = app.coding_agent(attempt.id).await;
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &format!("/api/execution-processes/{}/message", agent.id),
+            Some(json!({ "message": "Also update the README" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stopping_an_execution_kills_the_agent() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempt = app.start_attempt(task_id, "fake-slow").await;
+
+    let attempt = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    app.deployment.container().try_stop(&attempt).await;
+
+    let agent = app.coding_agent(attempt.id).await;
+    assert_eq!(agent.status, ExecutionProcessStatus::Killed);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn project_defaults_apply_to_new_attempts() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({
+            "setup_script": "printf \"$SETUP_GREETING\" > setup.txt",
+            "default_profile": { "profile": "fake-success", "variant": null },
+            "env_vars": { "SETUP_GREETING": "hello from the project" },
+        }))
+        .await;
+    let attempt = app.start_attempt_with_label(task_id, Value::Null).await;
+    assert_eq!(attempt.profile, "fake-success");
+
+    let agent = app.coding_agent(attempt.id).await;
+    assert_eq!(agent.status, ExecutionProcessStatus::Completed);
+    let attempt = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let worktree = PathBuf::from(attempt.container_ref.unwrap());
+    assert_eq!(
+        fs::read_to_string(worktree.join("setup.txt")).unwrap(),
+        "hello from the project"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unknown_default_profile_is_rejected() {
+    let app = TestApp::new().await;
+    let (_, body) = app
+        .send(
+            Method::POST,
+            "/api/projects",
+            Some(json!({
+                "name": "Fake agent project",
+                "git_repo_path": app.repo.path(),
+                "use_existing_repo": true,
+                "setup_script": null,
+                "dev_servers": null,
+                "cleanup_script": null,
+                "copy_files": null,
+                "default_profile": { "profile": "fake-success", "variant": "missing" },
+            })),
+        )
+        .await;
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["success"], false);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("no variant 'missing'"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn best_of_n_attempts_compare_and_pick() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempts: Vec<TaskAttempt> = serde_json::from_value(
+        app.request(
+            Method::POST,
+            "/api/task-attempts/batch",
+            Some(json!({
+                "task_id": task_id,
+                "profile_variant_labels": [
+                    { "profile": "fake-success", "variant": null },
+                    { "profile": "fake-failure", "variant": null },
+                ],
+                "base_branch": "main",
+            })),
+        )
+        .await,
+    )
+    .unwrap();
+    assert_eq!(attempts.len(), 2);
+    for attempt in &attempts {
+        app.wait_for_attempt(attempt.id).await;
+    }
+
+    let comparisons = app
+        .request(
+            Method::GET,
+            &format!("/api/tasks/{task_id}/attempts/compare"),
+            None,
+        )
+        .await;
+    let comparison = |attempt: &TaskAttempt| {
+        comparisons
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["attempt"]["id"] == attempt.id.to_string())
+            .unwrap()
+            .clone()
+    };
+    let (winner, loser) = (comparison(&attempts[0]), comparison(&attempts[1]));
+    assert_eq!(winner["status"], "completed");
+    assert_eq!(
+        winner["diff_stats"],
+        json!({ "files_changed": 1, "additions": 1, "deletions": 0 })
+    );
+    assert!(winner["duration_secs"].is_i64());
+    assert_eq!(loser["status"], "failed");
+    assert_eq!(loser["diff_stats"]["files_changed"], 0);
+
+    // A merged attempt is never discarded
+    let pool = &app.deployment.db().pool;
+    sqlx::query("UPDATE task_attempts SET merge_commit = 'abc123' WHERE id = $1")
+        .bind(attempts[1].id)
+        .execute(pool)
+        .await
+        .unwrap();
+    let (status, _) = app
+        .send(
+            Method::POST,
+            &format!("/api/tasks/{task_id}/attempts/pick"),
+            Some(json!({ "attempt_id": attempts[0].id })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        TaskAttempt::fetch_all(pool, Some(task_id))
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    sqlx::query("UPDATE task_attempts SET merge_commit = NULL WHERE id = $1")
+        .bind(attempts[1].id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+    // Picking the winner discards the other attempt with its worktree and branch
+    let loser = TaskAttempt::find_by_id(pool, attempts[1].id)
+        .await
+        .unwrap()
+        .unwrap();
+    app.request(
+        Method::POST,
+        &format!("/api/tasks/{task_id}/attempts/pick"),
+        Some(json!({ "attempt_id": attempts[0].id })),
+    )
+    .await;
+    let remaining = TaskAttempt::fetch_all(pool, Some(task_id)).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, attempts[0].id);
+    assert!(!Path::new(loser.container_ref.as_deref().unwrap()).exists());
+    assert!(git2::Repository::open(app.repo.path())
+        .unwrap()
+        .find_branch(loser.branch.as_deref().unwrap(), git2::BranchType::Local)
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn worktree_git_dir_is_shared_with_the_repository() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+    app.wait_for_attempt(attempt.id).await;
+    let attempt = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Sandboxed agents get the worktree's own git dir, with its index and HEAD, as a
+    // writable bind, and the common dir's refs and objects they commit to
+    let worktree = Path::new(attempt.container_ref.as_deref().unwrap());
+    let common_dir = app.deployment.git().get_common_dir(worktree).unwrap();
+    assert_eq!(
+        common_dir,
+        app.repo.path().join(".git").canonicalize().unwrap()
+    );
+    let worktree_git_dir = app.deployment.git().get_worktree_git_dir(worktree).unwrap();
+    assert!(worktree_git_dir.starts_with(common_dir.join("worktrees")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn best_of_n_batch_is_capped_and_all_or_nothing() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let batch = |count: usize, base_branch: &str| {
+        json!({
+            "task_id": task_id,
+            "profile_variant_labels": vec![
+                json!({ "profile": "fake-success", "variant": null });
+                count
+            ],
+            "base_branch": base_branch,
+        })
+    };
+    let pool = &app.deployment.db().pool;
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/api/task-attempts/batch",
+            Some(batch(9, "main")),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(TaskAttempt::fetch_all(pool, Some(task_id))
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Attempts that can't start are all discarded, not just the one that failed
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/api/task-attempts/batch",
+            Some(batch(3, "no-such-branch")),
+        )
+        .await;
+    assert!(!status.is_success());
+    assert!(TaskAttempt::fetch_all(pool, Some(task_id))
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,346 @@
+//! Shared setup for the end-to-end tests of the container flow (worktree, setup script,
+//! coding agent, commit, diff, merge), driven through the API router with `fake_agent`
+//! standing in for the coding agent CLI.
+//!
+//! Every fixture but `slow.jsonl` starts with a short sleep: like a real CLI, the agent
+//! should not exit before it has read the prompt, and the log normalizer and the DB log
+//! streamer have attached to its output.
+
+// Each test binary uses only some of the helpers
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Once,
+    time::Duration,
+};
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    http::{Method, Request, StatusCode},
+    Router,
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use serde_json::{json, Value};
+use server::{routes, DeploymentImpl};
+use services::services::container::ContainerService;
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+static INIT: Once = Once::new();
+
+pub fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// Point the asset and temp dirs at a scratch directory, with one profile per fixture
+fn init_env() {
+    INIT.call_once(|| {
+        let root = TempDir::new().unwrap().keep();
+        let asset_dir = root.join("assets");
+        fs::create_dir_all(&asset_dir).unwrap();
+
+        let agent = |fixture_name: &str, plan: bool| {
+            // Plan mode runs the command through a watch script that doesn't honour quotes
+            let quote = if plan { "" } else { "'" };
+            json!({
+                "command": {
+                    "base": format!(
+                        "{quote}{}{quote} {quote}{}{quote}",
+                        env!("CARGO_BIN_EXE_fake_agent"),
+                        fixture(fixture_name).display()
+                    ),
+                },
+                "plan": plan
+            })
+        };
+        let profile = |label: &str, fixture_name: &str| {
+            json!({
+                "label": label,
+                "mcp_config_path": null,
+                "CLAUDE_CODE": agent(fixture_name, false),
+                "variants": []
+            })
+        };
+        let mut plan_profile = profile("fake-plan", "success.jsonl");
+        plan_profile["variants"] = json!([{
+            "label": "plan",
+            "mcp_config_path": null,
+            "CLAUDE_CODE": agent("plan.jsonl", true),
+        }]);
+        let mut approvals_profile = profile("fake-approvals", "success.jsonl");
+        approvals_profile["CLAUDE_CODE"]["approvals"] = json!(true);
+        let profiles = json!({
+            "profiles": [
+                profile("fake-success", "success.jsonl"),
+                approvals_profile,
+                profile("fake-failure", "failure.jsonl"),
+                profile("fake-slow", "slow.jsonl"),
+                profile("fake-review", "review.jsonl"),
+                plan_profile,
+            ]
+        });
+        fs::write(asset_dir.join("profiles.json"), profiles.to_string()).unwrap();
+
+        // SAFETY: runs once, before any test reads the environment
+        unsafe {
+            std::env::set_var("VIBE_KANBAN_ASSET_DIR", &asset_dir);
+            std::env::set_var("VIBE_KANBAN_TEMP_DIR", root.join("tmp"));
+            std::env::set_var("DISABLE_WORKTREE_ORPHAN_CLEANUP", "1");
+        }
+    });
+}
+
+/// A git repository on `main` with a single commit
+pub fn init_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let mut opts = git2::RepositoryInitOptions::new();
+    opts.initial_head("main");
+    let repo = git2::Repository::init_opts(dir.path(), &opts).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Test").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+
+    fs::write(dir.path().join("README.md"), "# Test\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("README.md")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = repo.signature().unwrap();
+    repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+        .unwrap();
+    dir
+}
+
+pub struct TestApp {
+    pub deployment: DeploymentImpl,
+    pub router: Router,
+    pub repo: TempDir,
+}
+
+impl TestApp {
+    pub async fn new() -> Self {
+        init_env();
+        let deployment = DeploymentImpl::new().await.unwrap();
+        {
+            let mut config = deployment.config().write().await;
+            config.notifications.sound_enabled = false;
+            config.notifications.push_enabled = false;
+        }
+        let router = routes::router(deployment.clone())
+            .oneshot(())
+            .await
+            .unwrap();
+        Self {
+            deployment,
+            router,
+            repo: init_repo(),
+        }
+    }
+
+    /// Send a request and return the response status and raw body
+    pub async fn send(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Bytes) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+            .unwrap();
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        (
+            status,
+            to_bytes(response.into_body(), usize::MAX).await.unwrap(),
+        )
+    }
+
+    /// Send a request and return the raw response body
+    pub async fn request_raw(&self, method: Method, uri: &str, body: Option<Value>) -> Bytes {
+        self.send(method, uri, body).await.1
+    }
+
+    /// Send a request and return the `data` of the `ApiResponse`
+    pub async fn request(&self, method: Method, uri: &str, body: Option<Value>) -> Value {
+        let bytes = self.request_raw(method, uri, body).await;
+        let body: Value = serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| panic!("{uri} returned an invalid response: {bytes:?}"));
+        assert!(
+            body["success"].as_bool().unwrap_or(false),
+            "{uri} failed: {body}"
+        );
+        body["data"].clone()
+    }
+
+    /// Read a finished process's logs from one of its log endpoints, returning the index of
+    /// the first entry sent and the entries in order
+    pub async fn log_window(
+        &self,
+        process_id: Uuid,
+        endpoint: &str,
+        query: &str,
+    ) -> (usize, Vec<Value>) {
+        let body = self
+            .request_raw(
+                Method::GET,
+                &format!("/api/execution-processes/{process_id}/{endpoint}{query}"),
+                None,
+            )
+            .await;
+        let mut start = None;
+        let mut entries = Vec::new();
+        for event in String::from_utf8_lossy(&body).split("\n\n") {
+            let name = event.lines().find_map(|line| line.strip_prefix("event: "));
+            let data: Vec<_> = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data: "))
+                .collect();
+            match name {
+                Some("entries_start") => start = Some(data[0].parse().unwrap()),
+                Some("json_patch") => {
+                    let patch: Value = serde_json::from_str(&data.join("\n")).unwrap();
+                    for op in patch.as_array().unwrap() {
+                        assert_eq!(op["op"], "add");
+                        assert_eq!(op["path"], format!("/entries/{}", entries.len()));
+                        entries.push(op["value"].clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+        (start.expect("No entries_start event was sent"), entries)
+    }
+
+    pub async fn create_task(&self, setup_script: Option<&str>) -> Uuid {
+        self.create_task_with(json!({ "setup_script": setup_script }))
+            .await
+    }
+
+    /// Create a project with the given extra fields, and a task in it
+    pub async fn create_task_with(&self, project_fields: Value) -> Uuid {
+        let mut body = json!({
+            "name": "Fake agent project",
+            "git_repo_path": self.repo.path(),
+            "use_existing_repo": true,
+            "setup_script": null,
+            "dev_servers": null,
+            "cleanup_script": null,
+            "copy_files": null,
+        });
+        body.as_object_mut()
+            .unwrap()
+            .extend(project_fields.as_object().unwrap().clone());
+        let project = self
+            .request(Method::POST, "/api/projects", Some(body))
+            .await;
+        let task = self
+            .request(
+                Method::POST,
+                "/api/tasks",
+                Some(json!({
+                    "project_id": project["id"],
+                    "title": "Add a greeting",
+                    "description": null,
+                    "parent_task_attempt": null,
+                })),
+            )
+            .await;
+        task["id"].as_str().unwrap().parse().unwrap()
+    }
+
+    pub async fn start_attempt(&self, task_id: Uuid, profile: &str) -> TaskAttempt {
+        self.start_attempt_with_variant(task_id, profile, None)
+            .await
+    }
+
+    pub async fn start_attempt_with_variant(
+        &self,
+        task_id: Uuid,
+        profile: &str,
+        variant: Option<&str>,
+    ) -> TaskAttempt {
+        self.start_attempt_with_label(task_id, json!({ "profile": profile, "variant": variant }))
+            .await
+    }
+
+    pub async fn start_attempt_with_label(&self, task_id: Uuid, label: Value) -> TaskAttempt {
+        let attempt = self
+            .request(
+                Method::POST,
+                "/api/task-attempts",
+                Some(json!({
+                    "task_id": task_id,
+                    "profile_variant_label": label,
+                    "base_branch": "main",
+                })),
+            )
+            .await;
+        serde_json::from_value(attempt).unwrap()
+    }
+
+    /// Wait until a coding agent has run for the attempt and nothing is running or being
+    /// wrapped up any more
+    pub async fn wait_for_attempt(&self, attempt_id: Uuid) -> Vec<ExecutionProcess> {
+        for _ in 0..200 {
+            let processes =
+                ExecutionProcess::find_by_task_attempt_id(&self.deployment.db().pool, attempt_id)
+                    .await
+                    .unwrap();
+            let finished = |p: &ExecutionProcess| {
+                !matches!(
+                    p.status,
+                    ExecutionProcessStatus::Running | ExecutionProcessStatus::Queued
+                )
+            };
+            let agent_finished = processes
+                .iter()
+                .any(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent && finished(p));
+            let mut all_finished = processes.iter().all(finished);
+            // The exit monitor commits and starts the next action after recording the exit,
+            // and drops the process's log store once it's done
+            for process in &processes {
+                all_finished &= self
+                    .deployment
+                    .container()
+                    .get_msg_store_by_id(&process.id)
+                    .await
+                    .is_none();
+            }
+            if agent_finished && all_finished {
+                return processes;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("Attempt {attempt_id} did not finish");
+    }
+
+    /// The attempt's coding agent as it is now, without waiting for it to finish
+    pub async fn current_coding_agent(&self, attempt_id: Uuid) -> ExecutionProcess {
+        ExecutionProcess::find_by_task_attempt_id(&self.deployment.db().pool, attempt_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+            .unwrap()
+    }
+
+    pub async fn coding_agent(&self, attempt_id: Uuid) -> ExecutionProcess {
+        self.wait_for_attempt(attempt_id)
+            .await
+            .into_iter()
+            .find(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+            .unwrap()
+    }
+}
@@ -0,0 +1,256 @@
+//! Dev servers: URL detection, the preview proxy, health checks and restarts
+
+mod common;
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use axum::http::{Method, StatusCode};
+use common::TestApp;
+use db::models::{
+    execution_process::{
+        DevServerHealth, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
+    task::Task,
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use serde_json::json;
+use server::routes;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use uuid::Uuid;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dev_server_url_is_detected_and_proxied() {
+    let app = TestApp::new().await;
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let task_id = app
+        .create_task_with(json!({
+            "dev_servers": [{
+                "name": "web",
+                "script": format!("python3 -u -m http.server {port} --bind 127.0.0.1"),
+            }],
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+    app.wait_for_attempt(attempt.id).await;
+
+    let dev_server = start_dev_server(&app, attempt.id).await;
+    assert_eq!(
+        dev_server.dev_server_url,
+        Some(format!("http://127.0.0.1:{port}"))
+    );
+
+    // The preview forwards the rest of its path, still percent-encoded, and the query
+    let worktree = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .and_then(|attempt| attempt.container_ref)
+        .map(PathBuf::from)
+        .unwrap();
+    fs::create_dir(worktree.join("assets")).unwrap();
+    fs::write(worktree.join("assets/app.js"), "console.log('app');\n").unwrap();
+    fs::write(worktree.join("assets/a b.txt"), "spaced\n").unwrap();
+    let preview = format!("{}/api/preview/{}/web", serve(&app).await, attempt.id);
+
+    let get = |path: &str| reqwest::get(format!("{preview}{path}"));
+    let readme = get("/README.md").await.unwrap();
+    assert_eq!(readme.text().await.unwrap(), "# Test\n");
+    let asset = get("/assets/app.js?v=1").await.unwrap();
+    assert_eq!(asset.text().await.unwrap(), "console.log('app');\n");
+    let spaced = get("/assets/a%20b.txt").await.unwrap();
+    assert_eq!(spaced.text().await.unwrap(), "spaced\n");
+    let index = get("/").await.unwrap();
+    assert!(index.text().await.unwrap().contains("README.md"));
+
+    let (status, _) = app
+        .send(
+            Method::GET,
+            &format!("/api/preview/{}/worker", attempt.id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    app.request(
+        Method::POST,
+        &format!("/api/execution-processes/{}/stop", dev_server.id),
+        None,
+    )
+    .await;
+    // Nothing of the preview outlives the dev server
+    let readme = get("/README.md").await.unwrap();
+    assert_eq!(readme.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn preview_passes_connection_upgrades_through() {
+    let app = TestApp::new().await;
+    let server = common::fixture("upgrade_echo_server.py");
+    let task_id = app
+        .create_task_with(json!({
+            "dev_servers": [{
+                "name": "echo",
+                "script": format!("python3 -u {}", server.display()),
+            }],
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+    app.wait_for_attempt(attempt.id).await;
+    start_dev_server(&app, attempt.id).await;
+    let origin = serve(&app).await;
+
+    let mut stream = TcpStream::connect(origin.strip_prefix("http://").unwrap())
+        .await
+        .unwrap();
+    let request = format!(
+        "GET /api/preview/{}/echo/socket HTTP/1.1\r\n\
+         Host: preview\r\n\
+         Connection: upgrade\r\n\
+         Upgrade: echo\r\n\r\n",
+        attempt.id
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        let mut byte = [0u8];
+        stream.read_exact(&mut byte).await.unwrap();
+        response.push(byte[0]);
+    }
+    assert!(response.starts_with(b"HTTP/1.1 101"));
+
+    stream.write_all(b"ping").await.unwrap();
+    let mut echoed = [0u8; 4];
+    stream.read_exact(&mut echoed).await.unwrap();
+    assert_eq!(&echoed, b"ping");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dev_servers_report_health_and_restart_after_crashing() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({
+            "dev_servers": [
+                {
+                    "name": "api",
+                    "script": "echo 'api ready'; sleep 30",
+                    "health_check": { "type": "LogRegex", "pattern": "ready$" },
+                },
+                {
+                    "name": "worker",
+                    "script": "test -f crashed || { touch crashed; exit 1; }; sleep 30",
+                },
+            ],
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+    app.wait_for_attempt(attempt.id).await;
+
+    app.request(
+        Method::POST,
+        &format!("/api/task-attempts/{}/start-dev-server", attempt.id),
+        None,
+    )
+    .await;
+
+    let dev_server_name = |p: &ExecutionProcess| {
+        p.executor_action()
+            .unwrap()
+            .dev_server()
+            .unwrap()
+            .name
+            .clone()
+    };
+    let project_id = Task::find_by_id(&app.deployment.db().pool, task_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .project_id;
+    // The worker crashes on its first run and is started again
+    let pool = &app.deployment.db().pool;
+    let mut dev_servers = Vec::new();
+    let mut running = Vec::new();
+    for _ in 0..100 {
+        dev_servers = ExecutionProcess::find_by_task_attempt_id(pool, attempt.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|p| p.run_reason == ExecutionProcessRunReason::DevServer)
+            .collect();
+        running = ExecutionProcess::find_running_dev_servers_by_project(pool, project_id)
+            .await
+            .unwrap();
+        if dev_servers.len() == 3
+            && running.len() == 2
+            && running
+                .iter()
+                .all(|p| p.dev_server_health == Some(DevServerHealth::Healthy))
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let mut names: Vec<_> = running.iter().map(dev_server_name).collect();
+    names.sort();
+    assert_eq!(names, vec!["api", "worker"]);
+    assert!(running
+        .iter()
+        .all(|p| p.dev_server_health == Some(DevServerHealth::Healthy)));
+
+    let workers: Vec<_> = dev_servers
+        .iter()
+        .filter(|p| dev_server_name(p) == "worker")
+        .collect();
+    assert_eq!(workers.len(), 2);
+    assert_eq!(workers[0].status, ExecutionProcessStatus::Failed);
+    assert_eq!(workers[1].status, ExecutionProcessStatus::Running);
+
+    for dev_server in running {
+        app.request(
+            Method::POST,
+            &format!("/api/execution-processes/{}/stop", dev_server.id),
+            None,
+        )
+        .await;
+    }
+}
+
+/// Start the attempt's dev servers and wait for one to report its URL
+async fn start_dev_server(app: &TestApp, attempt_id: Uuid) -> ExecutionProcess {
+    app.request(
+        Method::POST,
+        &format!("/api/task-attempts/{attempt_id}/start-dev-server"),
+        None,
+    )
+    .await;
+
+    for _ in 0..100 {
+        let processes =
+            ExecutionProcess::find_by_task_attempt_id(&app.deployment.db().pool, attempt_id)
+                .await
+                .unwrap();
+        if let Some(dev_server) = processes.into_iter().find(|p| {
+            p.run_reason == ExecutionProcessRunReason::DevServer && p.dev_server_url.is_some()
+        }) {
+            return dev_server;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("The dev server URL wasn't detected");
+}
+
+/// Serve the API on a port, as vibe-kanban does, and return its origin. Unlike requests
+/// sent to the router directly, connections to it can be upgraded.
+async fn serve(app: &TestApp) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = routes::router(app.deployment.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    format!("http://{addr}")
+}
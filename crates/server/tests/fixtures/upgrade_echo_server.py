@@ -0,0 +1,20 @@
+"""A dev server that answers upgrades to any protocol by echoing what it's sent"""
+
+import socket
+
+server = socket.socket()
+server.bind(("127.0.0.1", 0))
+server.listen()
+print(f"http://127.0.0.1:{server.getsockname()[1]}", flush=True)
+
+while True:
+    conn, _ = server.accept()
+    request = b""
+    while b"\r\n\r\n" not in request:
+        request += conn.recv(1024)
+    conn.sendall(
+        b"HTTP/1.1 101 Switching Protocols\r\nConnection: upgrade\r\nUpgrade: echo\r\n\r\n"
+    )
+    while data := conn.recv(1024):
+        conn.sendall(data)
+    conn.close()
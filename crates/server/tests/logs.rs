@@ -0,0 +1,140 @@
+//! Execution logs: stored as append-only chunks, read a line range at a time and streamed
+//! a window at a time from the end
+
+mod common;
+
+use common::TestApp;
+use db::models::{
+    execution_process::{CreateExecutionProcess, ExecutionProcess, ExecutionProcessStatus},
+    execution_process_logs::ExecutionProcessLogs,
+    execution_process_normalized_entries::ExecutionProcessNormalizedEntries,
+};
+use deployment::Deployment;
+use uuid::Uuid;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn logs_are_read_a_window_at_a_time_from_the_end() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task(Some(
+            "for i in $(seq 1 30); do echo line $i; sleep 0.02; done",
+        ))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+    let processes = app.wait_for_attempt(attempt.id).await;
+    let (setup, agent) = (&processes[0], &processes[1]);
+
+    let pool = &app.deployment.db().pool;
+    let logs = ExecutionProcessLogs::find_by_execution_id(pool, setup.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(logs.logs.contains("line 30"));
+    assert_eq!(logs.byte_size, logs.logs.len() as i64);
+    let line_count = ExecutionProcessLogs::line_count(pool, setup.id)
+        .await
+        .unwrap();
+    assert_eq!(logs.parse_logs().unwrap().len() as i64, line_count);
+
+    // The tail comes first, then the page before it
+    let (start, all) = app.log_window(setup.id, "raw-logs", "").await;
+    assert_eq!(start, 0);
+    assert_eq!(all.len() as i64, line_count);
+    assert!(all.len() > 10);
+    let (tail_start, tail) = app.log_window(setup.id, "raw-logs", "?limit=5").await;
+    assert_eq!(tail_start, all.len() - 5);
+    assert_eq!(tail, all[tail_start..]);
+    let (page_start, page) = app
+        .log_window(
+            setup.id,
+            "raw-logs",
+            &format!("?before={tail_start}&limit=5"),
+        )
+        .await;
+    assert_eq!(page_start, tail_start - 5);
+    assert_eq!(page, all[page_start..tail_start]);
+
+    let (start, conversation) = app
+        .log_window(agent.id, "normalized-logs", "?limit=1000")
+        .await;
+    assert_eq!(start, 0);
+    assert!(conversation.len() > 1);
+    let (last_start, last) = app
+        .log_window(agent.id, "normalized-logs", "?limit=1")
+        .await;
+    assert_eq!(last_start, conversation.len() - 1);
+    assert_eq!(last, conversation[last_start..]);
+
+    // The finished conversation is normalized once and its windows read from the database
+    let stored = ExecutionProcessNormalizedEntries::count(pool, agent.id)
+        .await
+        .unwrap();
+    assert_eq!(stored as usize, conversation.len());
+    let (earlier_start, earlier) = app
+        .log_window(
+            agent.id,
+            "normalized-logs",
+            &format!("?before={last_start}&limit=1000"),
+        )
+        .await;
+    assert_eq!(earlier_start, 0);
+    assert_eq!(earlier, conversation[..last_start]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn lines_are_read_across_chunk_boundaries() {
+    let app = TestApp::new().await;
+    let id = process_with_chunks(&app, &["0\n1\n2\n", "3\n", "4\n5\n"]).await;
+    let pool = &app.deployment.db().pool;
+
+    let lines = |range| ExecutionProcessLogs::find_lines(pool, id, range);
+    assert_eq!(lines(0..3).await.unwrap().unwrap().logs, "0\n1\n2\n");
+    assert_eq!(lines(2..5).await.unwrap().unwrap().logs, "2\n3\n4\n");
+    assert_eq!(lines(3..6).await.unwrap().unwrap().logs, "3\n4\n5\n");
+    let all = lines(0..i64::MAX).await.unwrap().unwrap();
+    assert_eq!(all.logs, "0\n1\n2\n3\n4\n5\n");
+    assert_eq!(all.byte_size, 12);
+    assert_eq!(ExecutionProcessLogs::line_count(pool, id).await.unwrap(), 6);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn lines_are_read_from_the_middle_of_a_chunk() {
+    let app = TestApp::new().await;
+    let id = process_with_chunks(&app, &["0\n1\n2\n3\n", "4\n5\n6\n"]).await;
+    let pool = &app.deployment.db().pool;
+
+    let lines = |range| ExecutionProcessLogs::find_lines(pool, id, range);
+    assert_eq!(lines(1..3).await.unwrap().unwrap().logs, "1\n2\n");
+    assert_eq!(
+        lines(2..i64::MAX).await.unwrap().unwrap().logs,
+        "2\n3\n4\n5\n6\n"
+    );
+    assert_eq!(lines(5..6).await.unwrap().unwrap().logs, "5\n");
+    assert_eq!(lines(7..10).await.unwrap().unwrap().logs, "");
+}
+
+/// A new execution process of a finished attempt, with `chunks` appended as its logs
+async fn process_with_chunks(app: &TestApp, chunks: &[&str]) -> Uuid {
+    let task_id = app.create_task(None).await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+    let agent = app.coding_agent(attempt.id).await;
+    let pool = &app.deployment.db().pool;
+    let process = ExecutionProcess::create(
+        pool,
+        &CreateExecutionProcess {
+            task_attempt_id: attempt.id,
+            executor_action: agent.executor_action().unwrap().clone(),
+            run_reason: agent.run_reason.clone(),
+        },
+        Uuid::new_v4(),
+        ExecutionProcessStatus::Completed,
+    )
+    .await
+    .unwrap();
+    for chunk in chunks {
+        ExecutionProcessLogs::append_log_lines(pool, process.id, chunk)
+            .await
+            .unwrap();
+    }
+    process.id
+}
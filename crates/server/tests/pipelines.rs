@@ -0,0 +1,253 @@
+//! What runs after a coding agent: code review, the test script and pipeline steps
+
+mod common;
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use axum::http::Method;
+use common::TestApp;
+use db::models::{
+    execution_process::{ExecutionProcessRunReason, ExecutionProcessStatus},
+    task::{Task, TaskStatus},
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use executors::actions::ExecutorActionType;
+use serde_json::{json, Value};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn review_profile_reviews_agent_changes() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({
+            "review_profile": { "profile": "fake-review", "variant": null },
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+
+    let processes = app.wait_for_attempt(attempt.id).await;
+    let run_reasons: Vec<_> = processes.iter().map(|p| p.run_reason.clone()).collect();
+    assert_eq!(
+        run_reasons,
+        vec![
+            ExecutionProcessRunReason::CodingAgent,
+            ExecutionProcessRunReason::CodeReview
+        ]
+    );
+    let ExecutorActionType::CodingAgentInitialRequest(request) =
+        processes[1].executor_action().unwrap().typ()
+    else {
+        panic!("Review did not start a coding agent");
+    };
+    assert!(request.prompt.contains("+Hello from the fake agent"));
+
+    // What the reviewer wrote is dropped rather than committed to the branch
+    let attempt = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let worktree = PathBuf::from(attempt.container_ref.unwrap());
+    assert_eq!(
+        fs::read_to_string(worktree.join("hello.txt")).unwrap(),
+        "Hello from the fake agent\n"
+    );
+    assert!(!worktree.join("review-notes.txt").exists());
+    let repo = git2::Repository::open(&worktree).unwrap();
+    assert!(repo.statuses(None).unwrap().is_empty());
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let tree = head.tree().unwrap();
+    assert!(tree.get_name("review-notes.txt").is_none());
+
+    // The findings are stored once the review's logs have been read to the end
+    let uri = format!("/api/task-attempts/{}/review-comments", attempt.id);
+    let mut comments = Value::Null;
+    for _ in 0..50 {
+        comments = app.request(Method::GET, &uri, None).await;
+        if !comments.as_array().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(comments.as_array().unwrap().len(), 1);
+    assert_eq!(comments[0]["execution_process_id"], json!(processes[1].id));
+    assert_eq!(comments[0]["file_path"], "hello.txt");
+    assert_eq!(comments[0]["line"], 1);
+    assert_eq!(comments[0]["severity"], "warning");
+    assert_eq!(comments[0]["body"], "End the greeting with a full stop");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unknown_review_profile_is_rejected() {
+    let app = TestApp::new().await;
+    let (_, body) = app
+        .send(
+            Method::POST,
+            "/api/projects",
+            Some(json!({
+                "name": "Fake agent project",
+                "git_repo_path": app.repo.path(),
+                "use_existing_repo": true,
+                "setup_script": null,
+                "dev_servers": null,
+                "cleanup_script": null,
+                "copy_files": null,
+                "review_profile": { "profile": "missing", "variant": null },
+            })),
+        )
+        .await;
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["success"], false);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("Profile not found: missing"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failing_tests_are_sent_back_to_the_agent() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({
+            "test_script": "echo 'greeting test FAILED'; exit 1",
+            "max_fix_iterations": 1,
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+
+    let processes = app.wait_for_attempt(attempt.id).await;
+    let steps: Vec<_> = processes
+        .iter()
+        .map(|p| (p.run_reason.clone(), p.status.clone()))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![
+            (
+                ExecutionProcessRunReason::CodingAgent,
+                ExecutionProcessStatus::Completed
+            ),
+            (
+                ExecutionProcessRunReason::TestScript,
+                ExecutionProcessStatus::Failed
+            ),
+            (
+                ExecutionProcessRunReason::CodingAgent,
+                ExecutionProcessStatus::Completed
+            ),
+            (
+                ExecutionProcessRunReason::TestScript,
+                ExecutionProcessStatus::Failed
+            ),
+        ]
+    );
+    let ExecutorActionType::CodingAgentFollowUpRequest(fix) =
+        processes[2].executor_action().unwrap().typ()
+    else {
+        panic!("Tests weren't sent back as a follow-up");
+    };
+    assert!(fix.prompt.contains("greeting test FAILED"));
+
+    // Out of fix iterations, the attempt is left for review
+    let task = Task::find_by_id(&app.deployment.db().pool, task_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(task.status, TaskStatus::InReview);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pipeline_steps_run_as_their_own_processes() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({
+            "pipeline": [
+                {
+                    "name": "format",
+                    "action": { "type": "Script", "script": "echo formatted > formatted.txt" },
+                },
+                {
+                    "name": "lint",
+                    "action": { "type": "Script", "script": "echo 'lint failed'; exit 1" },
+                    "continue_on_failure": true,
+                },
+                {
+                    "name": "fix",
+                    "action": { "type": "AgentFollowUp", "prompt": "Fix the lint errors" },
+                },
+                {
+                    "name": "check",
+                    "action": { "type": "Script", "script": "exit 1" },
+                },
+            ],
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+
+    let processes = app.wait_for_attempt(attempt.id).await;
+    let steps: Vec<_> = processes
+        .iter()
+        .map(|p| {
+            (
+                p.run_reason.clone(),
+                p.executor_action()
+                    .unwrap()
+                    .pipeline_step()
+                    .map(|step| step.name.clone()),
+                p.status.clone(),
+            )
+        })
+        .collect();
+    let step =
+        |run_reason, name: Option<&str>, status| (run_reason, name.map(String::from), status);
+    assert_eq!(
+        steps,
+        vec![
+            step(
+                ExecutionProcessRunReason::CodingAgent,
+                None,
+                ExecutionProcessStatus::Completed
+            ),
+            step(
+                ExecutionProcessRunReason::PipelineStep,
+                Some("format"),
+                ExecutionProcessStatus::Completed
+            ),
+            // Allowed to fail, so the pipeline goes on
+            step(
+                ExecutionProcessRunReason::PipelineStep,
+                Some("lint"),
+                ExecutionProcessStatus::Failed
+            ),
+            step(
+                ExecutionProcessRunReason::CodingAgent,
+                Some("fix"),
+                ExecutionProcessStatus::Completed
+            ),
+            step(
+                ExecutionProcessRunReason::PipelineStep,
+                Some("check"),
+                ExecutionProcessStatus::Failed
+            ),
+        ]
+    );
+    let ExecutorActionType::CodingAgentFollowUpRequest(fix) =
+        processes[3].executor_action().unwrap().typ()
+    else {
+        panic!("The agent step wasn't a follow-up");
+    };
+    assert_eq!(fix.prompt, "Fix the lint errors");
+
+    // The step's changes are committed, and the failed step stops the pipeline for review
+    let attempt = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let repo = git2::Repository::open(attempt.container_ref.as_deref().unwrap()).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert!(head.tree().unwrap().get_name("formatted.txt").is_some());
+    let task = Task::find_by_id(&app.deployment.db().pool, task_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(task.status, TaskStatus::InReview);
+}
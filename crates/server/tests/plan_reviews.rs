@@ -0,0 +1,127 @@
+//! Plan-mode attempts waiting for the plan to be approved
+
+mod common;
+
+use axum::http::Method;
+use common::TestApp;
+use db::models::execution_process::{ExecutionProcessRunReason, ExecutionProcessStatus};
+use deployment::Deployment;
+use executors::actions::ExecutorActionType;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn approved_plan_starts_follow_up() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempt = app
+        .start_attempt_with_variant(task_id, "fake-plan", Some("plan"))
+        .await;
+    app.wait_for_attempt(attempt.id).await;
+
+    // The presented plan waits for approval, and the task shows it on the board
+    let plan_review = app
+        .request(
+            Method::GET,
+            &format!("/api/task-attempts/{}/plan-review", attempt.id),
+            None,
+        )
+        .await;
+    assert_eq!(plan_review["status"], "pending");
+    assert_eq!(plan_review["plan"], "1. Add hello.txt with a greeting");
+    let task = app
+        .request(Method::GET, &format!("/api/tasks/{task_id}"), None)
+        .await;
+    let tasks = app
+        .request(
+            Method::GET,
+            &format!(
+                "/api/tasks?project_id={}",
+                task["project_id"].as_str().unwrap()
+            ),
+            None,
+        )
+        .await;
+    assert_eq!(tasks[0]["awaiting_plan_approval"], true);
+
+    // Approving the edited plan continues the session with the profile's default variant
+    let plan_review = app
+        .request(
+            Method::POST,
+            &format!(
+                "/api/plan-reviews/{}/respond",
+                plan_review["id"].as_str().unwrap()
+            ),
+            Some(json!({ "approved": true, "plan": "Add hello.txt" })),
+        )
+        .await;
+    assert_eq!(plan_review["status"], "approved");
+    assert_eq!(plan_review["plan"], "Add hello.txt");
+
+    let processes = app.wait_for_attempt(attempt.id).await;
+    let agents: Vec<_> = processes
+        .iter()
+        .filter(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+        .collect();
+    assert_eq!(agents.len(), 2);
+    let follow_up = agents[1].executor_action().unwrap();
+    let ExecutorActionType::CodingAgentFollowUpRequest(request) = follow_up.typ() else {
+        panic!("Expected a follow-up, got {follow_up:?}");
+    };
+    assert_eq!(request.prompt, "Add hello.txt");
+    assert_eq!(request.session_id, "fake-session-plan");
+    assert_eq!(request.profile_variant_label.variant, None);
+    assert_eq!(agents[1].status, ExecutionProcessStatus::Completed);
+
+    let tasks = app
+        .request(
+            Method::GET,
+            &format!(
+                "/api/tasks?project_id={}",
+                task["project_id"].as_str().unwrap()
+            ),
+            None,
+        )
+        .await;
+    assert_eq!(tasks[0]["awaiting_plan_approval"], false);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn plan_stays_pending_when_follow_up_cannot_start() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let attempt = app
+        .start_attempt_with_variant(task_id, "fake-plan", Some("plan"))
+        .await;
+    app.wait_for_attempt(attempt.id).await;
+    let plan_review = app
+        .request(
+            Method::GET,
+            &format!("/api/task-attempts/{}/plan-review", attempt.id),
+            None,
+        )
+        .await;
+
+    // Without a session to continue, the follow-up fails to start
+    sqlx::query("UPDATE executor_sessions SET session_id = NULL")
+        .execute(&app.deployment.db().pool)
+        .await
+        .unwrap();
+    let uri = format!(
+        "/api/plan-reviews/{}/respond",
+        plan_review["id"].as_str().unwrap()
+    );
+    let (status, _) = app
+        .send(Method::POST, &uri, Some(json!({ "approved": true })))
+        .await;
+    assert!(!status.is_success());
+
+    let plan_review = app
+        .request(
+            Method::GET,
+            &format!("/api/task-attempts/{}/plan-review", attempt.id),
+            None,
+        )
+        .await;
+    assert_eq!(plan_review["status"], "pending");
+    assert!(plan_review["responded_at"].is_null());
+}
@@ -0,0 +1,302 @@
+//! When agents run: concurrency limits, task dependencies and resource limits
+
+mod common;
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use axum::http::{Method, StatusCode};
+use common::TestApp;
+use db::models::{
+    execution_process::{
+        ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+        ExecutionProcessStopReason,
+    },
+    task::Task,
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use serde_json::{json, Value};
+use services::services::container::ContainerService;
+use uuid::Uuid;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn agents_past_the_project_limit_are_queued() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({ "max_concurrent_agents": 1 }))
+        .await;
+    let task = app
+        .request(Method::GET, &format!("/api/tasks/{task_id}"), None)
+        .await;
+    let mut task_ids = vec![task_id];
+    for _ in 0..2 {
+        let task = app
+            .request(
+                Method::POST,
+                "/api/tasks",
+                Some(json!({
+                    "project_id": task["project_id"],
+                    "title": "Add a greeting",
+                    "description": null,
+                    "parent_task_attempt": null,
+                })),
+            )
+            .await;
+        task_ids.push(task["id"].as_str().unwrap().parse().unwrap());
+    }
+
+    let running = app.start_attempt(task_ids[0], "fake-slow").await;
+    let cancelled = app.start_attempt(task_ids[1], "fake-success").await;
+    let queued = app.start_attempt(task_ids[2], "fake-success").await;
+    assert_eq!(
+        app.current_coding_agent(running.id).await.status,
+        ExecutionProcessStatus::Running
+    );
+    let cancelled_agent = app.current_coding_agent(cancelled.id).await;
+    assert_eq!(cancelled_agent.status, ExecutionProcessStatus::Queued);
+    assert_eq!(cancelled_agent.queue_position, Some(1));
+    assert_eq!(
+        app.current_coding_agent(queued.id).await.queue_position,
+        Some(2)
+    );
+
+    // Stopping a queued attempt takes it out of the queue
+    let cancelled = TaskAttempt::find_by_id(&app.deployment.db().pool, cancelled.id)
+        .await
+        .unwrap()
+        .unwrap();
+    app.deployment.container().try_stop(&cancelled).await;
+    let cancelled_agent = app.current_coding_agent(cancelled.id).await;
+    assert_eq!(cancelled_agent.status, ExecutionProcessStatus::Killed);
+    assert_eq!(cancelled_agent.queue_position, None);
+    assert_eq!(
+        app.current_coding_agent(queued.id).await.queue_position,
+        Some(1)
+    );
+
+    // The queued agent runs once the running one is done
+    let running = TaskAttempt::find_by_id(&app.deployment.db().pool, running.id)
+        .await
+        .unwrap()
+        .unwrap();
+    app.deployment.container().try_stop(&running).await;
+    let queued_agent = app.coding_agent(queued.id).await;
+    assert_eq!(queued_agent.status, ExecutionProcessStatus::Completed);
+    assert_eq!(queued_agent.queue_position, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blocked_task_starts_once_its_blocker_is_done() {
+    let app = TestApp::new().await;
+    let blocker_id = app.create_task(None).await;
+    let blocker = app
+        .request(Method::GET, &format!("/api/tasks/{blocker_id}"), None)
+        .await;
+    let blocked = app
+        .request(
+            Method::POST,
+            "/api/tasks",
+            Some(json!({
+                "project_id": blocker["project_id"],
+                "title": "Use the greeting",
+                "description": null,
+                "parent_task_attempt": null,
+                "auto_start_profile": { "profile": "fake-success", "variant": null },
+            })),
+        )
+        .await;
+    let blocked_id: Uuid = blocked["id"].as_str().unwrap().parse().unwrap();
+
+    app.request(
+        Method::POST,
+        &format!("/api/tasks/{blocked_id}/dependencies"),
+        Some(json!({ "blocked_by_task_id": blocker_id })),
+    )
+    .await;
+    // The reverse edge would make the tasks wait on each other
+    let cycle: Value = serde_json::from_slice(
+        &app.request_raw(
+            Method::POST,
+            &format!("/api/tasks/{blocker_id}/dependencies"),
+            Some(json!({ "blocked_by_task_id": blocked_id })),
+        )
+        .await,
+    )
+    .unwrap();
+    assert_eq!(cycle["success"], false);
+
+    let pool = &app.deployment.db().pool;
+    app.deployment
+        .container()
+        .start_unblocked_tasks()
+        .await
+        .unwrap();
+    assert!(TaskAttempt::fetch_all(pool, Some(blocked_id))
+        .await
+        .unwrap()
+        .is_empty());
+
+    app.request(
+        Method::PUT,
+        &format!("/api/tasks/{blocker_id}"),
+        Some(json!({
+            "title": null,
+            "description": null,
+            "status": "done",
+            "parent_task_attempt": null,
+        })),
+    )
+    .await;
+    app.deployment
+        .container()
+        .start_unblocked_tasks()
+        .await
+        .unwrap();
+    // The background monitor may have claimed the task first and still be starting it
+    let mut attempts = vec![];
+    for _ in 0..100 {
+        attempts = TaskAttempt::fetch_all(pool, Some(blocked_id))
+            .await
+            .unwrap();
+        if !attempts.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(attempts.len(), 1);
+    let agent = app.coding_agent(attempts[0].id).await;
+    assert_eq!(agent.status, ExecutionProcessStatus::Completed);
+
+    // Auto-start happens once
+    let blocked = Task::find_by_id(pool, blocked_id).await.unwrap().unwrap();
+    assert!(blocked.auto_start_profile.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unknown_auto_start_profile_is_rejected() {
+    let app = TestApp::new().await;
+    let task_id = app.create_task(None).await;
+    let unknown = json!({ "profile": "fake-success", "variant": "missing" });
+
+    let (status, _) = app
+        .send(
+            Method::PUT,
+            &format!("/api/tasks/{task_id}/auto-start"),
+            Some(json!({ "profile_variant_label": unknown })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let task = Task::find_by_id(&app.deployment.db().pool, task_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(task.auto_start_profile.is_none());
+
+    let (status, _) = app
+        .send(
+            Method::POST,
+            "/api/tasks",
+            Some(json!({
+                "project_id": task.project_id,
+                "title": "Never starts",
+                "description": null,
+                "parent_task_attempt": null,
+                "auto_start_profile": unknown,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resource_limits_apply_and_running_agent_reports_stats() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({
+            "setup_script": "ulimit -t > limits.txt",
+            "max_cpu_secs": 600,
+            "max_memory_mb": 4096,
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-slow").await;
+
+    let mut agent = None;
+    for _ in 0..100 {
+        let processes =
+            ExecutionProcess::find_by_task_attempt_id(&app.deployment.db().pool, attempt.id)
+                .await
+                .unwrap();
+        agent = processes.into_iter().find(|p| {
+            p.run_reason == ExecutionProcessRunReason::CodingAgent
+                && p.status == ExecutionProcessStatus::Running
+        });
+        if agent.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let agent = agent.expect("Coding agent did not start");
+
+    let stats = app
+        .request(
+            Method::GET,
+            &format!("/api/execution-processes/{}/stats", agent.id),
+            None,
+        )
+        .await;
+    assert!(stats["process_count"].as_u64().unwrap() >= 1);
+    assert!(stats["rss_bytes"].as_u64().unwrap() > 0);
+
+    let attempt = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let worktree = PathBuf::from(attempt.container_ref.clone().unwrap());
+    assert_eq!(
+        fs::read_to_string(worktree.join("limits.txt")).unwrap(),
+        "600\n"
+    );
+
+    // Stats are only reported while the process runs
+    app.deployment.container().try_stop(&attempt).await;
+    app.coding_agent(attempt.id).await;
+    let stats = app
+        .request(
+            Method::GET,
+            &format!("/api/execution-processes/{}/stats", agent.id),
+            None,
+        )
+        .await;
+    assert!(stats.is_null());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resource_limits_hold_for_the_processes_of_an_execution_together() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({
+            "setup_script": "sleep 30 & sleep 30 & sleep 30 & wait",
+            "max_processes": 2,
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+
+    let mut setup = None;
+    for _ in 0..100 {
+        let processes =
+            ExecutionProcess::find_by_task_attempt_id(&app.deployment.db().pool, attempt.id)
+                .await
+                .unwrap();
+        if processes[0].status != ExecutionProcessStatus::Running {
+            setup = Some(processes.into_iter().next().unwrap());
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let setup = setup.expect("The setup script wasn't stopped");
+    assert_eq!(setup.status, ExecutionProcessStatus::Killed);
+    assert_eq!(
+        setup.stop_reason,
+        Some(ExecutionProcessStopReason::MaxProcesses)
+    );
+}
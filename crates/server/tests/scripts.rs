@@ -0,0 +1,93 @@
+//! Project scripts: language, working directory, environment and timeout
+
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use common::TestApp;
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus, ExecutionProcessStopReason},
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scripts_run_in_the_project_script_language_and_directory() {
+    let app = TestApp::new().await;
+    fs::create_dir(app.repo.path().join("docs")).unwrap();
+    fs::write(app.repo.path().join("docs/index.md"), "# Docs\n").unwrap();
+    let repo = git2::Repository::open(app.repo.path()).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("docs/index.md")).unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = repo.signature().unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "Docs",
+        &tree,
+        &[&parent],
+    )
+    .unwrap();
+
+    let task_id = app
+        .create_task_with(json!({
+            "setup_script": "import os\nwith open('setup.txt', 'w') as f:\n    f.write(os.path.basename(os.getcwd()) + ' ' + os.environ['SETUP_GREETING'])",
+            "script_language": "Python",
+            "script_working_dir": "docs",
+            "env_vars": { "SETUP_GREETING": "hello" },
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+
+    let agent = app.coding_agent(attempt.id).await;
+    assert_eq!(agent.status, ExecutionProcessStatus::Completed);
+    let attempt = TaskAttempt::find_by_id(&app.deployment.db().pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let worktree = PathBuf::from(attempt.container_ref.unwrap());
+    assert_eq!(
+        fs::read_to_string(worktree.join("docs/setup.txt")).unwrap(),
+        "docs hello"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scripts_are_stopped_after_their_timeout() {
+    let app = TestApp::new().await;
+    let task_id = app
+        .create_task_with(json!({
+            "setup_script": "sleep 30",
+            "script_timeout_secs": 1,
+        }))
+        .await;
+    let attempt = app.start_attempt(task_id, "fake-success").await;
+
+    let mut setup = None;
+    for _ in 0..100 {
+        let processes =
+            ExecutionProcess::find_by_task_attempt_id(&app.deployment.db().pool, attempt.id)
+                .await
+                .unwrap();
+        if processes[0].status != ExecutionProcessStatus::Running {
+            setup = Some(processes);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let processes = setup.expect("The setup script wasn't stopped");
+    assert_eq!(processes.len(), 1);
+    assert_eq!(processes[0].status, ExecutionProcessStatus::Killed);
+    assert_eq!(
+        processes[0].stop_reason,
+        Some(ExecutionProcessStopReason::MaxDuration)
+    );
+}
@@ -0,0 +1,188 @@
+//! Automated review of an attempt's changes by a second coding agent: the prompt it gets, and
+//! the structured comments read back from its final answer
+
+use std::fmt::Write;
+
+use db::models::{
+    review_comment::{CreateReviewComment, ReviewCommentSeverity},
+    task::Task,
+};
+use serde::Deserialize;
+use utils::diff::{Diff, create_unified_diff};
+
+/// The diff is cut off past this size so the prompt fits the reviewer's context window
+const MAX_DIFF_CHARS: usize = 100_000;
+
+/// Ask for a review of the changes made for `task`, with findings as a JSON code block
+pub fn review_prompt(task: &Task, diffs: &[Diff]) -> String {
+    let mut diff = String::new();
+    for file in diffs {
+        let path = file
+            .new_file
+            .as_ref()
+            .or(file.old_file.as_ref())
+            .and_then(|f| f.file_name.as_deref())
+            .unwrap_or_default();
+        let old = file.old_file.as_ref().and_then(|f| f.content.as_deref());
+        let new = file.new_file.as_ref().and_then(|f| f.content.as_deref());
+        if old.is_none() && new.is_none() {
+            let _ = writeln!(diff, "Binary file {path} changed");
+        } else {
+            diff.push_str(&create_unified_diff(
+                path,
+                old.unwrap_or_default(),
+                new.unwrap_or_default(),
+            ));
+        }
+    }
+    if diff.len() > MAX_DIFF_CHARS {
+        let mut end = MAX_DIFF_CHARS;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        diff.truncate(end);
+        diff.push_str("\n... (diff truncated)\n");
+    }
+
+    format!(
+        "Review the changes another coding agent made for the task below. Look for bugs, \
+         missed requirements and risky code. Do not modify any files.\n\n\
+         ## Task\n\n{}\n\n\
+         ## Changes\n\n```diff\n{}```\n\n\
+         ## Findings\n\n\
+         End your reply with your findings as a JSON array in a ```json code block. Each \
+         finding is an object with \"file\" (path relative to the repository root, or null), \
+         \"line\" (line number in the changed file, or null), \"severity\" (\"info\", \
+         \"warning\" or \"error\") and \"comment\". Use an empty array if there is nothing to \
+         report.",
+        task.to_prompt(),
+        diff
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct Finding {
+    #[serde(default, alias = "path")]
+    file: Option<String>,
+    #[serde(default)]
+    line: Option<i64>,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(alias = "body", alias = "message")]
+    comment: String,
+}
+
+impl From<Finding> for CreateReviewComment {
+    fn from(finding: Finding) -> Self {
+        let severity = match finding
+            .severity
+            .as_deref()
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("error" | "critical" | "high") => ReviewCommentSeverity::Error,
+            Some("warning" | "warn" | "medium") => ReviewCommentSeverity::Warning,
+            _ => ReviewCommentSeverity::Info,
+        };
+        Self {
+            file_path: finding.file.filter(|f| !f.is_empty()),
+            line: finding.line,
+            severity,
+            body: finding.comment,
+        }
+    }
+}
+
+/// Read the findings from the reviewer's final message. A reply without a parseable JSON
+/// block is kept as a single comment, so the review isn't lost.
+pub fn parse_review_comments(message: &str) -> Vec<CreateReviewComment> {
+    let findings = message.rfind("```json").and_then(|start| {
+        let block = &message[start + "```json".len()..];
+        let block = &block[..block.find("```").unwrap_or(block.len())];
+        serde_json::from_str::<Vec<Finding>>(block).ok()
+    });
+
+    match findings {
+        Some(findings) => findings.into_iter().map(Into::into).collect(),
+        None if message.trim().is_empty() => vec![],
+        None => vec![CreateReviewComment {
+            file_path: None,
+            line: None,
+            severity: ReviewCommentSeverity::Info,
+            body: message.trim().to_string(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::task::TaskStatus;
+    use utils::diff::FileDiffDetails;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn prompt_contains_task_and_diff() {
+        let task = Task {
+            id: Uuid::nil(),
+            project_id: Uuid::nil(),
+            title: "Add a greeting".to_string(),
+            description: None,
+            status: TaskStatus::InProgress,
+            parent_task_attempt: None,
+            auto_start_profile: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let diffs = vec![Diff {
+            old_file: None,
+            new_file: Some(FileDiffDetails {
+                file_name: Some("hello.txt".to_string()),
+                content: Some("Hello\n".to_string()),
+            }),
+            hunks: vec![],
+        }];
+
+        let prompt = review_prompt(&task, &diffs);
+        assert!(prompt.contains("## Task\n\nAdd a greeting"));
+        assert!(prompt.contains("+++ b/hello.txt\n"));
+        assert!(prompt.contains("+Hello\n"));
+    }
+
+    #[test]
+    fn parses_findings_from_last_json_block() {
+        let message = "Looks mostly fine.\n\n```json\n[\
+            {\"file\": \"src/main.rs\", \"line\": 3, \"severity\": \"ERROR\", \"comment\": \"Panics on empty input\"},\
+            {\"file\": null, \"line\": null, \"severity\": \"nit\", \"comment\": \"Add a test\"}\
+            ]\n```";
+
+        assert_eq!(
+            parse_review_comments(message),
+            vec![
+                CreateReviewComment {
+                    file_path: Some("src/main.rs".to_string()),
+                    line: Some(3),
+                    severity: ReviewCommentSeverity::Error,
+                    body: "Panics on empty input".to_string(),
+                },
+                CreateReviewComment {
+                    file_path: None,
+                    line: None,
+                    severity: ReviewCommentSeverity::Info,
+                    body: "Add a test".to_string(),
+                },
+            ]
+        );
+        assert!(parse_review_comments("All good.\n```json\n[]\n```").is_empty());
+    }
+
+    #[test]
+    fn unstructured_reply_becomes_one_comment() {
+        let comments = parse_review_comments("  The loop never terminates.\n");
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].body, "The loop never terminates.");
+        assert_eq!(comments[0].severity, ReviewCommentSeverity::Info);
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
+    ops::Range,
     path::PathBuf,
     sync::{
         Arc,
@@ -18,34 +19,82 @@ use db::{
             ExecutionProcessStatus,
         },
         execution_process_logs::ExecutionProcessLogs,
+        execution_process_normalized_entries::ExecutionProcessNormalizedEntries,
+        execution_process_token_usage::ExecutionProcessTokenUsage,
         executor_session::{CreateExecutorSession, ExecutorSession},
+        project::Project,
         task::{Task, TaskStatus},
-        task_attempt::{TaskAttempt, TaskAttemptError},
+        task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
+        task_dependency::TaskDependency,
     },
 };
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
+        coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
     executors::{CodingAgent, ExecutorError, StandardCodingAgentExecutor},
-    logs::utils::patch::ConversationPatch,
-    profile::ProfileVariantLabel,
+    logs::{NormalizedConversation, NormalizedEntry, utils::patch::ConversationPatch},
+    profile::{ProfileConfigs, ProfileVariantLabel},
 };
-use futures::{StreamExt, TryStreamExt, future};
+use futures::{FutureExt, StreamExt, TryStreamExt, future};
+use serde::Deserialize;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
-use tokio::{sync::RwLock, task::JoinHandle};
-use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+};
+use utils::{
+    log_msg::{EV_ENTRIES_START, LogMsg},
+    msg_store::MsgStore,
+    process_stats::ProcessStats,
+};
 use uuid::Uuid;
 
 use crate::services::{
-    git::{GitService, GitServiceError},
+    code_review,
+    git::{DiffTarget, GitService, GitServiceError},
+    pipeline, test_fix,
+    transcript::{AgentRunTranscript, AttemptTranscript},
     worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
 
+/// JSONL bytes of logs gathered before they are stored as a chunk, even if more is ready
+const LOG_CHUNK_MAX_BYTES: usize = 64 * 1024;
+
+/// A window of the entries of an execution's logs, so long logs can be loaded from the end
+/// a page at a time
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct LogRange {
+    /// Only entries before this index
+    pub before: Option<usize>,
+    /// Only this many entries, the latest of the rest
+    pub limit: Option<usize>,
+}
+
+impl LogRange {
+    /// Whether the window reaches the end of the logs, so it follows new entries
+    pub fn follows(&self) -> bool {
+        self.before.is_none()
+    }
+
+    /// Whether the whole log is asked for
+    pub fn is_unbounded(&self) -> bool {
+        self.before.is_none() && self.limit.is_none()
+    }
+
+    /// The entries in the window, out of `total`
+    pub fn window(&self, total: usize) -> Range<usize> {
+        let end = self.before.map_or(total, |before| before.min(total));
+        let start = self.limit.map_or(0, |limit| end.saturating_sub(limit));
+        start..end
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -62,6 +111,8 @@ pub enum ContainerError {
     KillFailed(std::io::Error),
     #[error(transparent)]
     TaskAttemptError(#[from] TaskAttemptError),
+    #[error("{0}")]
+    NotAcceptingMessages(&'static str),
     #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
@@ -74,6 +125,13 @@ pub trait ContainerService {
 
     fn git(&self) -> &GitService;
 
+    /// Held while deciding whether coding agents start or wait in the queue, so concurrent
+    /// starts can't exceed the concurrency limits
+    fn execution_queue_lock(&self) -> &Arc<Mutex<()>>;
+
+    /// Coding agents that may run at once across all projects
+    async fn max_concurrent_agents(&self) -> Option<u32>;
+
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf;
 
     async fn create(&self, task_attempt: &TaskAttempt) -> Result<ContainerRef, ContainerError>;
@@ -89,7 +147,10 @@ pub trait ContainerService {
             ExecutionProcess::find_by_task_attempt_id(&self.db().pool, task_attempt.id).await
         {
             for process in processes {
-                if process.status == ExecutionProcessStatus::Running {
+                if matches!(
+                    process.status,
+                    ExecutionProcessStatus::Running | ExecutionProcessStatus::Queued
+                ) {
                     self.stop_execution(&process).await.unwrap_or_else(|e| {
                         tracing::debug!(
                             "Failed to stop execution process {} for task attempt {}: {}",
@@ -122,6 +183,17 @@ pub trait ContainerService {
         execution_process: &ExecutionProcess,
     ) -> Result<(), ContainerError>;
 
+    /// Write a user message to a running coding agent that accepts input mid-execution
+    async fn send_message(
+        &self,
+        execution_process: &ExecutionProcess,
+        message: &str,
+    ) -> Result<(), ContainerError>;
+
+    /// Live resource usage of a running execution process and everything it started.
+    /// None once the process has exited.
+    async fn process_stats(&self, execution_process: &ExecutionProcess) -> Option<ProcessStats>;
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<(), ContainerError>;
 
     async fn copy_project_files(
@@ -142,216 +214,398 @@ pub trait ContainerService {
         map.get(uuid).cloned()
     }
 
+    /// Raw output of an execution as JSON patches, limited to a window of its entries. A
+    /// window that reaches the end of a running execution's output follows it live.
     async fn stream_raw_logs(
         &self,
         id: &Uuid,
+        range: LogRange,
     ) -> Option<futures::stream::BoxStream<'static, Result<Event, std::io::Error>>> {
+        let is_output = |m: &LogMsg| matches!(m, LogMsg::Stdout(_) | LogMsg::Stderr(_));
         if let Some(store) = self.get_msg_store_by_id(id).await {
+            let history: Vec<_> = store.get_history().into_iter().filter(is_output).collect();
+            let window = range.window(history.len());
+            if !range.follows() {
+                return Some(raw_entries_stream(window.start, history[window].to_vec()));
+            }
+
             // First try in-memory store
             let counter = Arc::new(AtomicUsize::new(0));
             return Some(
-                store
-                    .history_plus_stream()
-                    .filter(|msg| {
-                        future::ready(matches!(msg, Ok(LogMsg::Stdout(..) | LogMsg::Stderr(..))))
-                    })
-                    .map_ok({
-                        let counter = counter.clone();
-                        move |m| {
-                            let index = counter.fetch_add(1, Ordering::SeqCst);
-                            match m {
-                                LogMsg::Stdout(content) => {
-                                    let patch = ConversationPatch::add_stdout(index, content);
-                                    LogMsg::JsonPatch(patch).to_sse_event()
+                futures::stream::once(future::ready(Ok(entries_start_event(window.start))))
+                    .chain(
+                        store
+                            .history_plus_stream()
+                            .filter(|msg| {
+                                future::ready(matches!(
+                                    msg,
+                                    Ok(LogMsg::Stdout(..) | LogMsg::Stderr(..))
+                                ))
+                            })
+                            .skip(window.start)
+                            .map_ok({
+                                let counter = counter.clone();
+                                move |m| {
+                                    let index = counter.fetch_add(1, Ordering::SeqCst);
+                                    raw_entry_event(index, m)
                                 }
-                                LogMsg::Stderr(content) => {
-                                    let patch = ConversationPatch::add_stderr(index, content);
-                                    LogMsg::JsonPatch(patch).to_sse_event()
-                                }
-                                _ => unreachable!("Filter should only pass Stdout/Stderr"),
-                            }
-                        }
-                    })
+                            }),
+                    )
                     .boxed(),
             );
-        } else {
-            // Fallback: load from DB and create direct stream
-            let logs_record =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(Some(record)) => record,
-                    Ok(None) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
-
-            let messages = match logs_record.parse_logs() {
-                Ok(msgs) => msgs,
-                Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
-                    return None;
-                }
-            };
+        }
 
-            // Direct stream from parsed messages converted to JSON patches
-            let stream = futures::stream::iter(
-                messages
-                    .into_iter()
-                    .filter(|m| matches!(m, LogMsg::Stdout(_) | LogMsg::Stderr(_)))
-                    .enumerate()
-                    .map(|(index, m)| {
-                        let event = match m {
-                            LogMsg::Stdout(content) => {
-                                let patch = ConversationPatch::add_stdout(index, content);
-                                LogMsg::JsonPatch(patch).to_sse_event()
-                            }
-                            LogMsg::Stderr(content) => {
-                                let patch = ConversationPatch::add_stderr(index, content);
-                                LogMsg::JsonPatch(patch).to_sse_event()
-                            }
-                            _ => unreachable!("Filter should only pass Stdout/Stderr"),
-                        };
-                        Ok::<_, std::io::Error>(event)
-                    }),
-            )
-            .chain(futures::stream::once(async {
-                Ok::<_, std::io::Error>(LogMsg::Finished.to_sse_event())
-            }))
-            .boxed();
+        // Fallback: load the lines in the window from DB and create direct stream
+        let pool = &self.db().pool;
+        let total = match ExecutionProcessLogs::line_count(pool, *id).await {
+            Ok(0) => return None, // No logs exist
+            Ok(total) => total as usize,
+            Err(e) => {
+                tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
+                return None;
+            }
+        };
+        let window = range.window(total);
+        let lines = window.start as i64..window.end as i64;
+        let messages = match ExecutionProcessLogs::find_lines(pool, *id, lines).await {
+            Ok(record) => record.map(|record| record.parse_logs()).transpose(),
+            Err(e) => {
+                tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
+                return None;
+            }
+        };
+        let messages = match messages {
+            Ok(msgs) => msgs.unwrap_or_default(),
+            Err(e) => {
+                tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                return None;
+            }
+        };
 
-            Some(stream)
-        }
+        Some(raw_entries_stream(
+            window.start,
+            messages.into_iter().filter(is_output).collect(),
+        ))
     }
 
+    /// Normalized conversation of an execution as JSON patches, limited to a window of its
+    /// entries. A window that reaches the end of a running execution follows it live.
     async fn stream_normalized_logs(
         &self,
         id: &Uuid,
+        range: LogRange,
     ) -> Option<futures::stream::BoxStream<'static, Result<Event, std::io::Error>>> {
         // First try in-memory store (existing behavior)
-        if let Some(store) = self.get_msg_store_by_id(id).await {
-            Some(
-                store
-                    .history_plus_stream() // BoxStream<Result<LogMsg, io::Error>>
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .map_ok(|m| m.to_sse_event()) // LogMsg -> Event
+        if range.follows()
+            && let Some(store) = self.get_msg_store_by_id(id).await
+        {
+            let mut entries = BTreeMap::new();
+            for msg in store.get_history() {
+                if let LogMsg::JsonPatch(patch) = msg {
+                    ConversationPatch::apply_normalized_entries(&patch, &mut entries);
+                }
+            }
+            let total = entries.keys().next_back().map_or(0, |index| index + 1);
+            let start = range.window(total).start;
+            return Some(
+                futures::stream::once(future::ready(Ok(entries_start_event(start))))
+                    .chain(
+                        store
+                            .history_plus_stream() // BoxStream<Result<LogMsg, io::Error>>
+                            .filter_map(move |msg| {
+                                let patch = match msg {
+                                    Ok(LogMsg::JsonPatch(patch)) if start == 0 => Some(patch),
+                                    Ok(LogMsg::JsonPatch(patch)) => {
+                                        ConversationPatch::rebase(&patch, start)
+                                    }
+                                    _ => None,
+                                };
+                                future::ready(patch.map(|patch| {
+                                    Ok(LogMsg::JsonPatch(patch).to_sse_event()) // LogMsg -> Event
+                                }))
+                            }),
+                    )
                     .boxed(),
-            )
-        } else {
+            );
+        }
+
+        if range.is_unbounded() {
             // Fallback: load from DB and normalize
-            let logs_record =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(Some(record)) => record,
-                    Ok(None) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
+            let temp_store = self.normalize_logs_from_db(id).await?;
+            return Some(
+                futures::stream::once(future::ready(Ok(entries_start_event(0))))
+                    .chain(
+                        renormalized_patches(&temp_store)
+                            .map(|patch| Ok(LogMsg::JsonPatch(patch).to_sse_event())),
+                    )
+                    .chain(futures::stream::once(async {
+                        Ok::<_, std::io::Error>(LogMsg::Finished.to_sse_event())
+                    }))
+                    .boxed(),
+            );
+        }
+
+        // A window that ends before the latest entry is sent as it is now
+        let (start, entries) = self.normalized_entries_window(id, &range).await?;
+        let patches = entries.into_iter().enumerate().map(|(index, entry)| {
+            let patch = ConversationPatch::add_normalized_entry(index, entry);
+            Ok::<_, std::io::Error>(LogMsg::JsonPatch(patch).to_sse_event())
+        });
+        Some(
+            futures::stream::once(future::ready(Ok(entries_start_event(start))))
+                .chain(futures::stream::iter(patches))
+                .chain(futures::stream::once(async {
+                    Ok::<_, std::io::Error>(LogMsg::Finished.to_sse_event())
+                }))
+                .boxed(),
+        )
+    }
 
-            let raw_messages = match logs_record.parse_logs() {
-                Ok(msgs) => msgs,
+    /// Re-run the executor's normalizer over the raw logs stored for a finished execution
+    async fn normalize_logs_from_db(&self, id: &Uuid) -> Option<Arc<MsgStore>> {
+        let logs_record =
+            match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
+                Ok(Some(record)) => record,
+                Ok(None) => return None, // No logs exist
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
                     return None;
                 }
             };
 
-            // Create temporary store and populate
-            let temp_store = Arc::new(MsgStore::new());
-            for msg in raw_messages {
-                if matches!(msg, LogMsg::Stdout(_) | LogMsg::Stderr(_)) {
-                    temp_store.push(msg);
-                }
+        let raw_messages = match logs_record.parse_logs() {
+            Ok(msgs) => msgs,
+            Err(e) => {
+                tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                return None;
             }
-            temp_store.push_finished();
-
-            let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
-                Ok(Some(process)) => process,
-                Ok(None) => {
-                    tracing::error!("No execution process found for ID: {}", id);
-                    return None;
-                }
-                Err(e) => {
-                    tracing::error!("Failed to fetch execution process {}: {}", id, e);
-                    return None;
-                }
-            };
+        };
 
-            // Get the task attempt to determine correct directory
-            let task_attempt = match process.parent_task_attempt(&self.db().pool).await {
-                Ok(Some(task_attempt)) => task_attempt,
-                Ok(None) => {
-                    tracing::error!("No task attempt found for ID: {}", process.task_attempt_id);
-                    return None;
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "Failed to fetch task attempt {}: {}",
-                        process.task_attempt_id,
-                        e
-                    );
-                    return None;
-                }
-            };
+        // Create temporary store and populate
+        let temp_store = Arc::new(MsgStore::new());
+        for msg in raw_messages {
+            if matches!(msg, LogMsg::Stdout(_) | LogMsg::Stderr(_)) {
+                temp_store.push(msg);
+            }
+        }
+        temp_store.push_finished();
 
-            let current_dir = self.task_attempt_to_current_dir(&task_attempt);
+        let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
+            Ok(Some(process)) => process,
+            Ok(None) => {
+                tracing::error!("No execution process found for ID: {}", id);
+                return None;
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch execution process {}: {}", id, e);
+                return None;
+            }
+        };
 
-            let executor_action = if let Ok(executor_action) = process.executor_action() {
-                executor_action
-            } else {
+        // Get the task attempt to determine correct directory
+        let task_attempt = match process.parent_task_attempt(&self.db().pool).await {
+            Ok(Some(task_attempt)) => task_attempt,
+            Ok(None) => {
+                tracing::error!("No task attempt found for ID: {}", process.task_attempt_id);
+                return None;
+            }
+            Err(e) => {
                 tracing::error!(
-                    "Failed to parse executor action: {:?}",
+                    "Failed to fetch task attempt {}: {}",
+                    process.task_attempt_id,
+                    e
+                );
+                return None;
+            }
+        };
+
+        let current_dir = self.task_attempt_to_current_dir(&task_attempt);
+
+        let executor_action = if let Ok(executor_action) = process.executor_action() {
+            executor_action
+        } else {
+            tracing::error!(
+                "Failed to parse executor action: {:?}",
+                process.executor_action()
+            );
+            return None;
+        };
+
+        // Spawn normalizer on populated store
+        let profile_variant_label = match executor_action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                &request.profile_variant_label
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                &request.profile_variant_label
+            }
+            _ => {
+                tracing::debug!(
+                    "Executor action doesn't support log normalization: {:?}",
                     process.executor_action()
                 );
                 return None;
-            };
+            }
+        };
+        let normalizer = match CodingAgent::from_profile_variant_label(profile_variant_label) {
+            Ok(executor) => executor.normalize_logs(temp_store.clone(), &current_dir),
+            Err(_) => {
+                tracing::error!(
+                    "Failed to resolve profile '{:?}' for normalization",
+                    profile_variant_label
+                );
+                vec![]
+            }
+        };
+
+        // The raw logs end with the first `Finished`, the normalized ones with a second one
+        // pushed once the normalizer is done
+        let store = temp_store.clone();
+        tokio::spawn(async move {
+            future::join_all(normalizer).await;
+            store.push_finished();
+        });
+        Some(temp_store)
+    }
+
+    /// Snapshot of the normalized conversation of an execution. A finished execution's
+    /// conversation is normalized from its raw logs once and stored.
+    async fn normalized_entries(&self, id: &Uuid) -> Option<Vec<NormalizedEntry>> {
+        let mut entries = BTreeMap::new();
+        if let Some(store) = self.get_msg_store_by_id(id).await {
+            for msg in store.get_history() {
+                if let LogMsg::JsonPatch(patch) = msg {
+                    ConversationPatch::apply_normalized_entries(&patch, &mut entries);
+                }
+            }
+            return Some(entries.into_values().collect());
+        }
+
+        if let Some((_, stored)) = self
+            .stored_normalized_entries(id, &LogRange::default())
+            .await
+        {
+            return Some(stored);
+        }
+        let store = self.normalize_logs_from_db(id).await?;
+        let mut patches = renormalized_patches(&store);
+        while let Some(patch) = patches.next().await {
+            ConversationPatch::apply_normalized_entries(&patch, &mut entries);
+        }
+        let entries: Vec<_> = entries.into_values().collect();
+        if let Err(e) =
+            ExecutionProcessNormalizedEntries::replace(&self.db().pool, *id, &entries).await
+        {
+            tracing::error!("Failed to store normalized logs of execution {}: {}", id, e);
+        }
+        Some(entries)
+    }
+
+    /// A window of the normalized conversation of an execution, and the index of its first
+    /// entry. Once a finished execution's conversation is stored, only the window is read.
+    async fn normalized_entries_window(
+        &self,
+        id: &Uuid,
+        range: &LogRange,
+    ) -> Option<(usize, Vec<NormalizedEntry>)> {
+        if self.get_msg_store_by_id(id).await.is_none()
+            && let Some(window) = self.stored_normalized_entries(id, range).await
+        {
+            return Some(window);
+        }
+        let entries = self.normalized_entries(id).await?;
+        let window = range.window(entries.len());
+        Some((window.start, entries[window].to_vec()))
+    }
+
+    /// A window of the stored normalized conversation of an execution, and the index of its
+    /// first entry. Returns None if nothing is stored for it.
+    async fn stored_normalized_entries(
+        &self,
+        id: &Uuid,
+        range: &LogRange,
+    ) -> Option<(usize, Vec<NormalizedEntry>)> {
+        let pool = &self.db().pool;
+        let total = match ExecutionProcessNormalizedEntries::count(pool, *id).await {
+            Ok(0) => return None,
+            Ok(total) => total as usize,
+            Err(e) => {
+                tracing::error!("Failed to count normalized logs of execution {}: {}", id, e);
+                return None;
+            }
+        };
+        let window = range.window(total);
+        let entries = window.start as i64..window.end as i64;
+        match ExecutionProcessNormalizedEntries::find_range(pool, *id, entries).await {
+            Ok(entries) => Some((window.start, entries)),
+            Err(e) => {
+                tracing::error!("Failed to read normalized logs of execution {}: {}", id, e);
+                None
+            }
+        }
+    }
+
+    /// Collect the conversations of every coding agent run in an attempt, oldest first
+    async fn attempt_transcript(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<AttemptTranscript, ContainerError> {
+        let pool = &self.db().pool;
+        let task = task_attempt
+            .parent_task(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
 
-            // Spawn normalizer on populated store
-            match executor_action.typ() {
+        let mut runs = Vec::new();
+        for process in ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id).await? {
+            if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+                continue;
+            }
+            let profile_variant_label = match process.executor_action()?.typ() {
                 ExecutorActionType::CodingAgentInitialRequest(request) => {
-                    if let Ok(executor) =
-                        CodingAgent::from_profile_variant_label(&request.profile_variant_label)
-                    {
-                        executor.normalize_logs(temp_store.clone(), &current_dir);
-                    } else {
-                        tracing::error!(
-                            "Failed to resolve profile '{:?}' for normalization",
-                            request.profile_variant_label
-                        );
-                    }
+                    request.profile_variant_label.clone()
                 }
                 ExecutorActionType::CodingAgentFollowUpRequest(request) => {
-                    if let Ok(executor) =
-                        CodingAgent::from_profile_variant_label(&request.profile_variant_label)
-                    {
-                        executor.normalize_logs(temp_store.clone(), &current_dir);
-                    } else {
-                        tracing::error!(
-                            "Failed to resolve profile '{:?}' for normalization",
-                            request.profile_variant_label
-                        );
-                    }
+                    request.profile_variant_label.clone()
                 }
-                _ => {
-                    tracing::debug!(
-                        "Executor action doesn't support log normalization: {:?}",
-                        process.executor_action()
-                    );
-                    return None;
-                }
-            }
-            Some(
-                temp_store
-                    .history_plus_stream()
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .map_ok(|m| m.to_sse_event())
-                    .chain(futures::stream::once(async {
-                        Ok::<_, std::io::Error>(LogMsg::Finished.to_sse_event())
-                    }))
-                    .boxed(),
-            )
+                _ => continue,
+            };
+            let executor_type = match &profile_variant_label.variant {
+                Some(variant) => format!("{}/{variant}", profile_variant_label.profile),
+                None => profile_variant_label.profile,
+            };
+            let session = ExecutorSession::find_by_execution_process_id(pool, process.id).await?;
+            let entries = self
+                .normalized_entries(&process.id)
+                .await
+                .unwrap_or_default();
+
+            runs.push(AgentRunTranscript {
+                execution_process_id: process.id,
+                status: process.status,
+                exit_code: process.exit_code,
+                started_at: process.started_at,
+                completed_at: process.completed_at,
+                conversation: NormalizedConversation {
+                    entries,
+                    session_id: session.as_ref().and_then(|s| s.session_id.clone()),
+                    executor_type,
+                    prompt: session.as_ref().and_then(|s| s.prompt.clone()),
+                    summary: session.and_then(|s| s.summary),
+                },
+            });
         }
+
+        Ok(AttemptTranscript {
+            task_attempt_id: task_attempt.id,
+            task_title: task.title,
+            task_description: task.description,
+            profile: task_attempt.profile.clone(),
+            branch: task_attempt.branch.clone(),
+            base_branch: task_attempt.base_branch.clone(),
+            created_at: task_attempt.created_at,
+            runs,
+        })
     }
 
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
@@ -368,29 +622,33 @@ pub trait ContainerService {
 
             if let Some(store) = store {
                 let mut stream = store.history_plus_stream();
+                // Lines that are ready together are stored as one chunk
+                let mut chunk = String::new();
+
+                loop {
+                    let ready = if chunk.len() < LOG_CHUNK_MAX_BYTES {
+                        stream.next().now_or_never()
+                    } else {
+                        None
+                    };
+                    let next = match ready {
+                        Some(next) => next,
+                        None => {
+                            append_log_chunk(&db, execution_id, &mut chunk).await;
+                            stream.next().await
+                        }
+                    };
+                    let Some(Ok(msg)) = next else {
+                        break;
+                    };
 
-                while let Some(Ok(msg)) = stream.next().await {
                     match &msg {
                         LogMsg::Stdout(_) | LogMsg::Stderr(_) => {
                             // Serialize this individual message as a JSONL line
                             match serde_json::to_string(&msg) {
                                 Ok(jsonl_line) => {
-                                    let jsonl_line_with_newline = format!("{jsonl_line}\n");
-
-                                    // Append this line to the database
-                                    if let Err(e) = ExecutionProcessLogs::append_log_line(
-                                        &db.pool,
-                                        execution_id,
-                                        &jsonl_line_with_newline,
-                                    )
-                                    .await
-                                    {
-                                        tracing::error!(
-                                            "Failed to append log line for execution {}: {}",
-                                            execution_id,
-                                            e
-                                        );
-                                    }
+                                    chunk.push_str(&jsonl_line);
+                                    chunk.push('\n');
                                 }
                                 Err(e) => {
                                     tracing::error!(
@@ -418,12 +676,25 @@ pub trait ContainerService {
                                 );
                             }
                         }
+                        LogMsg::TokenUsage(usage) => {
+                            if let Err(e) =
+                                ExecutionProcessTokenUsage::record(&db.pool, execution_id, usage)
+                                    .await
+                            {
+                                tracing::error!(
+                                    "Failed to record token usage for execution process {}: {}",
+                                    execution_id,
+                                    e
+                                );
+                            }
+                        }
                         LogMsg::Finished => {
                             break;
                         }
                         LogMsg::JsonPatch(_) => continue,
                     }
                 }
+                append_log_chunk(&db, execution_id, &mut chunk).await;
             }
         })
     }
@@ -453,25 +724,21 @@ pub trait ContainerService {
             .await?
             .ok_or(SqlxError::RowNotFound)?;
 
-        let cleanup_action = project.cleanup_script.map(|script| {
+        let cleanup_action = project.cleanup_script.clone().map(|script| {
             Box::new(ExecutorAction::new(
-                ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script,
-                    language: ScriptRequestLanguage::Bash,
-                    context: ScriptContext::CleanupScript,
-                }),
+                ExecutorActionType::ScriptRequest(
+                    project.script_request(script, ScriptContext::CleanupScript),
+                ),
                 None,
             ))
         });
 
         // Choose whether to execute the setup_script or coding agent first
-        let execution_process = if let Some(setup_script) = project.setup_script {
+        let execution_process = if let Some(setup_script) = project.setup_script.clone() {
             let executor_action = ExecutorAction::new(
-                ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script: setup_script,
-                    language: ScriptRequestLanguage::Bash,
-                    context: ScriptContext::SetupScript,
-                }),
+                ExecutorActionType::ScriptRequest(
+                    project.script_request(setup_script, ScriptContext::SetupScript),
+                ),
                 // once the setup script is done, run the initial coding agent request
                 Some(Box::new(ExecutorAction::new(
                     ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
@@ -530,9 +797,24 @@ pub trait ContainerService {
             run_reason: run_reason.clone(),
         };
 
-        let execution_process =
-            ExecutionProcess::create(&self.db().pool, &create_execution_process, Uuid::new_v4())
-                .await?;
+        // Coding agents past the concurrency limits wait in the queue
+        let execution_process = {
+            let _guard = self.execution_queue_lock().lock().await;
+            let status = if run_reason.is_coding_agent()
+                && !self.has_free_agent_slot(task.project_id).await?
+            {
+                ExecutionProcessStatus::Queued
+            } else {
+                ExecutionProcessStatus::Running
+            };
+            ExecutionProcess::create(
+                &self.db().pool,
+                &create_execution_process,
+                Uuid::new_v4(),
+                status,
+            )
+            .await?
+        };
 
         if let Some(prompt) = match executor_action.typ() {
             ExecutorActionType::CodingAgentInitialRequest(coding_agent_request) => {
@@ -559,8 +841,32 @@ pub trait ContainerService {
             .await?;
         }
 
+        if execution_process.status == ExecutionProcessStatus::Queued {
+            tracing::info!(
+                "Queued execution process {} until a coding agent slot is free",
+                execution_process.id
+            );
+            // Sets its queue position
+            self.start_queued_executions().await?;
+            return ExecutionProcess::find_by_id(&self.db().pool, execution_process.id)
+                .await?
+                .ok_or(SqlxError::RowNotFound.into());
+        }
+
+        self.launch_execution(task_attempt, &execution_process, executor_action)
+            .await?;
+        Ok(execution_process)
+    }
+
+    /// Spawn the process of an execution whose record exists, and start processing its logs
+    async fn launch_execution(
+        &self,
+        task_attempt: &TaskAttempt,
+        execution_process: &ExecutionProcess,
+        executor_action: &ExecutorAction,
+    ) -> Result<(), ContainerError> {
         let _ = self
-            .start_execution_inner(task_attempt, &execution_process, executor_action)
+            .start_execution_inner(task_attempt, execution_process, executor_action)
             .await?;
 
         // Start processing normalised logs for executor requests and follow ups
@@ -603,7 +909,77 @@ pub trait ContainerService {
         };
 
         self.spawn_stream_raw_logs_to_db(&execution_process.id);
-        Ok(execution_process)
+        Ok(())
+    }
+
+    /// Whether another coding agent of the project may start without exceeding the global or
+    /// the project's concurrency limit
+    async fn has_free_agent_slot(&self, project_id: Uuid) -> Result<bool, ContainerError> {
+        let pool = &self.db().pool;
+        // A limit of 0 would never start anything, so it counts as unset
+        if let Some(limit) = self.max_concurrent_agents().await.filter(|l| *l > 0)
+            && ExecutionProcess::count_running_agents(pool).await? >= i64::from(limit)
+        {
+            return Ok(false);
+        }
+        let project_limit = Project::find_by_id(pool, project_id)
+            .await?
+            .and_then(|project| project.agent_limit())
+            .filter(|l| *l > 0);
+        if let Some(limit) = project_limit
+            && ExecutionProcess::count_running_agents_by_project(pool, project_id).await?
+                >= i64::try_from(limit).unwrap_or(i64::MAX)
+        {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Start queued coding agents while the concurrency limits allow, and update the queue
+    /// positions of the rest. Follow-ups and reviews of earlier runs go first, otherwise the
+    /// queue is first in, first out.
+    async fn start_queued_executions(&self) -> Result<(), ContainerError> {
+        let pool = &self.db().pool;
+        let mut to_launch = Vec::new();
+        {
+            let _guard = self.execution_queue_lock().lock().await;
+            let mut queued = ExecutionProcess::find_queued(pool).await?;
+            // Stable, so each group stays oldest first
+            queued.sort_by_key(|process| !continues_earlier_run(process));
+
+            let mut queue_position = 0;
+            for process in queued {
+                let ctx = ExecutionProcess::load_context(pool, process.id).await?;
+                if self.has_free_agent_slot(ctx.task.project_id).await?
+                    && ExecutionProcess::mark_started(pool, process.id).await?
+                {
+                    to_launch.push(ctx);
+                } else {
+                    queue_position += 1;
+                    ExecutionProcess::set_queue_position(pool, process.id, queue_position).await?;
+                }
+            }
+        }
+
+        for ctx in to_launch {
+            let id = ctx.execution_process.id;
+            let launched = match ctx.execution_process.executor_action() {
+                Ok(action) => {
+                    self.launch_execution(&ctx.task_attempt, &ctx.execution_process, action)
+                        .await
+                }
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = launched {
+                tracing::error!("Failed to start queued execution process {}: {}", id, e);
+                ExecutionProcess::update_completion(pool, id, ExecutionProcessStatus::Failed, None)
+                    .await?;
+                Task::update_status(pool, ctx.task.id, TaskStatus::InReview).await?;
+            } else {
+                tracing::info!("Started queued execution process {}", id);
+            }
+        }
+        Ok(())
     }
 
     async fn try_start_next_action(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
@@ -624,8 +1000,14 @@ pub trait ContainerService {
 
         // Determine the run reason of the next action
         let next_run_reason = match ctx.execution_process.run_reason {
+            // A fix for failing tests goes on to run them again
+            _ if test_fix::is_test_run(next_action) => ExecutionProcessRunReason::TestScript,
+            _ if next_action.pipeline_step().is_some() => pipeline::run_reason(next_action),
             ExecutionProcessRunReason::SetupScript => ExecutionProcessRunReason::CodingAgent,
-            ExecutionProcessRunReason::CodingAgent => ExecutionProcessRunReason::CleanupScript,
+            ExecutionProcessRunReason::CodingAgent
+            | ExecutionProcessRunReason::CodeReview
+            | ExecutionProcessRunReason::TestScript
+            | ExecutionProcessRunReason::PipelineStep => ExecutionProcessRunReason::CleanupScript,
             _ => {
                 tracing::warn!(
                     "Unexpected run reason: {:?}, defaulting to current reason",
@@ -635,10 +1017,478 @@ pub trait ContainerService {
             }
         };
 
-        self.start_execution(&ctx.task_attempt, next_action, &next_run_reason)
+        // Agent steps of a pipeline continue the agent's latest session, which earlier agent
+        // steps may have moved on from
+        let mut next_action = next_action.as_ref().clone();
+        if next_action.pipeline_step().is_some()
+            && let ExecutorActionType::CodingAgentFollowUpRequest(request) = &mut next_action.typ
+            && let Some(session_id) = self.latest_session_id(ctx).await?
+        {
+            request.session_id = session_id;
+        }
+
+        self.start_execution(&ctx.task_attempt, &next_action, &next_run_reason)
             .await?;
 
         tracing::debug!("Started next action: {:?}", next_action);
         Ok(())
     }
+
+    /// The attempt's latest coding agent session. A coding agent that just exited may not
+    /// have had its session stored yet, so its logs are checked first.
+    async fn latest_session_id(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<Option<String>, ContainerError> {
+        if ctx.execution_process.run_reason == ExecutionProcessRunReason::CodingAgent
+            && let Some(store) = self.get_msg_store_by_id(&ctx.execution_process.id).await
+            && let Some(session_id) =
+                store
+                    .get_history()
+                    .into_iter()
+                    .rev()
+                    .find_map(|msg| match msg {
+                        LogMsg::SessionId(session_id) => Some(session_id),
+                        _ => None,
+                    })
+        {
+            return Ok(Some(session_id));
+        }
+        Ok(ExecutionProcess::find_latest_session_id_by_task_attempt(
+            &self.db().pool,
+            ctx.task_attempt.id,
+        )
+        .await?)
+    }
+
+    /// Run the project's pipeline after a coding agent run, followed by its test script if it
+    /// has one. The pipeline takes over the run's next action. Returns whether the first step
+    /// was started.
+    async fn try_start_pipeline(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        let pool = &self.db().pool;
+        if ctx.execution_process.run_reason != ExecutionProcessRunReason::CodingAgent {
+            return Ok(false);
+        }
+        let action = ctx.execution_process.executor_action()?;
+        // Agent steps don't start the pipeline over, and fixes for failing tests run them next
+        if action.pipeline_step().is_some()
+            || action
+                .next_action()
+                .is_some_and(|next| test_fix::is_test_run(next))
+        {
+            return Ok(false);
+        }
+        let Some(project) = Project::find_by_id(pool, ctx.task.project_id).await? else {
+            return Ok(false);
+        };
+        if project.pipeline.is_empty() {
+            return Ok(false);
+        }
+
+        let session_id = self.latest_session_id(ctx).await?;
+        let profile_variant_label = action.typ().profile_variant_label();
+        let agent = session_id.as_deref().zip(profile_variant_label);
+        let then = match project.test_script {
+            Some(test_script) => Some(Box::new(test_action(
+                test_script,
+                action.next_action().cloned(),
+            ))),
+            None => action.next_action().cloned(),
+        };
+        let Some(first_step) = pipeline::compile(&project.pipeline, agent, then)? else {
+            return Ok(false);
+        };
+        self.start_execution(
+            &ctx.task_attempt,
+            &first_step,
+            &pipeline::run_reason(&first_step),
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Run the project's test script after a coding agent run. The tests take over the run's
+    /// next action. Returns whether the tests were started.
+    async fn try_start_tests(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        if ctx.execution_process.run_reason != ExecutionProcessRunReason::CodingAgent {
+            return Ok(false);
+        }
+        let action = ctx.execution_process.executor_action()?;
+        // A fix for failing tests already runs them next, and a pipeline runs them after its
+        // last step
+        if action.pipeline_step().is_some()
+            || action
+                .next_action()
+                .is_some_and(|next| test_fix::is_test_run(next))
+        {
+            return Ok(false);
+        }
+        let Some(test_script) = Project::find_by_id(&self.db().pool, ctx.task.project_id)
+            .await?
+            .and_then(|project| project.test_script)
+        else {
+            return Ok(false);
+        };
+
+        let test_action = test_action(test_script, action.next_action().cloned());
+        self.start_execution(
+            &ctx.task_attempt,
+            &test_action,
+            &ExecutionProcessRunReason::TestScript,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Send the coding agent the end of a failing test run's output to fix, unless the
+    /// project's fix iterations are used up. The fix runs the tests again once it's done.
+    /// Returns whether a fix was started.
+    async fn try_start_test_fix(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        let pool = &self.db().pool;
+        if ctx.execution_process.run_reason != ExecutionProcessRunReason::TestScript {
+            return Ok(false);
+        }
+        let test_action = ctx.execution_process.executor_action()?;
+        let ExecutorActionType::ScriptRequest(test_request) = test_action.typ() else {
+            return Ok(false);
+        };
+
+        let project = Project::find_by_id(pool, ctx.task.project_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let processes =
+            ExecutionProcess::find_by_task_attempt_id(pool, ctx.task_attempt.id).await?;
+        let iterations = test_fix::fix_iterations(&processes) as u64;
+        if iterations >= project.fix_iteration_limit() {
+            tracing::info!(
+                "Tests still fail for task attempt {} after {} fix iterations, leaving it for review",
+                ctx.task_attempt.id,
+                iterations
+            );
+            return Ok(false);
+        }
+
+        let Some(session_id) =
+            ExecutionProcess::find_latest_session_id_by_task_attempt(pool, ctx.task_attempt.id)
+                .await?
+        else {
+            return Ok(false);
+        };
+        // The fix continues with the agent's latest profile variant
+        let Some(agent) = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+            pool,
+            ctx.task_attempt.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        else {
+            return Ok(false);
+        };
+        let Some(profile_variant_label) = agent
+            .executor_action()?
+            .typ()
+            .profile_variant_label()
+            .cloned()
+        else {
+            return Ok(false);
+        };
+
+        let logs = match self.get_msg_store_by_id(&ctx.execution_process.id).await {
+            Some(store) => store.get_history(),
+            None => ExecutionProcessLogs::find_by_execution_id(pool, ctx.execution_process.id)
+                .await?
+                .and_then(|logs| logs.parse_logs().ok())
+                .unwrap_or_default(),
+        };
+        let fix_action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: test_fix::fix_prompt(&test_request.script, &test_fix::output_tail(&logs)),
+                session_id,
+                profile_variant_label,
+            }),
+            Some(Box::new(test_action.clone())),
+        );
+        self.start_execution(
+            &ctx.task_attempt,
+            &fix_action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+
+        tracing::info!(
+            "Tests failed for task attempt {}, starting fix iteration {}",
+            ctx.task_attempt.id,
+            iterations + 1
+        );
+        Ok(true)
+    }
+
+    /// Have the project's review profile review the attempt's changes after a coding agent
+    /// run, or after the pipeline and tests if the project has them. The review takes over
+    /// the run's next action. Returns whether a review was started.
+    async fn try_start_review(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        match ctx.execution_process.run_reason {
+            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::PipelineStep => {
+                // A fix for failing tests is reviewed once they pass, and a pipeline step once
+                // the pipeline is done
+                if ctx
+                    .execution_process
+                    .executor_action()?
+                    .next_action()
+                    .is_some_and(|next| {
+                        test_fix::is_test_run(next) || next.pipeline_step().is_some()
+                    })
+                {
+                    return Ok(false);
+                }
+            }
+            ExecutionProcessRunReason::TestScript => {}
+            _ => return Ok(false),
+        }
+        let Some(review_profile) = Project::find_by_id(&self.db().pool, ctx.task.project_id)
+            .await?
+            .and_then(|project| project.review_profile)
+        else {
+            return Ok(false);
+        };
+        let (Some(container_ref), Some(branch_name)) =
+            (&ctx.task_attempt.container_ref, &ctx.task_attempt.branch)
+        else {
+            return Ok(false);
+        };
+
+        let diffs = self.git().get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: &PathBuf::from(container_ref),
+                branch_name,
+                base_branch: &ctx.task_attempt.base_branch,
+            },
+            None,
+        )?;
+        // Nothing to review, e.g. when the agent only presented a plan
+        if diffs.is_empty() {
+            return Ok(false);
+        }
+
+        let review_action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: code_review::review_prompt(&ctx.task, &diffs),
+                profile_variant_label: review_profile.0,
+            }),
+            ctx.execution_process
+                .executor_action()?
+                .next_action()
+                .cloned(),
+        );
+        self.start_execution(
+            &ctx.task_attempt,
+            &review_action,
+            &ExecutionProcessRunReason::CodeReview,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Retry a failed initial coding agent request in a new attempt, using the next profile
+    /// of the fallback chain configured on the profile the chain started with.
+    /// Returns the new attempt, if one was started.
+    async fn try_start_fallback(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<Option<TaskAttempt>, ContainerError> {
+        if !matches!(
+            ctx.execution_process.executor_action()?.typ(),
+            ExecutorActionType::CodingAgentInitialRequest(_)
+        ) {
+            return Ok(None);
+        }
+
+        // Walk back to the attempt the chain started with
+        let mut first_attempt = ctx.task_attempt.clone();
+        let mut failures = 1;
+        while let Some(previous_id) = first_attempt.fallback_from_attempt_id {
+            let Some(previous) = TaskAttempt::find_by_id(&self.db().pool, previous_id).await?
+            else {
+                break;
+            };
+            first_attempt = previous;
+            failures += 1;
+        }
+
+        let profiles = ProfileConfigs::get_cached();
+        let Some(fallback) = profiles.get_fallback(&first_attempt.profile, failures) else {
+            return Ok(None);
+        };
+        let label = fallback.default.label.clone();
+
+        let task_attempt = TaskAttempt::create(
+            &self.db().pool,
+            &CreateTaskAttempt {
+                profile: label.clone(),
+                base_branch: ctx.task_attempt.base_branch.clone(),
+                fallback_from_attempt_id: Some(ctx.task_attempt.id),
+            },
+            ctx.task.id,
+        )
+        .await?;
+        self.start_attempt(&task_attempt, ProfileVariantLabel::default(label.clone()))
+            .await?;
+
+        tracing::info!(
+            "Attempt {} failed, retrying in attempt {} with profile {}",
+            ctx.task_attempt.id,
+            task_attempt.id,
+            label
+        );
+        Ok(Some(task_attempt))
+    }
+
+    /// Start an attempt for each task set to auto-start whose blockers are all done.
+    /// Auto-start is turned off as a task is started, so a task that fails to start is
+    /// logged and left to the user.
+    async fn start_unblocked_tasks(&self) -> Result<(), ContainerError> {
+        for task in TaskDependency::find_unblocked_auto_start_tasks(&self.db().pool).await? {
+            if let Err(e) = self.start_unblocked_task(&task).await {
+                tracing::error!("Failed to auto-start unblocked task {}: {}", task.id, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn start_unblocked_task(&self, task: &Task) -> Result<(), ContainerError> {
+        let pool = &self.db().pool;
+        let Some(profile_variant_label) = task.auto_start_profile.clone().map(|p| p.0) else {
+            return Ok(());
+        };
+        if !Task::claim_auto_start(pool, task.id).await? {
+            return Ok(());
+        }
+        let profile_label = ProfileConfigs::get_cached()
+            .get_profile(&profile_variant_label.profile)
+            .map(|profile| profile.default.label.clone())
+            .ok_or_else(|| anyhow::anyhow!("Profile not found: {profile_variant_label:?}"))?;
+        let project = task
+            .parent_project(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let base_branch = self
+            .git()
+            .get_current_branch(&project.git_repo_path)
+            .map_err(GitServiceError::from)?;
+
+        let task_attempt = TaskAttempt::create(
+            pool,
+            &CreateTaskAttempt {
+                profile: profile_label,
+                base_branch,
+                fallback_from_attempt_id: None,
+            },
+            task.id,
+        )
+        .await?;
+        self.start_attempt(&task_attempt, profile_variant_label)
+            .await?;
+
+        tracing::info!(
+            "Task {} is no longer blocked, started attempt {}",
+            task.id,
+            task_attempt.id
+        );
+        Ok(())
+    }
+}
+
+/// Run the project's test script, then go on to `next_action`
+fn test_action(test_script: String, next_action: Option<Box<ExecutorAction>>) -> ExecutorAction {
+    ExecutorAction::new(
+        ExecutorActionType::ScriptRequest(ScriptRequest::new(
+            test_script,
+            ScriptRequestLanguage::Bash,
+            ScriptContext::TestScript,
+        )),
+        next_action,
+    )
+}
+
+/// Whether a process continues an attempt's earlier coding agent run, rather than starting
+/// new work
+fn continues_earlier_run(process: &ExecutionProcess) -> bool {
+    process.run_reason == ExecutionProcessRunReason::CodeReview
+        || process.executor_action().is_ok_and(|action| {
+            matches!(
+                action.typ(),
+                ExecutorActionType::CodingAgentFollowUpRequest(_)
+            )
+        })
+}
+
+/// Tells the client which entry a window of logs starts at, so it can ask for the entries
+/// before it
+fn entries_start_event(start: usize) -> Event {
+    Event::default()
+        .event(EV_ENTRIES_START)
+        .data(start.to_string())
+}
+
+/// A raw stdout/stderr message as a JSON patch adding it at the given index
+fn raw_entry_event(index: usize, msg: LogMsg) -> Event {
+    let patch = match msg {
+        LogMsg::Stdout(content) => ConversationPatch::add_stdout(index, content),
+        LogMsg::Stderr(content) => ConversationPatch::add_stderr(index, content),
+        _ => unreachable!("Filter should only pass Stdout/Stderr"),
+    };
+    LogMsg::JsonPatch(patch).to_sse_event()
+}
+
+/// A finished window of raw messages, starting at entry `start`
+fn raw_entries_stream(
+    start: usize,
+    messages: Vec<LogMsg>,
+) -> futures::stream::BoxStream<'static, Result<Event, std::io::Error>> {
+    futures::stream::once(future::ready(Ok(entries_start_event(start))))
+        .chain(futures::stream::iter(messages.into_iter().enumerate().map(
+            |(index, m)| Ok::<_, std::io::Error>(raw_entry_event(index, m)),
+        )))
+        .chain(futures::stream::once(async {
+            Ok::<_, std::io::Error>(LogMsg::Finished.to_sse_event())
+        }))
+        .boxed()
+}
+
+/// The patches the normalizer pushed to a store from `normalize_logs_from_db`, ending once the
+/// normalizer is done
+fn renormalized_patches(
+    store: &MsgStore,
+) -> futures::stream::BoxStream<'static, json_patch::Patch> {
+    let mut finished = 0;
+    store
+        .history_plus_stream()
+        .take_while(move |msg| {
+            if matches!(msg, Ok(LogMsg::Finished)) {
+                finished += 1;
+            }
+            future::ready(msg.is_ok() && finished < 2)
+        })
+        .filter_map(|msg| {
+            future::ready(match msg {
+                Ok(LogMsg::JsonPatch(patch)) => Some(patch),
+                _ => None,
+            })
+        })
+        .boxed()
+}
+
+/// Store the JSONL lines gathered for an execution as one chunk of its logs
+async fn append_log_chunk(db: &DBService, execution_id: Uuid, chunk: &mut String) {
+    if chunk.is_empty() {
+        return;
+    }
+    if let Err(e) = ExecutionProcessLogs::append_log_lines(&db.pool, execution_id, chunk).await {
+        tracing::error!(
+            "Failed to append log lines for execution {}: {}",
+            execution_id,
+            e
+        );
+    }
+    chunk.clear();
 }
@@ -0,0 +1,101 @@
+//! Finding the local URL a dev server is listening on from the lines it prints on startup,
+//! so it can be previewed through the vibe-kanban port, checking its health and restarting
+//! it when it crashes
+
+use std::{sync::OnceLock, time::Duration};
+
+use regex::Regex;
+
+/// How often a dev server's health check URL is polled while it runs
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Crashes in a row after which a dev server is left stopped
+const MAX_RESTARTS: u32 = 5;
+/// Wait before the first restart, doubled for each crash in a row after it
+const INITIAL_RESTART_DELAY: Duration = Duration::from_secs(1);
+/// A dev server that ran this long before crashing starts its count of crashes over
+pub const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+/// Terminal colour codes, which dev servers like to wrap their URLs in
+fn ansi_regex() -> &'static Regex {
+    static ANSI_REGEX: OnceLock<Regex> = OnceLock::new();
+    ANSI_REGEX.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap())
+}
+
+/// A local URL with an explicit port, e.g. `http://localhost:5173/`
+fn url_regex() -> &'static Regex {
+    static URL_REGEX: OnceLock<Regex> = OnceLock::new();
+    URL_REGEX.get_or_init(|| {
+        Regex::new(r"(https?)://(localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1?\]):(\d{1,5})\b").unwrap()
+    })
+}
+
+/// The origin of the first local URL in a line of dev server output. Wildcard addresses
+/// are reported as `localhost`, which is where the server can be reached from here.
+pub fn detect_url(line: &str) -> Option<String> {
+    let line = ansi_regex().replace_all(line, "");
+    let captures = url_regex().captures(&line)?;
+    let port: u16 = captures[3].parse().ok().filter(|port| *port != 0)?;
+    let host = match &captures[2] {
+        "0.0.0.0" | "[::]" => "localhost",
+        host => host,
+    };
+    Some(format!("{}://{}:{}", &captures[1], host, port))
+}
+
+/// Whether a GET of a dev server's health check URL succeeds
+pub async fn check_url(client: &reqwest::Client, url: &str) -> bool {
+    client
+        .get(url)
+        .timeout(HEALTH_CHECK_TIMEOUT)
+        .send()
+        .await
+        .is_ok_and(|response| response.status().is_success())
+}
+
+/// How long to wait before restarting a dev server that has already been restarted
+/// `restarts` times in a row, or None once it has crashed too often to try again
+pub fn restart_delay(restarts: u32) -> Option<Duration> {
+    (restarts < MAX_RESTARTS).then(|| INITIAL_RESTART_DELAY * 2u32.pow(restarts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_common_dev_server_banners() {
+        assert_eq!(
+            detect_url("  ➜  Local:   \x1b[36mhttp://localhost:\x1b[1m5173\x1b[22m/\x1b[39m"),
+            Some("http://localhost:5173".to_string())
+        );
+        assert_eq!(
+            detect_url("Serving HTTP on 0.0.0.0 port 8000 (http://0.0.0.0:8000/) ..."),
+            Some("http://localhost:8000".to_string())
+        );
+        assert_eq!(
+            detect_url("ready - started server on https://127.0.0.1:3000/app"),
+            Some("https://127.0.0.1:3000".to_string())
+        );
+        assert_eq!(
+            detect_url("Listening on http://[::]:4000"),
+            Some("http://localhost:4000".to_string())
+        );
+    }
+
+    #[test]
+    fn ignores_urls_without_a_local_port() {
+        assert_eq!(detect_url("See https://vitejs.dev/config for help"), None);
+        assert_eq!(detect_url("Proxying to http://localhost/api"), None);
+        assert_eq!(detect_url("http://localhost:99999"), None);
+    }
+
+    #[test]
+    fn restarts_back_off_then_give_up() {
+        assert_eq!(restart_delay(0), Some(Duration::from_secs(1)));
+        assert_eq!(restart_delay(1), Some(Duration::from_secs(2)));
+        assert_eq!(restart_delay(4), Some(Duration::from_secs(16)));
+        assert_eq!(restart_delay(MAX_RESTARTS), None);
+    }
+}
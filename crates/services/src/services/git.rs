@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
 use git2::{
@@ -59,6 +59,14 @@ pub struct BranchStatus {
     pub remote_up_to_date: Option<bool>,
 }
 
+/// Size of a diff, as in `git diff --shortstat`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct DiffStats {
+    pub files_changed: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
 /// Target for diff generation
 pub enum DiffTarget<'p> {
     /// Work-in-progress branch checked out in this worktree
@@ -162,6 +170,26 @@ impl GitService {
         Ok(())
     }
 
+    /// The git dir shared by a worktree and its repository, which holds the worktree's own
+    /// index and HEAD under `worktrees/` as well as the objects and refs commits write to
+    pub fn get_common_dir(&self, worktree_path: &Path) -> Result<PathBuf, GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        let git_dir = repo.path();
+        if !repo.is_worktree() {
+            return Ok(git_dir.to_path_buf());
+        }
+        // A worktree's git dir names the common dir in its `commondir` file, usually as `../..`
+        let common_dir = std::fs::read_to_string(git_dir.join("commondir"))?;
+        Ok(git_dir.join(common_dir.trim()).canonicalize()?)
+    }
+
+    /// The git dir of a worktree itself, with its index and HEAD, which for a linked
+    /// worktree lives under `worktrees/` in the common dir
+    pub fn get_worktree_git_dir(&self, worktree_path: &Path) -> Result<PathBuf, GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        Ok(repo.path().canonicalize()?)
+    }
+
     pub fn commit(&self, path: &Path, message: &str) -> Result<(), GitServiceError> {
         let repo = Repository::open(path)?;
 
@@ -208,12 +236,70 @@ impl GitService {
         Ok(())
     }
 
+    /// The commit checked out in a worktree
+    pub fn get_head_commit(&self, worktree_path: &Path) -> Result<String, GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        Ok(repo.head()?.peel_to_commit()?.id().to_string())
+    }
+
+    /// Put a worktree back to `commit`, dropping any commits, changes and untracked files
+    /// made since. Ignored files are left alone.
+    pub fn restore_worktree(
+        &self,
+        worktree_path: &Path,
+        commit: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        let commit = repo.find_commit(git2::Oid::from_str(commit)?)?;
+        repo.reset(commit.as_object(), git2::ResetType::Hard, None)?;
+
+        let mut status_options = StatusOptions::new();
+        status_options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+        for entry in repo.statuses(Some(&mut status_options))?.iter() {
+            if entry.status().contains(Status::WT_NEW)
+                && let Some(path) = entry.path()
+            {
+                std::fs::remove_file(worktree_path.join(path))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Get diffs between branches or worktree changes
     pub fn get_diffs(
         &self,
         target: DiffTarget,
         path_filter: Option<&[&str]>,
     ) -> Result<Vec<Diff>, GitServiceError> {
+        self.with_diff(target, path_filter, false, |diff, repo| {
+            self.convert_diff_to_file_diffs(diff, repo)
+        })
+    }
+
+    /// Count the files and lines changed between branches or in worktree changes
+    pub fn get_diff_stats(&self, target: DiffTarget) -> Result<DiffStats, GitServiceError> {
+        // Untracked files' content is diffed too, so their lines count as additions
+        self.with_diff(target, None, true, |diff, _| {
+            let stats = diff.stats()?;
+            Ok(DiffStats {
+                files_changed: stats.files_changed(),
+                additions: stats.insertions(),
+                deletions: stats.deletions(),
+            })
+        })
+    }
+
+    /// Build the git2 diff for a target and hand it to `f` along with its repository
+    fn with_diff<T>(
+        &self,
+        target: DiffTarget,
+        path_filter: Option<&[&str]>,
+        untracked_content: bool,
+        f: impl FnOnce(git2::Diff<'_>, &Repository) -> Result<T, GitServiceError>,
+    ) -> Result<T, GitServiceError> {
         match target {
             DiffTarget::Worktree {
                 worktree_path,
@@ -229,6 +315,7 @@ impl GitService {
                 let mut diff_opts = DiffOptions::new();
                 diff_opts
                     .include_untracked(true)
+                    .show_untracked_content(untracked_content)
                     .include_typechange(true)
                     .recurse_untracked_dirs(true);
 
@@ -246,7 +333,7 @@ impl GitService {
                 let mut find_opts = DiffFindOptions::new();
                 diff.find_similar(Some(&mut find_opts))?;
 
-                self.convert_diff_to_file_diffs(diff, &repo)
+                f(diff, &repo)
             }
             DiffTarget::Branch {
                 repo_path,
@@ -287,7 +374,7 @@ impl GitService {
                 let mut find_opts = DiffFindOptions::new();
                 diff.find_similar(Some(&mut find_opts))?;
 
-                self.convert_diff_to_file_diffs(diff, &repo)
+                f(diff, &repo)
             }
             DiffTarget::Commit {
                 repo_path,
@@ -332,7 +419,7 @@ impl GitService {
                 let mut find_opts = git2::DiffFindOptions::new();
                 diff.find_similar(Some(&mut find_opts))?;
 
-                self.convert_diff_to_file_diffs(diff, &repo)
+                f(diff, &repo)
             }
         }
     }
@@ -939,6 +1026,20 @@ impl GitService {
         Ok(commit_id.to_string())
     }
 
+    /// Delete a local branch; its worktree must have been removed first
+    pub fn delete_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let mut branch = repo
+            .find_branch(branch_name, BranchType::Local)
+            .map_err(|_| GitServiceError::BranchNotFound(branch_name.to_string()))?;
+        branch.delete()?;
+        Ok(())
+    }
+
     /// Get the default branch name for the repository
     pub fn get_default_branch_name(&self, repo_path: &Path) -> Result<String, GitServiceError> {
         let repo = self.open_repo(repo_path)?;
@@ -1,13 +1,18 @@
 pub mod analytics;
 pub mod auth;
+pub mod code_review;
 pub mod config;
 pub mod container;
+pub mod dev_server;
 pub mod events;
 pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git;
 pub mod github_service;
 pub mod notification;
+pub mod pipeline;
 pub mod pr_monitor;
 pub mod sentry;
+pub mod test_fix;
+pub mod transcript;
 pub mod worktree_manager;
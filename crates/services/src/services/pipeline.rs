@@ -0,0 +1,146 @@
+//! Project pipelines: the steps a project runs after each coding agent run, compiled into an
+//! executor action chain so that every step runs as its own execution process
+
+use anyhow::anyhow;
+use db::models::execution_process::ExecutionProcessRunReason;
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType,
+        coding_agent_follow_up::CodingAgentFollowUpRequest,
+        pipeline::{PipelineStep, PipelineStepAction},
+        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+    },
+    profile::ProfileVariantLabel,
+};
+
+/// Compile the steps into a chain that goes on to `then` after the last step. Agent
+/// follow-ups continue `session_id` with the given profile variant.
+pub fn compile(
+    steps: &[PipelineStep],
+    agent: Option<(&str, &ProfileVariantLabel)>,
+    then: Option<Box<ExecutorAction>>,
+) -> Result<Option<Box<ExecutorAction>>, anyhow::Error> {
+    let mut next_action = then;
+    for step in steps.iter().rev() {
+        let typ = match &step.action {
+            PipelineStepAction::Script { script } => {
+                ExecutorActionType::ScriptRequest(ScriptRequest::new(
+                    script.clone(),
+                    ScriptRequestLanguage::Bash,
+                    ScriptContext::PipelineStep,
+                ))
+            }
+            PipelineStepAction::AgentFollowUp { prompt } => {
+                let Some((session_id, profile_variant_label)) = agent else {
+                    return Err(anyhow!(
+                        "Pipeline step '{}' needs a coding agent session to follow up on",
+                        step.name
+                    ));
+                };
+                ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                    prompt: prompt.clone(),
+                    session_id: session_id.to_string(),
+                    profile_variant_label: profile_variant_label.clone(),
+                })
+            }
+        };
+        next_action = Some(Box::new(
+            ExecutorAction::new(typ, next_action).with_pipeline_step(step.info()),
+        ));
+    }
+    Ok(next_action)
+}
+
+/// The run reason of an action compiled from a pipeline step
+pub fn run_reason(action: &ExecutorAction) -> ExecutionProcessRunReason {
+    match action.typ() {
+        ExecutorActionType::ScriptRequest(_) => ExecutionProcessRunReason::PipelineStep,
+        _ => ExecutionProcessRunReason::CodingAgent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(name: &str, action: PipelineStepAction) -> PipelineStep {
+        PipelineStep {
+            name: name.to_string(),
+            action,
+            continue_on_failure: name == "lint",
+        }
+    }
+
+    #[test]
+    fn steps_chain_in_order_before_the_next_action() {
+        let steps = vec![
+            step(
+                "format",
+                PipelineStepAction::Script {
+                    script: "cargo fmt".to_string(),
+                },
+            ),
+            step(
+                "lint",
+                PipelineStepAction::Script {
+                    script: "cargo clippy".to_string(),
+                },
+            ),
+            step(
+                "fix",
+                PipelineStepAction::AgentFollowUp {
+                    prompt: "Fix the lints".to_string(),
+                },
+            ),
+        ];
+        let then = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest::new(
+                "cleanup".to_string(),
+                ScriptRequestLanguage::Bash,
+                ScriptContext::CleanupScript,
+            )),
+            None,
+        );
+        let label = ProfileVariantLabel::default("claude-code".to_string());
+
+        let chain = compile(&steps, Some(("session", &label)), Some(Box::new(then)))
+            .unwrap()
+            .unwrap();
+
+        let mut names = vec![];
+        let mut action = Some(&chain);
+        while let Some(current) = action {
+            names.push(current.pipeline_step().map(|step| step.name.clone()));
+            action = current.next_action();
+        }
+        assert_eq!(
+            names,
+            vec![
+                Some("format".to_string()),
+                Some("lint".to_string()),
+                Some("fix".to_string()),
+                None
+            ]
+        );
+        assert!(!chain.pipeline_step().unwrap().continue_on_failure);
+        let lint = chain.next_action().unwrap();
+        assert!(lint.pipeline_step().unwrap().continue_on_failure);
+        assert_eq!(run_reason(lint), ExecutionProcessRunReason::PipelineStep);
+        assert_eq!(
+            run_reason(lint.next_action().unwrap()),
+            ExecutionProcessRunReason::CodingAgent
+        );
+    }
+
+    #[test]
+    fn agent_steps_need_a_session() {
+        let steps = vec![step(
+            "fix",
+            PipelineStepAction::AgentFollowUp {
+                prompt: "Fix it".to_string(),
+            },
+        )];
+        assert!(compile(&steps, None, None).is_err());
+        assert!(compile(&[], None, None).unwrap().is_none());
+    }
+}
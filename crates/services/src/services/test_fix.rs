@@ -0,0 +1,94 @@
+//! The test-and-fix loop: the project's test script runs after each coding agent run, and
+//! while it fails the agent gets a follow-up with the end of the failing output
+
+use db::models::execution_process::{ExecutionProcess, ExecutionProcessRunReason};
+use executors::actions::{ExecutorAction, ExecutorActionType, script::ScriptContext};
+use utils::log_msg::LogMsg;
+
+/// Only the end of the test output goes into the fix prompt, where failures are summarised
+const MAX_OUTPUT_LINES: usize = 200;
+const MAX_OUTPUT_CHARS: usize = 20_000;
+
+/// Whether the action runs the project's test script
+pub fn is_test_run(action: &ExecutorAction) -> bool {
+    matches!(
+        action.typ(),
+        ExecutorActionType::ScriptRequest(request) if request.context == ScriptContext::TestScript
+    )
+}
+
+/// How many fix follow-ups the current loop has had, from the attempt's processes oldest
+/// first. Fix follow-ups are the coding agent runs that go on to run the tests again; the
+/// latest agent run that doesn't, or that is a pipeline step, is the one the loop started from.
+pub fn fix_iterations(processes: &[ExecutionProcess]) -> usize {
+    processes
+        .iter()
+        .rev()
+        .filter(|process| process.run_reason == ExecutionProcessRunReason::CodingAgent)
+        .take_while(|process| {
+            process.executor_action().is_ok_and(|action| {
+                action.pipeline_step().is_none()
+                    && action.next_action().is_some_and(|next| is_test_run(next))
+            })
+        })
+        .count()
+}
+
+/// The last lines of a process's stdout and stderr, interleaved as they were written
+pub fn output_tail(logs: &[LogMsg]) -> String {
+    let output: String = logs
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMsg::Stdout(content) | LogMsg::Stderr(content) => Some(content.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let lines: Vec<&str> = output.lines().collect();
+    let mut tail = lines[lines.len().saturating_sub(MAX_OUTPUT_LINES)..].join("\n");
+    if tail.len() > MAX_OUTPUT_CHARS {
+        let mut start = tail.len() - MAX_OUTPUT_CHARS;
+        while !tail.is_char_boundary(start) {
+            start += 1;
+        }
+        tail.drain(..start);
+    }
+    tail
+}
+
+/// Ask the agent to make the failing test script pass
+pub fn fix_prompt(test_script: &str, output_tail: &str) -> String {
+    format!(
+        "The tests fail after your changes. Fix the code so they pass, without weakening or \
+         skipping tests.\n\n\
+         ## Test script\n\n```sh\n{test_script}\n```\n\n\
+         ## End of the output\n\n```\n{output_tail}\n```"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_keeps_last_lines_of_both_streams() {
+        let mut logs = vec![LogMsg::SessionId("session".to_string())];
+        for i in 0..300 {
+            logs.push(LogMsg::Stdout(format!("line {i}\n")));
+        }
+        logs.push(LogMsg::Stderr("test greeting ... FAILED\n".to_string()));
+
+        let tail = output_tail(&logs);
+        assert_eq!(tail.lines().count(), MAX_OUTPUT_LINES);
+        assert!(tail.starts_with("line 101\n"));
+        assert!(tail.ends_with("line 299\ntest greeting ... FAILED"));
+    }
+
+    #[test]
+    fn tail_is_cut_to_size_on_a_char_boundary() {
+        let logs = vec![LogMsg::Stdout("é".repeat(MAX_OUTPUT_CHARS))];
+        let tail = output_tail(&logs);
+        assert!(tail.len() <= MAX_OUTPUT_CHARS);
+        assert!(tail.chars().all(|c| c == 'é'));
+    }
+}
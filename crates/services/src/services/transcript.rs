@@ -0,0 +1,562 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use db::models::execution_process::ExecutionProcessStatus;
+use executors::logs::{
+    ActionType, FileChange, NormalizedConversation, NormalizedEntry, NormalizedEntryType,
+    ToolResult,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    #[default]
+    Markdown,
+    Json,
+    Html,
+}
+
+impl TranscriptFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Markdown => "text/markdown; charset=utf-8",
+            Self::Json => "application/json",
+            Self::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// The conversations of every coding agent run in a task attempt
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AttemptTranscript {
+    pub task_attempt_id: Uuid,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    pub profile: String,
+    pub branch: Option<String>,
+    pub base_branch: String,
+    pub created_at: DateTime<Utc>,
+    pub runs: Vec<AgentRunTranscript>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AgentRunTranscript {
+    pub execution_process_id: Uuid,
+    pub status: ExecutionProcessStatus,
+    pub exit_code: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub conversation: NormalizedConversation,
+}
+
+impl AttemptTranscript {
+    pub fn file_name(&self, format: TranscriptFormat) -> String {
+        let id = self.task_attempt_id.to_string();
+        format!("attempt-{}-transcript.{}", &id[..8], format.extension())
+    }
+
+    pub fn render(&self, format: TranscriptFormat) -> Result<String, serde_json::Error> {
+        match format {
+            TranscriptFormat::Markdown => Ok(self.to_markdown()),
+            TranscriptFormat::Json => serde_json::to_string_pretty(self),
+            TranscriptFormat::Html => Ok(self.to_html()),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.task_title);
+        if let Some(description) = self.task_description.as_deref().filter(|d| !d.is_empty()) {
+            let _ = write!(out, "{description}\n\n");
+        }
+        let _ = writeln!(out, "- **Attempt:** `{}`", self.task_attempt_id);
+        let _ = writeln!(out, "- **Profile:** {}", self.profile);
+        if let Some(branch) = &self.branch {
+            let _ = writeln!(
+                out,
+                "- **Branch:** `{branch}` (based on `{}`)",
+                self.base_branch
+            );
+        }
+        let _ = writeln!(out, "- **Created:** {}", self.created_at.to_rfc3339());
+
+        for (number, run) in self.runs.iter().enumerate() {
+            let _ = write!(
+                out,
+                "\n## Run {}: {} ({})\n\n{}\n",
+                number + 1,
+                run.conversation.executor_type,
+                status_label(&run.status),
+                run.timing()
+            );
+            if let Some(prompt) = &run.conversation.prompt {
+                let _ = write!(out, "\n### Prompt\n\n{}\n", prompt.trim_end());
+            }
+            for entry in &run.conversation.entries {
+                out.push('\n');
+                write_markdown_entry(&mut out, entry);
+            }
+            if let Some(summary) = &run.conversation.summary {
+                let _ = write!(out, "\n### Summary\n\n{}\n", summary.trim_end());
+            }
+        }
+        out
+    }
+
+    /// A self-contained page: styles are inlined and nothing is loaded from elsewhere
+    pub fn to_html(&self) -> String {
+        let mut body = format!("<h1>{}</h1>\n", escape_html(&self.task_title));
+        if let Some(description) = self.task_description.as_deref().filter(|d| !d.is_empty()) {
+            let _ = writeln!(
+                body,
+                "<div class=\"content\">{}</div>",
+                escape_html(description)
+            );
+        }
+        body.push_str("<dl class=\"meta\">\n");
+        let _ = writeln!(
+            body,
+            "<dt>Attempt</dt><dd><code>{}</code></dd>",
+            self.task_attempt_id
+        );
+        let _ = writeln!(
+            body,
+            "<dt>Profile</dt><dd>{}</dd>",
+            escape_html(&self.profile)
+        );
+        if let Some(branch) = &self.branch {
+            let _ = writeln!(
+                body,
+                "<dt>Branch</dt><dd><code>{}</code> (based on <code>{}</code>)</dd>",
+                escape_html(branch),
+                escape_html(&self.base_branch)
+            );
+        }
+        let _ = writeln!(
+            body,
+            "<dt>Created</dt><dd>{}</dd>",
+            self.created_at.to_rfc3339()
+        );
+        body.push_str("</dl>\n");
+
+        for (number, run) in self.runs.iter().enumerate() {
+            let _ = writeln!(
+                body,
+                "<article class=\"run\">\n<h2>Run {}: {} ({})</h2>\n<p class=\"timing\">{}</p>",
+                number + 1,
+                escape_html(&run.conversation.executor_type),
+                status_label(&run.status),
+                escape_html(&run.timing())
+            );
+            if let Some(prompt) = &run.conversation.prompt {
+                write_html_section(&mut body, "prompt", "Prompt", prompt, "");
+            }
+            for entry in &run.conversation.entries {
+                write_html_entry(&mut body, entry);
+            }
+            if let Some(summary) = &run.conversation.summary {
+                write_html_section(&mut body, "summary", "Summary", summary, "");
+            }
+            body.push_str("</article>\n");
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+            escape_html(&self.task_title)
+        )
+    }
+}
+
+impl AgentRunTranscript {
+    fn timing(&self) -> String {
+        let mut timing = format!("Started {}", self.started_at.to_rfc3339());
+        if let Some(completed_at) = self.completed_at {
+            let _ = write!(timing, ", finished {}", completed_at.to_rfc3339());
+        }
+        if let Some(exit_code) = self.exit_code {
+            let _ = write!(timing, ", exit code {exit_code}");
+        }
+        timing
+    }
+}
+
+const HTML_STYLE: &str = "
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1f2328; line-height: 1.5; }
+h1, h2, h3 { line-height: 1.25; }
+code, pre { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 0.875rem; }
+pre { background: #f6f8fa; padding: 0.75rem; overflow-x: auto; border-radius: 6px; }
+dl.meta { display: grid; grid-template-columns: max-content 1fr; gap: 0.25rem 1rem; }
+dl.meta dt { font-weight: 600; }
+dl.meta dd { margin: 0; }
+.run { border-top: 1px solid #d0d7de; margin-top: 2rem; }
+.timing { color: #656d76; }
+.entry { border-left: 3px solid #d0d7de; padding: 0 0 0 1rem; margin: 1rem 0; }
+.entry h3 { font-size: 0.875rem; text-transform: uppercase; color: #656d76; margin: 0 0 0.25rem; }
+.entry-user, .entry-prompt { border-color: #0969da; }
+.entry-assistant, .entry-summary { border-color: #1a7f37; }
+.entry-tool { border-color: #8250df; }
+.entry-error { border-color: #cf222e; }
+.entry-thinking { color: #656d76; font-style: italic; }
+.content { white-space: pre-wrap; }
+.diff .add { color: #1a7f37; background: #dafbe1; }
+.diff .del { color: #cf222e; background: #ffebe9; }
+.diff .hunk { color: #0969da; }
+.result { font-weight: 600; color: #1a7f37; }
+.result-failed { color: #cf222e; }
+";
+
+fn status_label(status: &ExecutionProcessStatus) -> &'static str {
+    match status {
+        ExecutionProcessStatus::Queued => "queued",
+        ExecutionProcessStatus::Running => "running",
+        ExecutionProcessStatus::Completed => "completed",
+        ExecutionProcessStatus::Failed => "failed",
+        ExecutionProcessStatus::Killed => "killed",
+    }
+}
+
+fn entry_label(entry_type: &NormalizedEntryType) -> (&'static str, String) {
+    match entry_type {
+        NormalizedEntryType::UserMessage => ("user", "User".to_string()),
+        NormalizedEntryType::AssistantMessage => ("assistant", "Assistant".to_string()),
+        NormalizedEntryType::ToolUse { tool_name, .. } => ("tool", format!("Tool: {tool_name}")),
+        NormalizedEntryType::SystemMessage => ("system", "System".to_string()),
+        NormalizedEntryType::ErrorMessage => ("error", "Error".to_string()),
+        NormalizedEntryType::Thinking => ("thinking", "Thinking".to_string()),
+    }
+}
+
+/// A code fence longer than any run of backticks in the content
+fn fence(content: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in content.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn write_code_block(out: &mut String, language: &str, content: &str) {
+    let fence = fence(content);
+    let _ = write!(
+        out,
+        "\n{fence}{language}\n{}\n{fence}\n",
+        content.trim_end_matches('\n')
+    );
+}
+
+fn write_markdown_entry(out: &mut String, entry: &NormalizedEntry) {
+    let (_, label) = entry_label(&entry.entry_type);
+    let _ = writeln!(out, "### {label}\n");
+    match &entry.entry_type {
+        NormalizedEntryType::ErrorMessage => write_code_block(out, "", &entry.content),
+        NormalizedEntryType::Thinking => {
+            for line in entry.content.trim_end().lines() {
+                let _ = writeln!(out, "> {line}");
+            }
+        }
+        _ => {
+            let _ = writeln!(out, "{}", entry.content.trim_end());
+        }
+    }
+
+    let NormalizedEntryType::ToolUse {
+        action_type,
+        result,
+        ..
+    } = &entry.entry_type
+    else {
+        return;
+    };
+    match action_type {
+        ActionType::FileEdit { path, changes } => {
+            for change in changes {
+                match change {
+                    FileChange::Write { content } => {
+                        let _ = write!(out, "\nWrote `{path}`:\n");
+                        write_code_block(out, "", content);
+                    }
+                    FileChange::Delete => {
+                        let _ = writeln!(out, "\nDeleted `{path}`");
+                    }
+                    FileChange::Rename { new_path } => {
+                        let _ = writeln!(out, "\nRenamed `{path}` to `{new_path}`");
+                    }
+                    FileChange::Edit { unified_diff, .. } => {
+                        write_code_block(out, "diff", unified_diff)
+                    }
+                }
+            }
+        }
+        ActionType::CommandRun { command } => write_code_block(out, "sh", command),
+        ActionType::PlanPresentation { plan } => {
+            let _ = write!(out, "\n{}\n", plan.trim_end());
+        }
+        ActionType::TodoManagement { todos, .. } => {
+            out.push('\n');
+            for todo in todos {
+                let mark = if todo.status == "completed" { "x" } else { " " };
+                let _ = writeln!(out, "- [{mark}] {}", todo.content);
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(result) = result {
+        let _ = writeln!(out, "\n**Result:** {}", result_label(result));
+        if let Some(output) = &result.output {
+            write_code_block(out, "", output);
+        }
+    }
+}
+
+fn result_label(result: &ToolResult) -> String {
+    let status = if result.success {
+        "succeeded"
+    } else {
+        "failed"
+    };
+    match result.exit_code {
+        Some(exit_code) => format!("{status} (exit code {exit_code})"),
+        None => status.to_string(),
+    }
+}
+
+fn write_html_section(out: &mut String, kind: &str, label: &str, content: &str, details: &str) {
+    let _ = writeln!(
+        out,
+        "<section class=\"entry entry-{kind}\">\n<h3>{}</h3>\n<div class=\"content\">{}</div>{details}\n</section>",
+        escape_html(label),
+        escape_html(content.trim_end())
+    );
+}
+
+fn write_html_entry(out: &mut String, entry: &NormalizedEntry) {
+    let (kind, label) = entry_label(&entry.entry_type);
+    let mut details = String::new();
+    if let NormalizedEntryType::ToolUse {
+        action_type,
+        result,
+        ..
+    } = &entry.entry_type
+    {
+        match action_type {
+            ActionType::FileEdit { path, changes } => {
+                for change in changes {
+                    match change {
+                        FileChange::Write { content } => {
+                            let _ = write!(
+                                details,
+                                "\n<p>Wrote <code>{}</code>:</p>\n<pre>{}</pre>",
+                                escape_html(path),
+                                escape_html(content)
+                            );
+                        }
+                        FileChange::Delete => {
+                            let _ = write!(
+                                details,
+                                "\n<p>Deleted <code>{}</code></p>",
+                                escape_html(path)
+                            );
+                        }
+                        FileChange::Rename { new_path } => {
+                            let _ = write!(
+                                details,
+                                "\n<p>Renamed <code>{}</code> to <code>{}</code></p>",
+                                escape_html(path),
+                                escape_html(new_path)
+                            );
+                        }
+                        FileChange::Edit { unified_diff, .. } => {
+                            details.push_str("\n<pre class=\"diff\">");
+                            for line in unified_diff.lines() {
+                                let class = if line.starts_with("+++") || line.starts_with("---") {
+                                    ""
+                                } else if line.starts_with('+') {
+                                    "add"
+                                } else if line.starts_with('-') {
+                                    "del"
+                                } else if line.starts_with("@@") {
+                                    "hunk"
+                                } else {
+                                    ""
+                                };
+                                let _ = writeln!(
+                                    details,
+                                    "<span class=\"{class}\">{}</span>",
+                                    escape_html(line)
+                                );
+                            }
+                            details.push_str("</pre>");
+                        }
+                    }
+                }
+            }
+            ActionType::CommandRun { command } => {
+                let _ = write!(details, "\n<pre>{}</pre>", escape_html(command));
+            }
+            ActionType::PlanPresentation { plan } => {
+                let _ = write!(
+                    details,
+                    "\n<div class=\"content\">{}</div>",
+                    escape_html(plan.trim_end())
+                );
+            }
+            ActionType::TodoManagement { todos, .. } => {
+                details.push_str("\n<ul>");
+                for todo in todos {
+                    let checked = if todo.status == "completed" {
+                        " checked"
+                    } else {
+                        ""
+                    };
+                    let _ = write!(
+                        details,
+                        "<li><input type=\"checkbox\" disabled{checked}> {}</li>",
+                        escape_html(&todo.content)
+                    );
+                }
+                details.push_str("</ul>");
+            }
+            _ => {}
+        }
+        if let Some(result) = result {
+            let class = if result.success {
+                "result"
+            } else {
+                "result result-failed"
+            };
+            let _ = write!(
+                details,
+                "\n<p class=\"{class}\">Result: {}</p>",
+                result_label(result)
+            );
+            if let Some(output) = &result.output {
+                let _ = write!(details, "\n<pre>{}</pre>", escape_html(output));
+            }
+        }
+    }
+    write_html_section(out, kind, &label, &entry.content, &details);
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript() -> AttemptTranscript {
+        let entry = |entry_type, content: &str| NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        };
+        AttemptTranscript {
+            task_attempt_id: Uuid::nil(),
+            task_title: "Fix <script> handling".to_string(),
+            task_description: None,
+            profile: "claude-code".to_string(),
+            branch: Some("vk-fix".to_string()),
+            base_branch: "main".to_string(),
+            created_at: DateTime::UNIX_EPOCH,
+            runs: vec![AgentRunTranscript {
+                execution_process_id: Uuid::nil(),
+                status: ExecutionProcessStatus::Completed,
+                exit_code: Some(0),
+                started_at: DateTime::UNIX_EPOCH,
+                completed_at: None,
+                conversation: NormalizedConversation {
+                    entries: vec![
+                        entry(NormalizedEntryType::AssistantMessage, "Fixing it"),
+                        entry(
+                            NormalizedEntryType::ToolUse {
+                                tool_name: "Edit".to_string(),
+                                action_type: ActionType::FileEdit {
+                                    path: "src/lib.rs".to_string(),
+                                    changes: vec![FileChange::Edit {
+                                        unified_diff: "@@ -1 +1 @@\n-old\n+new".to_string(),
+                                        has_line_numbers: true,
+                                    }],
+                                },
+                                result: Some(ToolResult::new(false, Some(1), Some("oops"))),
+                            },
+                            "`src/lib.rs`",
+                        ),
+                    ],
+                    session_id: None,
+                    executor_type: "claude-code".to_string(),
+                    prompt: Some("Use ``` fences".to_string()),
+                    summary: None,
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn test_markdown_includes_conversation_and_diffs() {
+        let markdown = transcript().to_markdown();
+        assert!(markdown.starts_with("# Fix <script> handling\n"));
+        assert!(markdown.contains("## Run 1: claude-code (completed)"));
+        assert!(markdown.contains("### Assistant\n\nFixing it\n"));
+        assert!(
+            markdown.contains(
+                "### Tool: Edit\n\n`src/lib.rs`\n\n```diff\n@@ -1 +1 @@\n-old\n+new\n```\n"
+            )
+        );
+        assert!(markdown.contains("\n**Result:** failed (exit code 1)\n\n```\noops\n```\n"));
+    }
+
+    #[test]
+    fn test_fence_outgrows_content_backticks() {
+        assert_eq!(fence("no backticks"), "```");
+        assert_eq!(fence("Use ``` fences"), "````");
+    }
+
+    #[test]
+    fn test_html_escapes_content() {
+        let html = transcript().to_html();
+        assert!(html.contains("<title>Fix &lt;script&gt; handling</title>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<span class=\"del\">-old</span>"));
+        assert!(html.contains("<span class=\"add\">+new</span>"));
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            transcript().file_name(TranscriptFormat::Html),
+            "attempt-00000000-transcript.html"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Per-execution cgroups (cgroup v2, Linux only). Limits set on a cgroup hold for all of
+//! its processes together, and everything they start joins it.
+
+use std::path::{Path, PathBuf};
+
+/// Where the unified (v2) hierarchy is mounted when it is the only one
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// A cgroup created below vibe-kanban's own, for the processes of one execution
+#[derive(Debug)]
+pub struct ExecutionCgroup {
+    path: PathBuf,
+}
+
+impl ExecutionCgroup {
+    /// Create cgroup `name` with the given memory and process limits. Returns None where
+    /// cgroup v2 isn't used, vibe-kanban's cgroup can't be written to, or the memory and
+    /// pids controllers aren't delegated to it.
+    pub fn create(
+        name: &str,
+        max_memory_bytes: Option<u64>,
+        max_processes: Option<u64>,
+    ) -> Option<Self> {
+        if !Path::new(CGROUP_ROOT).join("cgroup.controllers").exists() {
+            return None;
+        }
+        let own = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+        let relative = own.lines().find_map(|line| line.strip_prefix("0::"))?;
+        let path = Path::new(CGROUP_ROOT)
+            .join(relative.trim_start_matches('/'))
+            .join(name);
+        if let Err(e) = std::fs::create_dir(&path) {
+            tracing::debug!("Failed to create cgroup {}: {}", path.display(), e);
+            return None;
+        }
+
+        let cgroup = Self { path };
+        let limits = [
+            ("memory.max", max_memory_bytes),
+            ("pids.max", max_processes),
+        ];
+        for (file, limit) in limits {
+            let Some(limit) = limit else { continue };
+            // The file only exists if the controller is enabled for our children
+            if let Err(e) = std::fs::write(cgroup.path.join(file), limit.to_string()) {
+                tracing::debug!("Failed to set {} of {}: {}", file, cgroup.path.display(), e);
+                cgroup.remove();
+                return None;
+            }
+        }
+        Some(cgroup)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// CPU time used by every process that has been in the cgroup, including exited ones
+    pub fn cpu_secs(&self) -> Option<f64> {
+        let stat = std::fs::read_to_string(self.path.join("cpu.stat")).ok()?;
+        parse_usage_usec(&stat).map(|usec| usec as f64 / 1_000_000.0)
+    }
+
+    /// Remove the cgroup. This fails while processes the execution left behind are still
+    /// in it, which then stay limited.
+    pub fn remove(self) {
+        if let Err(e) = std::fs::remove_dir(&self.path) {
+            tracing::debug!("Failed to remove cgroup {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+fn parse_usage_usec(cpu_stat: &str) -> Option<u64> {
+    cpu_stat
+        .lines()
+        .find_map(|line| line.strip_prefix("usage_usec "))
+        .and_then(|usec| usec.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cpu_usage() {
+        let stat = "usage_usec 2500000\nuser_usec 2000000\nsystem_usec 500000\n";
+        assert_eq!(parse_usage_usec(stat), Some(2_500_000));
+        assert_eq!(parse_usage_usec("user_usec 1\n"), None);
+    }
+}
@@ -4,11 +4,13 @@ use directories::ProjectDirs;
 
 pub mod assets;
 pub mod browser;
+pub mod cgroup;
 pub mod diff;
 pub mod log_msg;
 pub mod msg_store;
 pub mod path;
 pub mod port_file;
+pub mod process_stats;
 pub mod response;
 pub mod sentry;
 pub mod shell;
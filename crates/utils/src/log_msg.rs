@@ -6,7 +6,21 @@ pub const EV_STDOUT: &str = "stdout";
 pub const EV_STDERR: &str = "stderr";
 pub const EV_JSON_PATCH: &str = "json_patch";
 pub const EV_SESSION_ID: &str = "session_id";
+pub const EV_TOKEN_USAGE: &str = "token_usage";
 pub const EV_FINISHED: &str = "finished";
+/// Sent before a window of log entries with the index of its first entry
+pub const EV_ENTRIES_START: &str = "entries_start";
+
+/// Token counts reported by a coding agent for a single turn.
+/// Cache tokens are kept separate from `input_tokens`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub model: Option<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum LogMsg {
@@ -14,6 +28,7 @@ pub enum LogMsg {
     Stderr(String),
     JsonPatch(Patch),
     SessionId(String),
+    TokenUsage(TokenUsage),
     Finished,
 }
 
@@ -24,6 +39,7 @@ impl LogMsg {
             LogMsg::Stderr(_) => EV_STDERR,
             LogMsg::JsonPatch(_) => EV_JSON_PATCH,
             LogMsg::SessionId(_) => EV_SESSION_ID,
+            LogMsg::TokenUsage(_) => EV_TOKEN_USAGE,
             LogMsg::Finished => EV_FINISHED,
         }
     }
@@ -37,6 +53,10 @@ impl LogMsg {
                 Event::default().event(EV_JSON_PATCH).data(data)
             }
             LogMsg::SessionId(s) => Event::default().event(EV_SESSION_ID).data(s.clone()),
+            LogMsg::TokenUsage(usage) => {
+                let data = serde_json::to_string(usage).unwrap_or_else(|_| "{}".to_string());
+                Event::default().event(EV_TOKEN_USAGE).data(data)
+            }
             LogMsg::Finished => Event::default().event(EV_FINISHED).data(""),
         }
     }
@@ -52,6 +72,9 @@ impl LogMsg {
                 EV_JSON_PATCH.len() + json_len + OVERHEAD
             }
             LogMsg::SessionId(s) => EV_SESSION_ID.len() + s.len() + OVERHEAD,
+            LogMsg::TokenUsage(usage) => {
+                EV_TOKEN_USAGE.len() + usage.model.as_ref().map_or(0, |m| m.len()) + 32 + OVERHEAD
+            }
             LogMsg::Finished => EV_FINISHED.len() + OVERHEAD,
         }
     }
@@ -80,7 +80,7 @@ pub fn make_path_relative(path: &str, worktree_path: &str) -> String {
 
 /// Normalize macOS prefix /private/var/ and /private/tmp/ to their public aliases without resolving paths.
 /// This allows prefix normalization to work when the full paths don't exist.
-fn normalize_macos_private_alias<P: AsRef<Path>>(p: P) -> PathBuf {
+pub fn normalize_macos_private_alias<P: AsRef<Path>>(p: P) -> PathBuf {
     let p = p.as_ref();
     if cfg!(target_os = "macos")
         && let Some(s) = p.to_str()
@@ -102,6 +102,11 @@ fn normalize_macos_private_alias<P: AsRef<Path>>(p: P) -> PathBuf {
 }
 
 pub fn get_vibe_kanban_temp_dir() -> std::path::PathBuf {
+    // Allow tests to keep their worktrees out of the real temp dir
+    if let Ok(dir) = std::env::var("VIBE_KANBAN_TEMP_DIR") {
+        return std::path::PathBuf::from(dir);
+    }
+
     let dir_name = if cfg!(debug_assertions) {
         "vibe-kanban-dev"
     } else {
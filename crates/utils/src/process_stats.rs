@@ -0,0 +1,174 @@
+//! Live resource usage of a process group, read from `/proc` (Linux only)
+
+use std::time::Duration;
+
+use serde::Serialize;
+use ts_rs::TS;
+
+/// How long CPU time is measured over
+const SAMPLE_WINDOW: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProcessStats {
+    /// CPU usage of the whole group, where 100 is one fully used core
+    pub cpu_percent: f64,
+    /// Resident memory summed over the group's processes
+    #[ts(type = "number")]
+    pub rss_bytes: u64,
+    pub process_count: u32,
+}
+
+/// Totals of process group `pgid` at one point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessGroupUsage {
+    /// CPU time used by the group's live processes; processes that exited take theirs
+    /// with them
+    pub cpu_secs: f64,
+    pub rss_bytes: u64,
+    pub process_count: u32,
+}
+
+/// What process group `pgid` uses right now, without waiting for a CPU measurement.
+/// Returns None if the group has no processes left, or where `/proc` isn't available.
+pub fn process_group_usage(pgid: u32) -> Option<ProcessGroupUsage> {
+    #[cfg(target_os = "linux")]
+    {
+        let sample = linux::sample(pgid)?;
+        Some(ProcessGroupUsage {
+            cpu_secs: sample.cpu_ticks as f64 / linux::clock_ticks_per_sec() as f64,
+            rss_bytes: sample.rss_pages * linux::page_size(),
+            process_count: sample.process_count,
+        })
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pgid;
+        None
+    }
+}
+
+/// Usage of the processes in process group `pgid`. Returns None if the group has no
+/// processes left, or where `/proc` isn't available.
+pub async fn process_group_stats(pgid: u32) -> Option<ProcessStats> {
+    #[cfg(target_os = "linux")]
+    {
+        let before = linux::sample(pgid)?;
+        let started = std::time::Instant::now();
+        tokio::time::sleep(SAMPLE_WINDOW).await;
+        let after = linux::sample(pgid)?;
+
+        // Processes that exit during the window take their CPU time with them
+        let ticks = after.cpu_ticks.saturating_sub(before.cpu_ticks);
+        let cpu_secs = ticks as f64 / linux::clock_ticks_per_sec() as f64;
+        Some(ProcessStats {
+            cpu_percent: cpu_secs / started.elapsed().as_secs_f64() * 100.0,
+            rss_bytes: after.rss_pages * linux::page_size(),
+            process_count: after.process_count,
+        })
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (pgid, SAMPLE_WINDOW);
+        None
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Sample {
+    cpu_ticks: u64,
+    rss_pages: u64,
+    process_count: u32,
+}
+
+#[derive(Debug, PartialEq)]
+struct StatFields {
+    pgrp: u32,
+    cpu_ticks: u64,
+    rss_pages: u64,
+}
+
+/// Parse the fields we need from a `/proc/<pid>/stat` line. The command name can contain
+/// spaces and parentheses, so fields are counted from its closing parenthesis.
+fn parse_stat(stat: &str) -> Option<StatFields> {
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    // `fields[0]` is field 3 (state) in proc(5)
+    let field = |n: usize| fields.get(n - 3).and_then(|f| f.parse::<u64>().ok());
+    Some(StatFields {
+        pgrp: u32::try_from(field(5)?).ok()?,
+        cpu_ticks: field(14)? + field(15)?,
+        rss_pages: field(24)?,
+    })
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{Sample, parse_stat};
+
+    pub fn sample(pgid: u32) -> Option<Sample> {
+        let mut sample = Sample::default();
+        for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+            if !entry
+                .file_name()
+                .to_string_lossy()
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+            {
+                continue;
+            }
+            // The process may have exited since the directory was listed
+            let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+                continue;
+            };
+            if let Some(fields) = parse_stat(&stat)
+                && fields.pgrp == pgid
+            {
+                sample.cpu_ticks += fields.cpu_ticks;
+                sample.rss_pages += fields.rss_pages;
+                sample.process_count += 1;
+            }
+        }
+        (sample.process_count > 0).then_some(sample)
+    }
+
+    pub fn clock_ticks_per_sec() -> u64 {
+        // SAFETY: sysconf has no preconditions
+        let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        u64::try_from(ticks).ok().filter(|t| *t > 0).unwrap_or(100)
+    }
+
+    pub fn page_size() -> u64 {
+        // SAFETY: sysconf has no preconditions
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        u64::try_from(size).ok().filter(|s| *s > 0).unwrap_or(4096)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stat_with_spaces_in_command_name() {
+        let stat = "4242 (npm install (x)) S 1 4200 4200 0 -1 4194560 2370 0 0 0 \
+                    150 30 0 0 20 0 11 0 123456 987654321 2048 18446744073709551615";
+        assert_eq!(
+            parse_stat(stat),
+            Some(StatFields {
+                pgrp: 4200,
+                cpu_ticks: 180,
+                rss_pages: 2048,
+            })
+        );
+        assert_eq!(parse_stat("4242 (truncated"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn usage_counts_the_processes_of_the_group() {
+        // SAFETY: getpgrp has no preconditions
+        let pgid = unsafe { libc::getpgrp() } as u32;
+        let usage = process_group_usage(pgid).unwrap();
+        assert!(usage.process_count >= 1);
+        assert!(usage.rss_bytes > 0);
+    }
+}
@@ -18,9 +18,35 @@ pub fn get_shell_command() -> (&'static str, &'static str) {
     }
 }
 
+/// Quotes `arg` so the platform's shell passes it to the command as a single argument
+pub fn quote_arg(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_.,:/=@+%".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_string()
+    } else if cfg!(windows) {
+        format!("\"{}\"", arg.replace('"', "\"\""))
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
 /// Resolves the full path of an executable using the system's PATH environment variable.
 pub fn resolve_executable_path(executable: &str) -> Option<String> {
     which::which(executable)
         .ok()
         .map(|p| p.to_string_lossy().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(windows))]
+    #[test]
+    fn quotes_arguments_the_shell_would_split_or_expand() {
+        assert_eq!(quote_arg("abc-123"), "abc-123");
+        assert_eq!(quote_arg(""), "''");
+        assert_eq!(quote_arg("a b; rm -rf ~"), "'a b; rm -rf ~'");
+        assert_eq!(quote_arg("it's $HOME"), r"'it'\''s $HOME'");
+    }
+}
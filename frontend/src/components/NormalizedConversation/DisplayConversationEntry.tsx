@@ -22,6 +22,7 @@ import {
   type ActionType,
 } from 'shared/types.ts';
 import FileChangeRenderer from './FileChangeRenderer';
+import ToolResultRenderer from './ToolResultRenderer';
 
 type Props = {
   entry: NormalizedEntry;
@@ -160,6 +161,9 @@ function DisplayConversationEntry({ entry, index }: Props) {
         >)
       : null;
 
+  const toolResult =
+    entry.entry_type.type === 'tool_use' ? entry.entry_type.result : null;
+
   return (
     <div key={index} className="px-4 py-1">
       <div className="flex items-start gap-3">
@@ -233,6 +237,8 @@ function DisplayConversationEntry({ entry, index }: Props) {
                 change={change}
               />
             ))}
+
+          {toolResult && <ToolResultRenderer result={toolResult} />}
         </div>
       </div>
     </div>
@@ -0,0 +1,55 @@
+import { useState } from 'react';
+import { CheckCircle2, ChevronRight, ChevronUp, XCircle } from 'lucide-react';
+import type { ToolResult } from 'shared/types';
+
+type Props = {
+  result: ToolResult;
+};
+
+const ToolResultRenderer = ({ result }: Props) => {
+  const [expanded, setExpanded] = useState(false);
+
+  const label = result.success ? 'Succeeded' : 'Failed';
+  const exitCode =
+    result.exit_code !== null ? ` (exit code ${result.exit_code})` : '';
+
+  return (
+    <div className="mt-1 text-xs">
+      <div
+        className={`flex items-center gap-1 ${
+          result.success
+            ? 'text-green-700 dark:text-green-400'
+            : 'text-red-600 dark:text-red-400'
+        }`}
+      >
+        {result.success ? (
+          <CheckCircle2 className="h-3 w-3" />
+        ) : (
+          <XCircle className="h-3 w-3" />
+        )}
+        {label}
+        {exitCode}
+        {result.output && (
+          <button
+            onClick={() => setExpanded((prev) => !prev)}
+            className="ml-2 inline-flex items-center gap-1 text-muted-foreground hover:text-foreground transition-colors"
+          >
+            {expanded ? (
+              <ChevronUp className="h-3 w-3" />
+            ) : (
+              <ChevronRight className="h-3 w-3" />
+            )}
+            {expanded ? 'Hide output' : 'Show output'}
+          </button>
+        )}
+      </div>
+      {expanded && result.output && (
+        <pre className="mt-1 max-h-64 overflow-auto rounded bg-muted p-2 font-mono whitespace-pre-wrap break-words">
+          {result.output}
+        </pre>
+      )}
+    </div>
+  );
+};
+
+export default ToolResultRenderer;
@@ -133,8 +133,10 @@ const TaskDetailsProvider: FC<{
       (process: ExecutionProcess) =>
         (process.run_reason === 'codingagent' ||
           process.run_reason === 'setupscript' ||
-          process.run_reason === 'cleanupscript') &&
-        process.status === 'running'
+          process.run_reason === 'cleanupscript' ||
+          process.run_reason === 'testscript' ||
+          process.run_reason === 'pipelinestep') &&
+        (process.status === 'running' || process.status === 'queued')
     );
   }, [selectedAttempt, attemptData.processes, isStopping]);
 
@@ -1,6 +1,11 @@
 import { memo, useEffect, useRef } from 'react';
-import type { UnifiedLogEntry, ProcessStartPayload } from '@/types/logs';
+import type {
+  UnifiedLogEntry,
+  ProcessStartPayload,
+  LoadEarlierPayload,
+} from '@/types/logs';
 import type { NormalizedEntry } from 'shared/types';
+import { Button } from '@/components/ui/button';
 import StdoutEntry from './StdoutEntry';
 import StderrEntry from './StderrEntry';
 import ProcessStartCard from './ProcessStartCard';
@@ -13,6 +18,7 @@ interface LogEntryRowProps {
   setRowHeight?: (index: number, height: number) => void;
   isCollapsed?: boolean;
   onToggleCollapse?: (processId: string) => void;
+  onLoadEarlier?: (processId: string) => void;
 }
 
 function LogEntryRow({
@@ -22,6 +28,7 @@ function LogEntryRow({
   setRowHeight,
   isCollapsed,
   onToggleCollapse,
+  onLoadEarlier,
 }: LogEntryRowProps) {
   const rowRef = useRef<HTMLDivElement>(null);
 
@@ -55,6 +62,21 @@ function LogEntryRow({
                 onToggle={onToggleCollapse || (() => {})}
               />
             );
+          case 'load_earlier': {
+            const payload = entry.payload as LoadEarlierPayload;
+            return (
+              <div className="flex justify-center py-1">
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  className="text-xs text-muted-foreground"
+                  onClick={() => onLoadEarlier?.(payload.processId)}
+                >
+                  Load earlier ({payload.earlierCount} more)
+                </Button>
+              </div>
+            );
+          }
           default:
             return (
               <div className="text-red-500 text-xs">
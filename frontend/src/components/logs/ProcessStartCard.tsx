@@ -1,4 +1,14 @@
-import { Clock, Cog, Play, Terminal, Code, ChevronDown } from 'lucide-react';
+import {
+  Clock,
+  Cog,
+  Play,
+  Terminal,
+  Code,
+  ChevronDown,
+  SearchCheck,
+  FlaskConical,
+  ListChecks,
+} from 'lucide-react';
 import { cn } from '@/lib/utils';
 import type { ProcessStartPayload } from '@/types/logs';
 
@@ -23,6 +33,12 @@ function ProcessStartCard({
         return <Code className="h-4 w-4" />;
       case 'devserver':
         return <Play className="h-4 w-4" />;
+      case 'codereview':
+        return <SearchCheck className="h-4 w-4" />;
+      case 'testscript':
+        return <FlaskConical className="h-4 w-4" />;
+      case 'pipelinestep':
+        return <ListChecks className="h-4 w-4" />;
       default:
         return <Cog className="h-4 w-4" />;
     }
@@ -38,6 +54,12 @@ function ProcessStartCard({
         return 'Coding Agent';
       case 'devserver':
         return 'Dev Server';
+      case 'codereview':
+        return 'Code Review';
+      case 'testscript':
+        return 'Test Script';
+      case 'pipelinestep':
+        return 'Pipeline Step';
       default:
         return runReason;
     }
@@ -72,6 +94,7 @@ function ProcessStartCard({
             {getProcessIcon(payload.runReason)}
             <span className="font-medium">
               {getProcessLabel(payload.runReason)}
+              {payload.stepName && `: ${payload.stepName}`}
             </span>
           </div>
           <div className="flex items-center gap-1 text-muted-foreground">
@@ -82,11 +105,13 @@ function ProcessStartCard({
             className={`ml-auto text-xs px-2 py-1 rounded-full ${
               payload.status === 'running'
                 ? 'bg-blue-100 text-blue-700'
-                : payload.status === 'completed'
-                  ? 'bg-green-100 text-green-700'
-                  : payload.status === 'failed'
-                    ? 'bg-red-100 text-red-700'
-                    : 'bg-gray-100 text-gray-700'
+                : payload.status === 'queued'
+                  ? 'bg-amber-100 text-amber-700'
+                  : payload.status === 'completed'
+                    ? 'bg-green-100 text-green-700'
+                    : payload.status === 'failed'
+                      ? 'bg-red-100 text-red-700'
+                      : 'bg-gray-100 text-gray-700'
             }`}
           >
             {payload.status}
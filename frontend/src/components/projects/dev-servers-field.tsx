@@ -0,0 +1,139 @@
+import { Plus, X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import type { DevServer, DevServerHealthCheck } from 'shared/types';
+
+interface DevServersFieldProps {
+  value: DevServer[];
+  onChange: (devServers: DevServer[]) => void;
+  scriptPlaceholder?: string;
+}
+
+type HealthCheckType = DevServerHealthCheck['type'] | 'None';
+
+const emptyHealthCheck = (
+  type: HealthCheckType
+): DevServerHealthCheck | null => {
+  switch (type) {
+    case 'Url':
+      return { type: 'Url', url: '' };
+    case 'LogRegex':
+      return { type: 'LogRegex', pattern: '' };
+    default:
+      return null;
+  }
+};
+
+export function DevServersField({
+  value,
+  onChange,
+  scriptPlaceholder,
+}: DevServersFieldProps) {
+  const updateDevServer = (index: number, devServer: DevServer) => {
+    onChange(value.map((current, i) => (i === index ? devServer : current)));
+  };
+
+  const addDevServer = () => {
+    onChange([
+      ...value,
+      {
+        name: value.length === 0 ? 'dev' : '',
+        script: '',
+        health_check: null,
+      },
+    ]);
+  };
+
+  return (
+    <div className="space-y-3">
+      {value.map((devServer, index) => {
+        const healthCheck = devServer.health_check;
+        return (
+          <div key={index} className="border rounded-md p-3 space-y-2">
+            <div className="flex items-center gap-2">
+              <Input
+                value={devServer.name}
+                onChange={(e) =>
+                  updateDevServer(index, { ...devServer, name: e.target.value })
+                }
+                placeholder="Name, e.g. frontend"
+              />
+              <Select
+                value={healthCheck?.type ?? 'None'}
+                onValueChange={(type: HealthCheckType) =>
+                  updateDevServer(index, {
+                    ...devServer,
+                    health_check: emptyHealthCheck(type),
+                  })
+                }
+              >
+                <SelectTrigger className="w-44 flex-shrink-0">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="None">No health check</SelectItem>
+                  <SelectItem value="Url">Health check URL</SelectItem>
+                  <SelectItem value="LogRegex">Log pattern</SelectItem>
+                </SelectContent>
+              </Select>
+              <Button
+                type="button"
+                variant="ghost"
+                size="sm"
+                className="h-8 w-8 p-0 flex-shrink-0"
+                onClick={() => onChange(value.filter((_, i) => i !== index))}
+                title="Remove dev server"
+              >
+                <X className="h-4 w-4" />
+              </Button>
+            </div>
+            <textarea
+              value={devServer.script}
+              onChange={(e) =>
+                updateDevServer(index, { ...devServer, script: e.target.value })
+              }
+              placeholder={scriptPlaceholder}
+              rows={3}
+              className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md resize-vertical font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            {healthCheck && (
+              <Input
+                value={
+                  healthCheck.type === 'Url'
+                    ? healthCheck.url
+                    : healthCheck.pattern
+                }
+                onChange={(e) =>
+                  updateDevServer(index, {
+                    ...devServer,
+                    health_check:
+                      healthCheck.type === 'Url'
+                        ? { type: 'Url', url: e.target.value }
+                        : { type: 'LogRegex', pattern: e.target.value },
+                  })
+                }
+                placeholder={
+                  healthCheck.type === 'Url'
+                    ? 'http://localhost:3000/health'
+                    : 'ready in \\d+ ms'
+                }
+                className="font-mono text-sm"
+              />
+            )}
+          </div>
+        );
+      })}
+      <Button type="button" variant="outline" size="sm" onClick={addDevServer}>
+        <Plus className="h-4 w-4 mr-1" />
+        Add Dev Server
+      </Button>
+    </div>
+  );
+}
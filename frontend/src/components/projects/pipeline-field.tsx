@@ -0,0 +1,155 @@
+import { ArrowDown, ArrowUp, Plus, X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import type { PipelineStep, PipelineStepAction } from 'shared/types';
+
+interface PipelineFieldProps {
+  value: PipelineStep[];
+  onChange: (steps: PipelineStep[]) => void;
+}
+
+const emptyAction = (type: PipelineStepAction['type']): PipelineStepAction =>
+  type === 'Script'
+    ? { type: 'Script', script: '' }
+    : { type: 'AgentFollowUp', prompt: '' };
+
+export function PipelineField({ value, onChange }: PipelineFieldProps) {
+  const updateStep = (index: number, step: PipelineStep) => {
+    onChange(value.map((current, i) => (i === index ? step : current)));
+  };
+
+  const moveStep = (index: number, offset: number) => {
+    const steps = [...value];
+    const [step] = steps.splice(index, 1);
+    steps.splice(index + offset, 0, step);
+    onChange(steps);
+  };
+
+  const addStep = () => {
+    onChange([
+      ...value,
+      {
+        name: '',
+        action: emptyAction('Script'),
+        continue_on_failure: false,
+      },
+    ]);
+  };
+
+  return (
+    <div className="space-y-3">
+      {value.map((step, index) => (
+        <div key={index} className="border rounded-md p-3 space-y-2">
+          <div className="flex items-center gap-2">
+            <span className="text-sm text-muted-foreground w-5">
+              {index + 1}.
+            </span>
+            <Input
+              value={step.name}
+              onChange={(e) =>
+                updateStep(index, { ...step, name: e.target.value })
+              }
+              placeholder="Step name, e.g. lint"
+            />
+            <Select
+              value={step.action.type}
+              onValueChange={(type: PipelineStepAction['type']) =>
+                updateStep(index, { ...step, action: emptyAction(type) })
+              }
+            >
+              <SelectTrigger className="w-44 flex-shrink-0">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="Script">Script</SelectItem>
+                <SelectItem value="AgentFollowUp">Agent follow-up</SelectItem>
+              </SelectContent>
+            </Select>
+            <Button
+              type="button"
+              variant="ghost"
+              size="sm"
+              className="h-8 w-8 p-0 flex-shrink-0"
+              onClick={() => moveStep(index, -1)}
+              disabled={index === 0}
+              title="Move up"
+            >
+              <ArrowUp className="h-4 w-4" />
+            </Button>
+            <Button
+              type="button"
+              variant="ghost"
+              size="sm"
+              className="h-8 w-8 p-0 flex-shrink-0"
+              onClick={() => moveStep(index, 1)}
+              disabled={index === value.length - 1}
+              title="Move down"
+            >
+              <ArrowDown className="h-4 w-4" />
+            </Button>
+            <Button
+              type="button"
+              variant="ghost"
+              size="sm"
+              className="h-8 w-8 p-0 flex-shrink-0"
+              onClick={() => onChange(value.filter((_, i) => i !== index))}
+              title="Remove step"
+            >
+              <X className="h-4 w-4" />
+            </Button>
+          </div>
+          <textarea
+            value={
+              step.action.type === 'Script'
+                ? step.action.script
+                : step.action.prompt
+            }
+            onChange={(e) =>
+              updateStep(index, {
+                ...step,
+                action:
+                  step.action.type === 'Script'
+                    ? { type: 'Script', script: e.target.value }
+                    : { type: 'AgentFollowUp', prompt: e.target.value },
+              })
+            }
+            placeholder={
+              step.action.type === 'Script'
+                ? 'npm run lint'
+                : 'Fix the lint errors reported above'
+            }
+            rows={2}
+            className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md resize-vertical font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+          />
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id={`pipeline-step-${index}-continue`}
+              checked={step.continue_on_failure}
+              onCheckedChange={(checked: boolean) =>
+                updateStep(index, { ...step, continue_on_failure: checked })
+              }
+            />
+            <label
+              htmlFor={`pipeline-step-${index}-continue`}
+              className="text-sm cursor-pointer"
+            >
+              Continue on failure
+            </label>
+          </div>
+        </div>
+      ))}
+      <Button type="button" variant="outline" size="sm" onClick={addStep}>
+        <Plus className="h-4 w-4 mr-1" />
+        Add Step
+      </Button>
+    </div>
+  );
+}
@@ -1,7 +1,15 @@
 import { Label } from '@/components/ui/label';
 import { Input } from '@/components/ui/input';
 import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
 import { Alert, AlertDescription } from '@/components/ui/alert';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
 import { AlertCircle, Folder } from 'lucide-react';
 import {
   createScriptPlaceholderStrategy,
@@ -9,6 +17,117 @@ import {
 } from '@/utils/script-placeholders';
 import { useUserSystem } from '@/components/config-provider';
 import { CopyFilesField } from './copy-files-field';
+import { DevServersField } from './dev-servers-field';
+import { PipelineField } from './pipeline-field';
+import {
+  DevServer,
+  PipelineStep,
+  Project,
+  ProfileVariantLabel,
+  ScriptRequestLanguage,
+} from 'shared/types';
+
+export interface ExecutionLimitsInput {
+  maxDurationSecs: string;
+  maxIdleSecs: string;
+  maxToolCalls: string;
+  maxMemoryMb: string;
+  maxCpuSecs: string;
+  maxProcesses: string;
+  maxConcurrentAgents: string;
+  maxFixIterations: string;
+  scriptTimeoutSecs: string;
+}
+
+export function limitsFromProject(
+  project?: Project | null
+): ExecutionLimitsInput {
+  return {
+    maxDurationSecs: project?.max_duration_secs?.toString() ?? '',
+    maxIdleSecs: project?.max_idle_secs?.toString() ?? '',
+    maxToolCalls: project?.max_tool_calls?.toString() ?? '',
+    maxMemoryMb: project?.max_memory_mb?.toString() ?? '',
+    maxCpuSecs: project?.max_cpu_secs?.toString() ?? '',
+    maxProcesses: project?.max_processes?.toString() ?? '',
+    maxConcurrentAgents: project?.max_concurrent_agents?.toString() ?? '',
+    maxFixIterations: project?.max_fix_iterations?.toString() ?? '',
+    scriptTimeoutSecs: project?.script_timeout_secs?.toString() ?? '',
+  };
+}
+
+export function limitToNumber(value: string): number | null {
+  const limit = parseInt(value.trim(), 10);
+  return Number.isNaN(limit) || limit < 0 ? null : limit;
+}
+
+export function envVarsToText(project?: Project | null): string {
+  return Object.entries(project?.env_vars ?? {})
+    .map(([key, value]) => `${key}=${value}`)
+    .join('\n');
+}
+
+export function envVarsFromText(text: string): Record<string, string> {
+  const vars: Record<string, string> = {};
+  for (const line of text.split('\n')) {
+    const trimmed = line.trim();
+    if (!trimmed || trimmed.startsWith('#')) continue;
+    const separator = trimmed.indexOf('=');
+    if (separator <= 0) continue;
+    vars[trimmed.slice(0, separator).trim()] = trimmed.slice(separator + 1);
+  }
+  return vars;
+}
+
+const NO_PROFILE = '__none__';
+
+function profileToValue(label: ProfileVariantLabel | null): string {
+  if (!label) return NO_PROFILE;
+  return label.variant ? `${label.profile}/${label.variant}` : label.profile;
+}
+
+function profileFromValue(value: string): ProfileVariantLabel | null {
+  if (value === NO_PROFILE) return null;
+  const [profile, ...variant] = value.split('/');
+  return { profile, variant: variant.length ? variant.join('/') : null };
+}
+
+interface ProfileSelectProps {
+  id: string;
+  value: ProfileVariantLabel | null;
+  onChange: (profile: ProfileVariantLabel | null) => void;
+  noneLabel: string;
+}
+
+export function ProfileSelect({ id, value, onChange, noneLabel }: ProfileSelectProps) {
+  const { profiles } = useUserSystem();
+
+  return (
+    <Select
+      value={profileToValue(value)}
+      onValueChange={(selected) => onChange(profileFromValue(selected))}
+    >
+      <SelectTrigger id={id}>
+        <SelectValue placeholder={noneLabel} />
+      </SelectTrigger>
+      <SelectContent>
+        <SelectItem value={NO_PROFILE}>{noneLabel}</SelectItem>
+        {profiles?.flatMap((profile) => [
+          <SelectItem key={profile.label} value={profile.label}>
+            {profile.label}
+          </SelectItem>,
+          ...profile.variants.map((variant) => (
+            <SelectItem
+              key={`${profile.label}/${variant.label}`}
+              value={`${profile.label}/${variant.label}`}
+            >
+              {profile.label} / {variant.label}
+            </SelectItem>
+          )),
+        ])}
+      </SelectContent>
+    </Select>
+  );
+}
 
 interface ProjectFormFieldsProps {
   isEditing: boolean;
@@ -25,12 +144,32 @@ interface ProjectFormFieldsProps {
   name: string;
   setupScript: string;
   setSetupScript: (script: string) => void;
-  devScript: string;
-  setDevScript: (script: string) => void;
+  devServers: DevServer[];
+  setDevServers: (devServers: DevServer[]) => void;
   cleanupScript: string;
   setCleanupScript: (script: string) => void;
+  testScript: string;
+  setTestScript: (script: string) => void;
+  scriptLanguage: ScriptRequestLanguage;
+  setScriptLanguage: (language: ScriptRequestLanguage) => void;
+  scriptWorkingDir: string;
+  setScriptWorkingDir: (dir: string) => void;
   copyFiles: string;
   setCopyFiles: (files: string) => void;
+  limits: ExecutionLimitsInput;
+  setLimits: (limits: ExecutionLimitsInput) => void;
+  defaultProfile: ProfileVariantLabel | null;
+  setDefaultProfile: (profile: ProfileVariantLabel | null) => void;
+  reviewProfile: ProfileVariantLabel | null;
+  setReviewProfile: (profile: ProfileVariantLabel | null) => void;
+  envVars: string;
+  setEnvVars: (envVars: string) => void;
+  pipeline: PipelineStep[];
+  setPipeline: (pipeline: PipelineStep[]) => void;
+  sandboxed: boolean;
+  setSandboxed: (sandboxed: boolean) => void;
+  sandboxNetwork: boolean;
+  setSandboxNetwork: (sandboxNetwork: boolean) => void;
   error: string;
   projectId?: string;
 }
@@ -50,12 +189,32 @@ export function ProjectFormFields({
   name,
   setupScript,
   setSetupScript,
-  devScript,
-  setDevScript,
+  devServers,
+  setDevServers,
   cleanupScript,
   setCleanupScript,
+  testScript,
+  setTestScript,
+  scriptLanguage,
+  setScriptLanguage,
+  scriptWorkingDir,
+  setScriptWorkingDir,
   copyFiles,
   setCopyFiles,
+  limits,
+  setLimits,
+  defaultProfile,
+  setDefaultProfile,
+  reviewProfile,
+  setReviewProfile,
+  envVars,
+  setEnvVars,
+  pipeline,
+  setPipeline,
+  sandboxed,
+  setSandboxed,
+  sandboxNetwork,
+  setSandboxNetwork,
   error,
   projectId,
 }: ProjectFormFieldsProps) {
@@ -202,6 +361,40 @@ export function ProjectFormFields({
         />
       </div>
 
+      <div className="grid grid-cols-2 gap-4">
+        <div className="space-y-2">
+          <Label htmlFor="script-language">Script Language</Label>
+          <Select
+            value={scriptLanguage}
+            onValueChange={(value: ScriptRequestLanguage) =>
+              setScriptLanguage(value)
+            }
+          >
+            <SelectTrigger id="script-language">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="Bash">Bash</SelectItem>
+              <SelectItem value="Python">Python</SelectItem>
+              <SelectItem value="Node">Node.js</SelectItem>
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="space-y-2">
+          <Label htmlFor="script-working-dir">Working Directory</Label>
+          <Input
+            id="script-working-dir"
+            value={scriptWorkingDir}
+            onChange={(e) => setScriptWorkingDir(e.target.value)}
+            placeholder="Repository root"
+          />
+        </div>
+        <p className="col-span-2 text-sm text-muted-foreground">
+          The setup, dev server and cleanup scripts are written in this
+          language and run in this directory, relative to the repository root.
+        </p>
+      </div>
+
       <div className="space-y-2">
         <Label htmlFor="setup-script">Setup Script (Optional)</Label>
         <textarea
@@ -220,19 +413,16 @@ export function ProjectFormFields({
       </div>
 
       <div className="space-y-2">
-        <Label htmlFor="dev-script">Dev Server Script (Optional)</Label>
-        <textarea
-          id="dev-script"
-          value={devScript}
-          onChange={(e) => setDevScript(e.target.value)}
-          placeholder={placeholders.dev}
-          rows={4}
-          className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md resize-vertical focus:outline-none focus:ring-2 focus:ring-ring"
+        <Label>Dev Servers (Optional)</Label>
+        <DevServersField
+          value={devServers}
+          onChange={setDevServers}
+          scriptPlaceholder={placeholders.dev}
         />
         <p className="text-sm text-muted-foreground">
-          This script can be run from task attempts to start a development
-          server. Use it to quickly start your project's dev server for testing
-          changes.
+          These processes can be started together from task attempts, e.g. a
+          frontend and an API server. A dev server that crashes is restarted,
+          waiting longer after each crash in a row.
         </p>
       </div>
 
@@ -253,6 +443,72 @@ export function ProjectFormFields({
         </p>
       </div>
 
+      <div className="space-y-2">
+        <Label>Pipeline (Optional)</Label>
+        <PipelineField value={pipeline} onChange={setPipeline} />
+        <p className="text-sm text-muted-foreground">
+          Steps that run in order after each coding agent run, before the test
+          script. A failing step stops the pipeline unless it may continue on
+          failure.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <Label htmlFor="test-script">Test Script (Optional)</Label>
+        <textarea
+          id="test-script"
+          value={testScript}
+          onChange={(e) => setTestScript(e.target.value)}
+          placeholder={'#!/bin/bash\nnpm test'}
+          rows={4}
+          className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md resize-vertical focus:outline-none focus:ring-2 focus:ring-ring"
+        />
+        <p className="text-sm text-muted-foreground">
+          This script runs after each coding agent run. When it fails, the
+          agent is sent the end of its output to fix, and the script runs
+          again.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <Label htmlFor="max-fix-iterations">
+          Max Fix Iterations (Optional)
+        </Label>
+        <Input
+          id="max-fix-iterations"
+          type="number"
+          min={0}
+          value={limits.maxFixIterations}
+          onChange={(e) =>
+            setLimits({ ...limits, maxFixIterations: e.target.value })
+          }
+          placeholder="3"
+        />
+        <p className="text-sm text-muted-foreground">
+          Fixes the agent gets in a row while the test script keeps failing.
+          After that the attempt is left for review. Use 0 to only run the
+          tests.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <Label htmlFor="script-timeout">Script Timeout (Optional)</Label>
+        <Input
+          id="script-timeout"
+          type="number"
+          min={1}
+          value={limits.scriptTimeoutSecs}
+          onChange={(e) =>
+            setLimits({ ...limits, scriptTimeoutSecs: e.target.value })
+          }
+          placeholder="No timeout"
+        />
+        <p className="text-sm text-muted-foreground">
+          Seconds the setup and cleanup scripts may run for before they are
+          stopped. The dev server runs until you stop it.
+        </p>
+      </div>
+
       <div className="space-y-2">
         <Label htmlFor="copy-files">Copy Files (Optional)</Label>
         <CopyFilesField
@@ -269,6 +525,188 @@ export function ProjectFormFields({
         </p>
       </div>
 
+      <div className="space-y-2">
+        <Label>Execution Limits (Optional)</Label>
+        <div className="grid grid-cols-3 gap-2">
+          <Input
+            id="max-duration-secs"
+            type="number"
+            min={0}
+            value={limits.maxDurationSecs}
+            onChange={(e) =>
+              setLimits({ ...limits, maxDurationSecs: e.target.value })
+            }
+            placeholder="Max duration (s)"
+          />
+          <Input
+            id="max-idle-secs"
+            type="number"
+            min={0}
+            value={limits.maxIdleSecs}
+            onChange={(e) =>
+              setLimits({ ...limits, maxIdleSecs: e.target.value })
+            }
+            placeholder="Max idle (s)"
+          />
+          <Input
+            id="max-tool-calls"
+            type="number"
+            min={0}
+            value={limits.maxToolCalls}
+            onChange={(e) =>
+              setLimits({ ...limits, maxToolCalls: e.target.value })
+            }
+            placeholder="Max tool calls"
+          />
+        </div>
+        <p className="text-sm text-muted-foreground">
+          Coding agents are stopped when they run longer than the max duration,
+          produce no output for longer than the max idle time, or make more
+          tool calls than allowed. Limits set in the agent profile also apply;
+          the stricter one wins.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <Label>Resource Limits (Optional)</Label>
+        <div className="grid grid-cols-3 gap-2">
+          <Input
+            id="max-memory-mb"
+            type="number"
+            min={0}
+            value={limits.maxMemoryMb}
+            onChange={(e) =>
+              setLimits({ ...limits, maxMemoryMb: e.target.value })
+            }
+            placeholder="Max memory (MB)"
+          />
+          <Input
+            id="max-cpu-secs"
+            type="number"
+            min={0}
+            value={limits.maxCpuSecs}
+            onChange={(e) =>
+              setLimits({ ...limits, maxCpuSecs: e.target.value })
+            }
+            placeholder="Max CPU time (s)"
+          />
+          <Input
+            id="max-processes"
+            type="number"
+            min={0}
+            value={limits.maxProcesses}
+            onChange={(e) =>
+              setLimits({ ...limits, maxProcesses: e.target.value })
+            }
+            placeholder="Max processes"
+          />
+        </div>
+        <p className="text-sm text-muted-foreground">
+          Shared by all processes of each script or coding agent run (Linux
+          only). A run that uses more is stopped.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <Label htmlFor="max-concurrent-agents">
+          Max Concurrent Agents (Optional)
+        </Label>
+        <Input
+          id="max-concurrent-agents"
+          type="number"
+          min={1}
+          value={limits.maxConcurrentAgents}
+          onChange={(e) =>
+            setLimits({ ...limits, maxConcurrentAgents: e.target.value })
+          }
+          placeholder="Unlimited"
+        />
+        <p className="text-sm text-muted-foreground">
+          Coding agents of this project that run at once. Further attempts are
+          queued and start as running ones finish.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <Label htmlFor="default-profile">Default Agent Profile (Optional)</Label>
+        <ProfileSelect
+          id="default-profile"
+          value={defaultProfile}
+          onChange={setDefaultProfile}
+          noneLabel="Use global default"
+        />
+        <p className="text-sm text-muted-foreground">
+          Agent profile used for new attempts in this project when none is
+          chosen explicitly.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <Label htmlFor="review-profile">Review Profile (Optional)</Label>
+        <ProfileSelect
+          id="review-profile"
+          value={reviewProfile}
+          onChange={setReviewProfile}
+          noneLabel="No review"
+        />
+        <p className="text-sm text-muted-foreground">
+          When a coding agent finishes with changes, an agent with this profile
+          reviews the diff in the same worktree. Its findings are listed with
+          the attempt's logs.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <Label htmlFor="env-vars">Environment Variables (Optional)</Label>
+        <textarea
+          id="env-vars"
+          value={envVars}
+          onChange={(e) => setEnvVars(e.target.value)}
+          placeholder={'API_URL=http://localhost:3000\nNODE_ENV=development'}
+          rows={3}
+          className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md resize-vertical font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+        />
+        <p className="text-sm text-muted-foreground">
+          One KEY=VALUE per line. These are set for the setup script, dev
+          server, cleanup script and coding agents of this project.
+        </p>
+      </div>
+
+      <div className="space-y-3">
+        <div className="flex items-center space-x-2">
+          <Checkbox
+            id="sandboxed"
+            checked={sandboxed}
+            onCheckedChange={(checked: boolean) => setSandboxed(checked)}
+          />
+          <div className="space-y-0.5">
+            <Label htmlFor="sandboxed" className="cursor-pointer">
+              Run in Sandbox (Linux)
+            </Label>
+            <p className="text-sm text-muted-foreground">
+              Coding agents and scripts can only write to the worktree, the
+              agent's config directory and a private /tmp, and can't read the
+              rest of your home directory. Needs bubblewrap (bwrap) to be
+              installed.
+            </p>
+          </div>
+        </div>
+        {sandboxed && (
+          <div className="flex items-center space-x-2 ml-6">
+            <Checkbox
+              id="sandbox-network"
+              checked={sandboxNetwork}
+              onCheckedChange={(checked: boolean) =>
+                setSandboxNetwork(checked)
+              }
+            />
+            <Label htmlFor="sandbox-network" className="cursor-pointer">
+              Allow network access
+            </Label>
+          </div>
+        )}
+      </div>
+
       {error && (
         <Alert variant="destructive">
           <AlertCircle className="h-4 w-4" />
@@ -11,8 +11,23 @@ import {
 import { Tabs, TabsContent, TabsList, TabsTrigger } from '@/components/ui/tabs';
 import { FolderPicker } from '@/components/ui/folder-picker';
 import { TaskTemplateManager } from '@/components/TaskTemplateManager';
-import { ProjectFormFields } from './project-form-fields';
-import { CreateProject, Project, UpdateProject } from 'shared/types';
+import {
+  ExecutionLimitsInput,
+  ProjectFormFields,
+  envVarsFromText,
+  envVarsToText,
+  limitsFromProject,
+  limitToNumber,
+} from './project-form-fields';
+import {
+  CreateProject,
+  DevServer,
+  PipelineStep,
+  ProfileVariantLabel,
+  Project,
+  ScriptRequestLanguage,
+  UpdateProject,
+} from 'shared/types';
 import { projectsApi } from '@/lib/api';
 
 interface ProjectFormProps {
@@ -31,11 +46,35 @@ export function ProjectForm({
   const [name, setName] = useState(project?.name || '');
   const [gitRepoPath, setGitRepoPath] = useState(project?.git_repo_path || '');
   const [setupScript, setSetupScript] = useState(project?.setup_script ?? '');
-  const [devScript, setDevScript] = useState(project?.dev_script ?? '');
+  const [devServers, setDevServers] = useState<DevServer[]>(
+    project?.dev_servers ?? []
+  );
   const [cleanupScript, setCleanupScript] = useState(
     project?.cleanup_script ?? ''
   );
+  const [testScript, setTestScript] = useState(project?.test_script ?? '');
+  const [scriptLanguage, setScriptLanguage] = useState<ScriptRequestLanguage>(
+    project?.script_language ?? 'Bash'
+  );
+  const [scriptWorkingDir, setScriptWorkingDir] = useState(
+    project?.script_working_dir ?? ''
+  );
   const [copyFiles, setCopyFiles] = useState(project?.copy_files ?? '');
+  const [limits, setLimits] = useState<ExecutionLimitsInput>(
+    limitsFromProject(project)
+  );
+  const [defaultProfile, setDefaultProfile] =
+    useState<ProfileVariantLabel | null>(project?.default_profile ?? null);
+  const [reviewProfile, setReviewProfile] =
+    useState<ProfileVariantLabel | null>(project?.review_profile ?? null);
+  const [envVars, setEnvVars] = useState(envVarsToText(project));
+  const [pipeline, setPipeline] = useState<PipelineStep[]>(
+    project?.pipeline ?? []
+  );
+  const [sandboxed, setSandboxed] = useState(project?.sandboxed ?? false);
+  const [sandboxNetwork, setSandboxNetwork] = useState(
+    project?.sandbox_network ?? false
+  );
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState('');
   const [showFolderPicker, setShowFolderPicker] = useState(false);
@@ -51,16 +90,36 @@ export function ProjectForm({
       setName(project.name || '');
       setGitRepoPath(project.git_repo_path || '');
       setSetupScript(project.setup_script ?? '');
-      setDevScript(project.dev_script ?? '');
+      setDevServers(project.dev_servers);
       setCleanupScript(project.cleanup_script ?? '');
+      setTestScript(project.test_script ?? '');
+      setScriptLanguage(project.script_language ?? 'Bash');
+      setScriptWorkingDir(project.script_working_dir ?? '');
       setCopyFiles(project.copy_files ?? '');
+      setLimits(limitsFromProject(project));
+      setDefaultProfile(project.default_profile ?? null);
+      setReviewProfile(project.review_profile ?? null);
+      setEnvVars(envVarsToText(project));
+      setPipeline(project.pipeline);
+      setSandboxed(project.sandboxed);
+      setSandboxNetwork(project.sandbox_network);
     } else {
       setName('');
       setGitRepoPath('');
       setSetupScript('');
-      setDevScript('');
+      setDevServers([]);
       setCleanupScript('');
+      setTestScript('');
+      setScriptLanguage('Bash');
+      setScriptWorkingDir('');
       setCopyFiles('');
+      setLimits(limitsFromProject(null));
+      setDefaultProfile(null);
+      setReviewProfile(null);
+      setEnvVars('');
+      setPipeline([]);
+      setSandboxed(false);
+      setSandboxNetwork(false);
     }
   }, [project]);
 
@@ -99,9 +158,27 @@ export function ProjectForm({
           name,
           git_repo_path: finalGitRepoPath,
           setup_script: setupScript.trim() || null,
-          dev_script: devScript.trim() || null,
+          dev_servers: devServers,
           cleanup_script: cleanupScript.trim() || null,
+          test_script: testScript.trim() || null,
+          script_language: scriptLanguage,
+          script_working_dir: scriptWorkingDir.trim() || null,
+          script_timeout_secs: limitToNumber(limits.scriptTimeoutSecs),
           copy_files: copyFiles.trim() || null,
+          max_duration_secs: limitToNumber(limits.maxDurationSecs),
+          max_idle_secs: limitToNumber(limits.maxIdleSecs),
+          max_tool_calls: limitToNumber(limits.maxToolCalls),
+          max_memory_mb: limitToNumber(limits.maxMemoryMb),
+          max_cpu_secs: limitToNumber(limits.maxCpuSecs),
+          max_processes: limitToNumber(limits.maxProcesses),
+          max_concurrent_agents: limitToNumber(limits.maxConcurrentAgents),
+          max_fix_iterations: limitToNumber(limits.maxFixIterations),
+          default_profile: defaultProfile,
+          review_profile: reviewProfile,
+          env_vars: envVarsFromText(envVars),
+          pipeline,
+          sandboxed,
+          sandbox_network: sandboxNetwork,
         };
 
         await projectsApi.update(project.id, updateData);
@@ -137,9 +214,27 @@ export function ProjectForm({
           git_repo_path: finalGitRepoPath,
           use_existing_repo: repoMode === 'existing',
           setup_script: setupScript.trim() || null,
-          dev_script: devScript.trim() || null,
+          dev_servers: devServers,
           cleanup_script: cleanupScript.trim() || null,
+          test_script: testScript.trim() || null,
+          script_language: scriptLanguage,
+          script_working_dir: scriptWorkingDir.trim() || null,
+          script_timeout_secs: limitToNumber(limits.scriptTimeoutSecs),
           copy_files: copyFiles.trim() || null,
+          max_duration_secs: limitToNumber(limits.maxDurationSecs),
+          max_idle_secs: limitToNumber(limits.maxIdleSecs),
+          max_tool_calls: limitToNumber(limits.maxToolCalls),
+          max_memory_mb: limitToNumber(limits.maxMemoryMb),
+          max_cpu_secs: limitToNumber(limits.maxCpuSecs),
+          max_processes: limitToNumber(limits.maxProcesses),
+          max_concurrent_agents: limitToNumber(limits.maxConcurrentAgents),
+          max_fix_iterations: limitToNumber(limits.maxFixIterations),
+          default_profile: defaultProfile,
+          review_profile: reviewProfile,
+          env_vars: envVarsFromText(envVars),
+          pipeline,
+          sandboxed,
+          sandbox_network: sandboxNetwork,
         };
 
         await projectsApi.create(createData);
@@ -151,9 +246,19 @@ export function ProjectForm({
       setName('');
       setGitRepoPath('');
       setSetupScript('');
-      setDevScript('');
+      setDevServers([]);
       setCleanupScript('');
+      setTestScript('');
+      setScriptLanguage('Bash');
+      setScriptWorkingDir('');
       setCopyFiles('');
+      setLimits(limitsFromProject(null));
+      setDefaultProfile(null);
+      setReviewProfile(null);
+      setEnvVars('');
+      setPipeline([]);
+      setSandboxed(false);
+      setSandboxNetwork(false);
       setParentPath('');
       setFolderName('');
     } catch (error) {
@@ -168,14 +273,28 @@ export function ProjectForm({
       setName(project.name || '');
       setGitRepoPath(project.git_repo_path || '');
       setSetupScript(project.setup_script ?? '');
-      setDevScript(project.dev_script ?? '');
+      setDevServers(project.dev_servers);
       setCopyFiles(project.copy_files ?? '');
+      setLimits(limitsFromProject(project));
+      setDefaultProfile(project.default_profile ?? null);
+      setReviewProfile(project.review_profile ?? null);
+      setEnvVars(envVarsToText(project));
+      setPipeline(project.pipeline);
+      setSandboxed(project.sandboxed);
+      setSandboxNetwork(project.sandbox_network);
     } else {
       setName('');
       setGitRepoPath('');
       setSetupScript('');
-      setDevScript('');
+      setDevServers([]);
       setCopyFiles('');
+      setLimits(limitsFromProject(null));
+      setDefaultProfile(null);
+      setReviewProfile(null);
+      setEnvVars('');
+      setPipeline([]);
+      setSandboxed(false);
+      setSandboxNetwork(false);
     }
     setParentPath('');
     setFolderName('');
@@ -222,12 +341,32 @@ export function ProjectForm({
                   name={name}
                   setupScript={setupScript}
                   setSetupScript={setSetupScript}
-                  devScript={devScript}
-                  setDevScript={setDevScript}
+                  devServers={devServers}
+                  setDevServers={setDevServers}
                   cleanupScript={cleanupScript}
                   setCleanupScript={setCleanupScript}
+                  testScript={testScript}
+                  setTestScript={setTestScript}
+                  scriptLanguage={scriptLanguage}
+                  setScriptLanguage={setScriptLanguage}
+                  scriptWorkingDir={scriptWorkingDir}
+                  setScriptWorkingDir={setScriptWorkingDir}
                   copyFiles={copyFiles}
                   setCopyFiles={setCopyFiles}
+                  limits={limits}
+                  setLimits={setLimits}
+                  defaultProfile={defaultProfile}
+                  setDefaultProfile={setDefaultProfile}
+                  reviewProfile={reviewProfile}
+                  setReviewProfile={setReviewProfile}
+                  envVars={envVars}
+                  setEnvVars={setEnvVars}
+                  pipeline={pipeline}
+                  setPipeline={setPipeline}
+                  sandboxed={sandboxed}
+                  setSandboxed={setSandboxed}
+                  sandboxNetwork={sandboxNetwork}
+                  setSandboxNetwork={setSandboxNetwork}
                   error={error}
                   projectId={(project as any)?.id}
                 />
@@ -333,12 +472,32 @@ export function ProjectForm({
               name={name}
               setupScript={setupScript}
               setSetupScript={setSetupScript}
-              devScript={devScript}
-              setDevScript={setDevScript}
+              devServers={devServers}
+              setDevServers={setDevServers}
               cleanupScript={cleanupScript}
               setCleanupScript={setCleanupScript}
+              testScript={testScript}
+              setTestScript={setTestScript}
+              scriptLanguage={scriptLanguage}
+              setScriptLanguage={setScriptLanguage}
+              scriptWorkingDir={scriptWorkingDir}
+              setScriptWorkingDir={setScriptWorkingDir}
               copyFiles={copyFiles}
               setCopyFiles={setCopyFiles}
+              limits={limits}
+              setLimits={setLimits}
+              defaultProfile={defaultProfile}
+              setDefaultProfile={setDefaultProfile}
+              reviewProfile={reviewProfile}
+              setReviewProfile={setReviewProfile}
+              envVars={envVars}
+              setEnvVars={setEnvVars}
+              pipeline={pipeline}
+              setPipeline={setPipeline}
+              sandboxed={sandboxed}
+              setSandboxed={setSandboxed}
+              sandboxNetwork={sandboxNetwork}
+              setSandboxNetwork={setSandboxNetwork}
               error={error}
               projectId={(project as any)?.id}
             />
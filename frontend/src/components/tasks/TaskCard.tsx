@@ -9,8 +9,10 @@ import {
 import { KanbanCard } from '@/components/ui/shadcn-io/kanban';
 import {
   CheckCircle,
+  ClipboardList,
   Edit,
   Loader2,
+  Lock,
   MoreHorizontal,
   Trash2,
   XCircle,
@@ -83,6 +85,13 @@ export function TaskCard({
             </div>
           </div>
           <div className="flex items-center space-x-1">
+            {/* Blocked Indicator */}
+            {task.is_blocked && (
+              <Lock
+                className="h-3 w-3 text-muted-foreground"
+                aria-label="Blocked by unfinished tasks"
+              />
+            )}
             {/* In Progress Spinner */}
             {task.has_in_progress_attempt && (
               <Loader2 className="h-3 w-3 animate-spin text-blue-500" />
@@ -91,6 +100,10 @@ export function TaskCard({
             {task.has_merged_attempt && (
               <CheckCircle className="h-3 w-3 text-green-500" />
             )}
+            {/* Plan Awaiting Approval Indicator */}
+            {task.awaiting_plan_approval && (
+              <ClipboardList className="h-3 w-3 text-amber-500" />
+            )}
             {/* Failed Indicator */}
             {task.last_attempt_failed && !task.has_merged_attempt && (
               <XCircle className="h-3 w-3 text-red-500" />
@@ -16,6 +16,9 @@ import { useProcessesLogs } from '@/hooks/useProcessesLogs';
 import { usePinnedTodos } from '@/hooks/usePinnedTodos';
 import LogEntryRow from '@/components/logs/LogEntryRow';
 import { PinnedTodoBox } from '@/components/PinnedTodoBox';
+import { PendingApprovals } from './PendingApprovals';
+import { PendingPlanReview } from './PendingPlanReview';
+import { ReviewComments } from './ReviewComments';
 import {
   shouldShowInLogs,
   isAutoCollapsibleProcess,
@@ -137,7 +140,10 @@ function LogsTab() {
     [attemptData.processes?.map((p) => p.id).join(',')]
   );
 
-  const { entries } = useProcessesLogs(filteredProcesses, true);
+  const { entries, loadEarlier } = useProcessesLogs(
+    filteredProcesses,
+    true
+  );
 
   // Extract todos from entries using the usePinnedTodos hook
   const { todos, lastUpdated } = usePinnedTodos(entries);
@@ -264,9 +270,10 @@ function LogsTab() {
         onToggleCollapse={
           entry.channel === 'process_start' ? toggleProcessCollapse : undefined
         }
+        onLoadEarlier={loadEarlier}
       />
     ),
-    [allCollapsedProcesses, toggleProcessCollapse]
+    [allCollapsedProcesses, toggleProcessCollapse, loadEarlier]
   );
 
   if (!filteredProcesses || filteredProcesses.length === 0) {
@@ -283,6 +290,13 @@ function LogsTab() {
   return (
     <div className="w-full h-full flex flex-col">
       <PinnedTodoBox todos={todos} lastUpdated={lastUpdated} />
+      {selectedAttempt && (
+        <PendingApprovals attemptId={selectedAttempt.id} />
+      )}
+      {selectedAttempt && (
+        <PendingPlanReview attemptId={selectedAttempt.id} />
+      )}
+      {selectedAttempt && <ReviewComments attemptId={selectedAttempt.id} />}
       <div className="flex-1">
         <Virtuoso
           ref={virtuosoRef}
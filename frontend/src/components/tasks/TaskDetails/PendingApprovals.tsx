@@ -0,0 +1,87 @@
+import { useCallback, useEffect, useState } from 'react';
+import { ShieldAlert } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { approvalsApi, attemptsApi } from '@/lib/api';
+import type { ToolApproval } from 'shared/types';
+
+interface PendingApprovalsProps {
+  attemptId: string;
+}
+
+const POLL_INTERVAL_MS = 2000;
+
+const describeToolInput = (approval: ToolApproval): string => {
+  const input = approval.tool_input as Record<string, unknown> | null;
+  const summary = input?.command ?? input?.file_path ?? input?.notebook_path;
+  return typeof summary === 'string' ? summary : JSON.stringify(input);
+};
+
+export function PendingApprovals({ attemptId }: PendingApprovalsProps) {
+  const [approvals, setApprovals] = useState<ToolApproval[]>([]);
+  const [respondingId, setRespondingId] = useState<string | null>(null);
+
+  const fetchApprovals = useCallback(async () => {
+    try {
+      const all = await attemptsApi.getApprovals(attemptId);
+      setApprovals(all.filter((approval) => approval.status === 'pending'));
+    } catch (err) {
+      console.error('Failed to load tool approvals:', err);
+    }
+  }, [attemptId]);
+
+  useEffect(() => {
+    fetchApprovals();
+    const interval = setInterval(fetchApprovals, POLL_INTERVAL_MS);
+    return () => clearInterval(interval);
+  }, [fetchApprovals]);
+
+  const respond = async (approvalId: string, approved: boolean) => {
+    setRespondingId(approvalId);
+    try {
+      await approvalsApi.respond(approvalId, { approved, reason: null });
+      await fetchApprovals();
+    } catch (err) {
+      console.error('Failed to respond to tool approval:', err);
+    } finally {
+      setRespondingId(null);
+    }
+  };
+
+  if (approvals.length === 0) return null;
+
+  return (
+    <div className="border border-amber-300 dark:border-amber-800 bg-amber-50 dark:bg-amber-950/40">
+      {approvals.map((approval) => (
+        <div
+          key={approval.id}
+          className="flex items-center gap-3 px-4 py-3 text-sm border-b last:border-b-0 border-amber-200 dark:border-amber-900"
+        >
+          <ShieldAlert className="h-4 w-4 flex-shrink-0 text-amber-600" />
+          <div className="flex-1 min-w-0">
+            <div className="font-medium">
+              {approval.tool_name} is waiting for approval
+            </div>
+            <div className="font-mono text-xs text-muted-foreground break-all">
+              {describeToolInput(approval)}
+            </div>
+          </div>
+          <Button
+            size="sm"
+            variant="outline"
+            disabled={respondingId === approval.id}
+            onClick={() => respond(approval.id, false)}
+          >
+            Deny
+          </Button>
+          <Button
+            size="sm"
+            disabled={respondingId === approval.id}
+            onClick={() => respond(approval.id, true)}
+          >
+            Approve
+          </Button>
+        </div>
+      ))}
+    </div>
+  );
+}
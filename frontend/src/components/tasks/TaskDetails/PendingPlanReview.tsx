@@ -0,0 +1,96 @@
+import { useCallback, useEffect, useState } from 'react';
+import { ClipboardList } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Textarea } from '@/components/ui/textarea';
+import MarkdownRenderer from '@/components/ui/markdown-renderer.tsx';
+import { attemptsApi, planReviewsApi } from '@/lib/api';
+import type { PlanReview } from 'shared/types';
+
+interface PendingPlanReviewProps {
+  attemptId: string;
+}
+
+const POLL_INTERVAL_MS = 2000;
+
+export function PendingPlanReview({ attemptId }: PendingPlanReviewProps) {
+  const [planReview, setPlanReview] = useState<PlanReview | null>(null);
+  const [editedPlan, setEditedPlan] = useState<string | null>(null);
+  const [responding, setResponding] = useState(false);
+
+  const fetchPlanReview = useCallback(async () => {
+    try {
+      const latest = await attemptsApi.getPlanReview(attemptId);
+      setPlanReview(latest?.status === 'pending' ? latest : null);
+    } catch (err) {
+      console.error('Failed to load plan review:', err);
+    }
+  }, [attemptId]);
+
+  useEffect(() => {
+    fetchPlanReview();
+    const interval = setInterval(fetchPlanReview, POLL_INTERVAL_MS);
+    return () => clearInterval(interval);
+  }, [fetchPlanReview]);
+
+  const respond = async (approved: boolean) => {
+    if (!planReview) return;
+    setResponding(true);
+    try {
+      await planReviewsApi.respond(planReview.id, {
+        approved,
+        plan: approved ? editedPlan : null,
+      });
+      setEditedPlan(null);
+      await fetchPlanReview();
+    } catch (err) {
+      console.error('Failed to respond to plan review:', err);
+    } finally {
+      setResponding(false);
+    }
+  };
+
+  if (!planReview) return null;
+
+  return (
+    <div className="border border-amber-300 dark:border-amber-800 bg-amber-50 dark:bg-amber-950/40 px-4 py-3 text-sm space-y-2">
+      <div className="flex items-center gap-3">
+        <ClipboardList className="h-4 w-4 flex-shrink-0 text-amber-600" />
+        <div className="flex-1 font-medium">Plan is waiting for approval</div>
+        {editedPlan === null && (
+          <Button
+            size="sm"
+            variant="ghost"
+            disabled={responding}
+            onClick={() => setEditedPlan(planReview.plan)}
+          >
+            Edit
+          </Button>
+        )}
+        <Button
+          size="sm"
+          variant="outline"
+          disabled={responding}
+          onClick={() => respond(false)}
+        >
+          Reject
+        </Button>
+        <Button size="sm" disabled={responding} onClick={() => respond(true)}>
+          Approve
+        </Button>
+      </div>
+      {editedPlan === null ? (
+        <MarkdownRenderer
+          content={planReview.plan}
+          className="whitespace-pre-wrap break-words max-h-64 overflow-auto"
+        />
+      ) : (
+        <Textarea
+          value={editedPlan}
+          onChange={(e) => setEditedPlan(e.target.value)}
+          className="font-mono text-xs min-h-[160px]"
+          disabled={responding}
+        />
+      )}
+    </div>
+  );
+}
@@ -1,4 +1,4 @@
-import { useState, useRef } from 'react';
+import { useEffect, useState, useRef } from 'react';
 import {
   Play,
   Square,
@@ -8,10 +8,33 @@ import {
   ChevronDown,
   ChevronRight,
 } from 'lucide-react';
-import type { ExecutionProcessStatus, ExecutionProcess } from 'shared/types';
+import type {
+  ExecutionProcessStatus,
+  ExecutionProcess,
+  ProcessStats,
+} from 'shared/types';
+import { executionProcessesApi } from '@/lib/api';
 import { useLogStream } from '@/hooks/useLogStream';
 import { useProcessConversation } from '@/hooks/useProcessConversation';
 import DisplayConversationEntry from '@/components/NormalizedConversation/DisplayConversationEntry';
+import { stopReasonLabel } from '@/utils/stop-reason';
+import { processStatusLabel } from '@/constants/processes';
+
+const STATS_POLL_INTERVAL_MS = 3000;
+
+function formatBytes(bytes: number) {
+  const mb = bytes / (1024 * 1024);
+  return mb >= 1024 ? `${(mb / 1024).toFixed(1)} GB` : `${Math.round(mb)} MB`;
+}
+
+function formatStats(stats: ProcessStats) {
+  const processes = stats.process_count === 1 ? 'process' : 'processes';
+  return [
+    `${Math.round(stats.cpu_percent)}% CPU`,
+    formatBytes(stats.rss_bytes),
+    `${stats.process_count} ${processes}`,
+  ].join(' · ');
+}
 
 interface ProcessCardProps {
   process: ExecutionProcess;
@@ -33,12 +56,33 @@ function ProcessCard({ process }: ProcessCardProps) {
     error: normalizedError,
   } = useProcessConversation(process.id, showLogs && isCodingAgent);
 
+  const [stats, setStats] = useState<ProcessStats | null>(null);
+  const isRunning = process.status === 'running';
+  useEffect(() => {
+    if (!isRunning) {
+      setStats(null);
+      return;
+    }
+    const fetchStats = async () => {
+      try {
+        setStats(await executionProcessesApi.getStats(process.id));
+      } catch (err) {
+        console.error('Failed to load process stats:', err);
+      }
+    };
+    fetchStats();
+    const interval = setInterval(fetchStats, STATS_POLL_INTERVAL_MS);
+    return () => clearInterval(interval);
+  }, [process.id, isRunning]);
+
   const logEndRef = useRef<HTMLDivElement>(null);
   const isConnected = isCodingAgent ? normalizedConnected : rawConnected;
   const error = isCodingAgent ? normalizedError : rawError;
 
   const getStatusIcon = (status: ExecutionProcessStatus) => {
     switch (status) {
+      case 'queued':
+        return <Clock className="h-4 w-4 text-amber-500" />;
       case 'running':
         return <Play className="h-4 w-4 text-blue-500" />;
       case 'completed':
@@ -54,6 +98,8 @@ function ProcessCard({ process }: ProcessCardProps) {
 
   const getStatusColor = (status: ExecutionProcessStatus) => {
     switch (status) {
+      case 'queued':
+        return 'bg-amber-50 border-amber-200 text-amber-800';
       case 'running':
         return 'bg-blue-50 border-blue-200 text-blue-800';
       case 'completed':
@@ -106,13 +152,18 @@ function ProcessCard({ process }: ProcessCardProps) {
               process.status
             )}`}
           >
-            {process.status}
+            {processStatusLabel(process)}
           </span>
           {process.exit_code !== null && (
             <p className="text-xs text-muted-foreground mt-1">
               Exit: {process.exit_code.toString()}
             </p>
           )}
+          {process.stop_reason && (
+            <p className="text-xs text-amber-600 mt-1">
+              {stopReasonLabel(process.stop_reason)}
+            </p>
+          )}
         </div>
       </div>
 
@@ -127,6 +178,11 @@ function ProcessCard({ process }: ProcessCardProps) {
             {formatDate(process.completed_at)}
           </div>
         )}
+        {stats && (
+          <div>
+            <span className="font-medium">Usage:</span> {formatStats(stats)}
+          </div>
+        )}
         <div>
           <span className="font-medium">Process ID:</span> {process.id}
         </div>
@@ -12,6 +12,11 @@ import { TaskAttemptDataContext } from '@/components/context/taskDetailsContext.
 import { executionProcessesApi } from '@/lib/api.ts';
 import { ProfileVariantBadge } from '@/components/common/ProfileVariantBadge.tsx';
 import type { ExecutionProcessStatus, ExecutionProcess } from 'shared/types';
+import { stopReasonLabel } from '@/utils/stop-reason';
+import {
+  processRunReasonLabel,
+  processStatusLabel,
+} from '@/constants/processes';
 
 function ProcessesTab() {
   const { attemptData, setAttemptData } = useContext(TaskAttemptDataContext);
@@ -22,6 +27,8 @@ function ProcessesTab() {
 
   const getStatusIcon = (status: ExecutionProcessStatus) => {
     switch (status) {
+      case 'queued':
+        return <Clock className="h-4 w-4 text-amber-500" />;
       case 'running':
         return <Play className="h-4 w-4 text-blue-500" />;
       case 'completed':
@@ -37,6 +44,8 @@ function ProcessesTab() {
 
   const getStatusColor = (status: ExecutionProcessStatus) => {
     switch (status) {
+      case 'queued':
+        return 'bg-amber-50 border-amber-200 text-amber-800';
       case 'running':
         return 'bg-blue-50 border-blue-200 text-blue-800';
       case 'completed':
@@ -120,7 +129,7 @@ function ProcessesTab() {
                     {getStatusIcon(process.status)}
                     <div>
                       <h3 className="font-medium text-sm">
-                        {process.run_reason}
+                        {processRunReasonLabel(process)}
                       </h3>
                       <p className="text-sm text-muted-foreground mt-1">
                         Process ID: {process.id}
@@ -149,13 +158,18 @@ function ProcessesTab() {
                         process.status
                       )}`}
                     >
-                      {process.status}
+                      {processStatusLabel(process)}
                     </span>
                     {process.exit_code !== null && (
                       <p className="text-xs text-muted-foreground mt-1">
                         Exit: {process.exit_code.toString()}
                       </p>
                     )}
+                    {process.stop_reason && (
+                      <p className="text-xs text-amber-600 mt-1">
+                        {stopReasonLabel(process.stop_reason)}
+                      </p>
+                    )}
                   </div>
                 </div>
                 <div className="mt-3 text-xs text-muted-foreground">
@@ -192,11 +206,11 @@ function ProcessesTab() {
                     <div className="space-y-1 text-sm">
                       <p>
                         <span className="font-medium">Type:</span>{' '}
-                        {selectedProcess.run_reason}
+                        {processRunReasonLabel(selectedProcess)}
                       </p>
                       <p>
                         <span className="font-medium">Status:</span>{' '}
-                        {selectedProcess.status}
+                        {processStatusLabel(selectedProcess)}
                       </p>
                       {/* Executor type field not available in new type */}
                       <p>
@@ -247,12 +261,15 @@ function ProcessesTab() {
                       Task Attempt ID: {selectedProcess.task_attempt_id}
                     </div>
                     <div>Run Reason: {selectedProcess.run_reason}</div>
-                    <div>Status: {selectedProcess.status}</div>
+                    <div>Status: {processStatusLabel(selectedProcess)}</div>
                     {selectedProcess.exit_code !== null && (
                       <div>
                         Exit Code: {selectedProcess.exit_code.toString()}
                       </div>
                     )}
+                    {selectedProcess.stop_reason && (
+                      <div>{stopReasonLabel(selectedProcess.stop_reason)}</div>
+                    )}
                   </div>
                 </div>
               </div>
@@ -0,0 +1,86 @@
+import { useCallback, useEffect, useState } from 'react';
+import { ChevronDown, ChevronRight, SearchCheck } from 'lucide-react';
+import { cn } from '@/lib/utils';
+import { attemptsApi } from '@/lib/api';
+import type { ReviewComment, ReviewCommentSeverity } from 'shared/types';
+
+interface ReviewCommentsProps {
+  attemptId: string;
+}
+
+const POLL_INTERVAL_MS = 5000;
+
+const SEVERITY_STYLES: Record<ReviewCommentSeverity, string> = {
+  error: 'text-red-600',
+  warning: 'text-amber-600',
+  info: 'text-muted-foreground',
+};
+
+function location({ file_path, line }: ReviewComment) {
+  if (!file_path) return null;
+  return line !== null ? `${file_path}:${line}` : file_path;
+}
+
+export function ReviewComments({ attemptId }: ReviewCommentsProps) {
+  const [comments, setComments] = useState<ReviewComment[]>([]);
+  const [expanded, setExpanded] = useState(true);
+
+  const fetchComments = useCallback(async () => {
+    try {
+      setComments(await attemptsApi.getReviewComments(attemptId));
+    } catch (err) {
+      console.error('Failed to load review comments:', err);
+    }
+  }, [attemptId]);
+
+  useEffect(() => {
+    fetchComments();
+    const interval = setInterval(fetchComments, POLL_INTERVAL_MS);
+    return () => clearInterval(interval);
+  }, [fetchComments]);
+
+  if (comments.length === 0) return null;
+
+  return (
+    <div className="border-b px-4 py-3 text-sm space-y-2">
+      <button
+        className="flex items-center gap-2 font-medium"
+        onClick={() => setExpanded((value) => !value)}
+      >
+        {expanded ? (
+          <ChevronDown className="h-4 w-4" />
+        ) : (
+          <ChevronRight className="h-4 w-4" />
+        )}
+        <SearchCheck className="h-4 w-4" />
+        Review comments ({comments.length})
+      </button>
+      {expanded && (
+        <ul className="space-y-2 max-h-64 overflow-y-auto">
+          {comments.map((comment) => (
+            <li key={comment.id} className="flex gap-2">
+              <span
+                className={cn(
+                  'text-xs font-medium uppercase w-16 flex-shrink-0',
+                  SEVERITY_STYLES[comment.severity]
+                )}
+              >
+                {comment.severity}
+              </span>
+              <div className="min-w-0">
+                {location(comment) && (
+                  <div className="font-mono text-xs text-muted-foreground truncate">
+                    {location(comment)}
+                  </div>
+                )}
+                <div className="whitespace-pre-wrap break-words">
+                  {comment.body}
+                </div>
+              </div>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
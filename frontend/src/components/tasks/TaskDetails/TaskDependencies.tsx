@@ -0,0 +1,173 @@
+import { useCallback, useContext, useEffect, useState } from 'react';
+import { ChevronDown, ChevronRight, Link2, X } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Label } from '@/components/ui/label';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { ProfileSelect } from '@/components/projects/project-form-fields';
+import { TaskDetailsContext } from '@/components/context/taskDetailsContext.ts';
+import { tasksApi } from '@/lib/api';
+import { cn } from '@/lib/utils';
+import type {
+  ProfileVariantLabel,
+  Task,
+  TaskWithAttemptStatus,
+} from 'shared/types';
+
+export function TaskDependencies() {
+  const { task, projectId } = useContext(TaskDetailsContext);
+  const [blockers, setBlockers] = useState<Task[]>([]);
+  const [projectTasks, setProjectTasks] = useState<TaskWithAttemptStatus[]>(
+    []
+  );
+  const [autoStartProfile, setAutoStartProfile] =
+    useState<ProfileVariantLabel | null>(task.auto_start_profile);
+  const [expanded, setExpanded] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const fetchBlockers = useCallback(async () => {
+    try {
+      setBlockers(await tasksApi.getDependencies(task.id));
+    } catch (err) {
+      console.error('Failed to load task dependencies:', err);
+    }
+  }, [task.id]);
+
+  useEffect(() => {
+    fetchBlockers();
+  }, [fetchBlockers]);
+
+  useEffect(() => {
+    if (!expanded) return;
+    tasksApi
+      .getAll(projectId)
+      .then(setProjectTasks)
+      .catch((err) => console.error('Failed to load project tasks:', err));
+  }, [expanded, projectId]);
+
+  const addBlocker = async (blockedByTaskId: string) => {
+    setError(null);
+    try {
+      await tasksApi.addDependency(task.id, {
+        blocked_by_task_id: blockedByTaskId,
+      });
+      await fetchBlockers();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to add blocker');
+    }
+  };
+
+  const removeBlocker = async (blockedByTaskId: string) => {
+    setError(null);
+    try {
+      await tasksApi.removeDependency(task.id, blockedByTaskId);
+      await fetchBlockers();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to remove blocker');
+    }
+  };
+
+  const updateAutoStart = async (profile: ProfileVariantLabel | null) => {
+    setError(null);
+    try {
+      await tasksApi.updateAutoStart(task.id, {
+        profile_variant_label: profile,
+      });
+      setAutoStartProfile(profile);
+    } catch (err) {
+      setError(
+        err instanceof Error ? err.message : 'Failed to update auto-start'
+      );
+    }
+  };
+
+  const candidates = projectTasks.filter(
+    (candidate) =>
+      candidate.id !== task.id &&
+      !blockers.some((blocker) => blocker.id === candidate.id)
+  );
+  const pending = blockers.filter((blocker) => blocker.status !== 'done');
+
+  return (
+    <div className="border-b px-4 py-3 text-sm space-y-2">
+      <button
+        className="flex items-center gap-2 font-medium"
+        onClick={() => setExpanded((value) => !value)}
+      >
+        {expanded ? (
+          <ChevronDown className="h-4 w-4" />
+        ) : (
+          <ChevronRight className="h-4 w-4" />
+        )}
+        <Link2 className="h-4 w-4" />
+        Blocked by
+        {blockers.length > 0 && (
+          <span className="text-muted-foreground font-normal">
+            ({pending.length} of {blockers.length} not done)
+          </span>
+        )}
+      </button>
+      {expanded && (
+        <div className="space-y-3">
+          {blockers.length > 0 && (
+            <ul className="space-y-1">
+              {blockers.map((blocker) => (
+                <li key={blocker.id} className="flex items-center gap-2">
+                  <span
+                    className={cn(
+                      'truncate',
+                      blocker.status === 'done' &&
+                        'line-through text-muted-foreground'
+                    )}
+                  >
+                    {blocker.title}
+                  </span>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    className="h-6 w-6 p-0 ml-auto flex-shrink-0"
+                    onClick={() => removeBlocker(blocker.id)}
+                    title="Remove blocker"
+                  >
+                    <X className="h-3 w-3" />
+                  </Button>
+                </li>
+              ))}
+            </ul>
+          )}
+          <Select value="" onValueChange={addBlocker}>
+            <SelectTrigger>
+              <SelectValue placeholder="Add a task this one waits for" />
+            </SelectTrigger>
+            <SelectContent>
+              {candidates.map((candidate) => (
+                <SelectItem key={candidate.id} value={candidate.id}>
+                  {candidate.title}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <div className="space-y-1">
+            <Label htmlFor="auto-start-profile">Auto-start</Label>
+            <ProfileSelect
+              id="auto-start-profile"
+              value={autoStartProfile}
+              onChange={updateAutoStart}
+              noneLabel="Don't start automatically"
+            />
+            <p className="text-xs text-muted-foreground">
+              Start an attempt with this profile once every blocking task is
+              done.
+            </p>
+          </div>
+          {error && <p className="text-xs text-destructive">{error}</p>}
+        </div>
+      )}
+    </div>
+  );
+}
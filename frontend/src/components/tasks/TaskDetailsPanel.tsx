@@ -15,6 +15,7 @@ import DeleteFileConfirmationDialog from '@/components/tasks/DeleteFileConfirmat
 import TabNavigation from '@/components/tasks/TaskDetails/TabNavigation.tsx';
 import TaskDetailsProvider from '../context/TaskDetailsContextProvider.tsx';
 import TaskDetailsToolbar from './TaskDetailsToolbar.tsx';
+import { TaskDependencies } from './TaskDetails/TaskDependencies.tsx';
 
 interface TaskDetailsPanelProps {
   task: TaskWithAttemptStatus | null;
@@ -96,6 +97,8 @@ export function TaskDetailsPanel({
                 />
               )}
 
+              <TaskDependencies />
+
               <TaskDetailsToolbar />
 
               <TabNavigation
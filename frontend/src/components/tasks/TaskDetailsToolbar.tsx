@@ -361,6 +361,7 @@ function TaskDetailsToolbar() {
                   creatingPR={ui.creatingPR}
                   handleEnterCreateAttemptMode={handleEnterCreateAttemptMode}
                   handleAttemptSelect={handleAttemptSelect}
+                  fetchTaskAttempts={fetchTaskAttempts}
                   branches={branches}
                 />
               ) : (
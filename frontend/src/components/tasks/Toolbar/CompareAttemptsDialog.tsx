@@ -0,0 +1,139 @@
+import { useCallback, useEffect, useState } from 'react';
+import { Trophy } from 'lucide-react';
+import { Button } from '@/components/ui/button.tsx';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog.tsx';
+import { tasksApi } from '@/lib/api.ts';
+import type { AttemptComparison, TaskAttempt } from 'shared/types';
+
+type Props = {
+  taskId: string;
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  onPicked: (attempt: TaskAttempt) => void;
+};
+
+function formatDuration(secs: number | null) {
+  if (secs === null) return 'Running';
+  const minutes = Math.floor(secs / 60);
+  return minutes > 0 ? `${minutes}m ${secs % 60}s` : `${secs}s`;
+}
+
+function CompareAttemptsDialog({
+  taskId,
+  open,
+  onOpenChange,
+  onPicked,
+}: Props) {
+  const [comparisons, setComparisons] = useState<AttemptComparison[]>([]);
+  const [picking, setPicking] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const fetchComparisons = useCallback(async () => {
+    try {
+      setComparisons(await tasksApi.compareAttempts(taskId));
+    } catch (err) {
+      console.error('Failed to compare attempts:', err);
+    }
+  }, [taskId]);
+
+  useEffect(() => {
+    if (open) fetchComparisons();
+  }, [open, fetchComparisons]);
+
+  const pick = async (attempt: TaskAttempt) => {
+    setPicking(attempt.id);
+    setError(null);
+    try {
+      const picked = await tasksApi.pickAttempt(taskId, {
+        attempt_id: attempt.id,
+      });
+      onOpenChange(false);
+      onPicked(picked);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to pick attempt');
+    } finally {
+      setPicking(null);
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-3xl">
+        <DialogHeader>
+          <DialogTitle>Compare Attempts</DialogTitle>
+          <DialogDescription>
+            Keep the best attempt. All other attempts are stopped and their
+            worktrees and branches are deleted.
+          </DialogDescription>
+        </DialogHeader>
+
+        <table className="w-full text-sm">
+          <thead className="text-xs text-muted-foreground text-left">
+            <tr>
+              <th className="py-1 font-medium">Profile</th>
+              <th className="py-1 font-medium">Status</th>
+              <th className="py-1 font-medium">Duration</th>
+              <th className="py-1 font-medium">Changes</th>
+              <th className="py-1 font-medium">Tokens (in / out)</th>
+              <th />
+            </tr>
+          </thead>
+          <tbody>
+            {comparisons.map(
+              ({ attempt, status, duration_secs, diff_stats, token_usage }) => (
+                <tr key={attempt.id} className="border-t">
+                  <td className="py-2">{attempt.profile}</td>
+                  <td className="py-2 capitalize">
+                    {status ?? 'Not started'}
+                  </td>
+                  <td className="py-2">{formatDuration(duration_secs)}</td>
+                  <td className="py-2 font-mono text-xs">
+                    {diff_stats ? (
+                      <>
+                        {diff_stats.files_changed} files{' '}
+                        <span className="text-green-600">
+                          +{diff_stats.additions}
+                        </span>{' '}
+                        <span className="text-red-600">
+                          -{diff_stats.deletions}
+                        </span>
+                      </>
+                    ) : (
+                      '-'
+                    )}
+                  </td>
+                  <td className="py-2 font-mono text-xs">
+                    {token_usage.input_tokens.toLocaleString()} /{' '}
+                    {token_usage.output_tokens.toLocaleString()}
+                  </td>
+                  <td className="py-2 text-right">
+                    <Button
+                      size="sm"
+                      variant="outline"
+                      className="gap-1"
+                      disabled={picking !== null}
+                      onClick={() => pick(attempt)}
+                    >
+                      <Trophy className="h-3 w-3" />
+                      {picking === attempt.id ? 'Keeping...' : 'Keep'}
+                    </Button>
+                  </td>
+                </tr>
+              )
+            )}
+          </tbody>
+        </table>
+
+        {error && <div className="text-sm text-red-600">{error}</div>}
+      </DialogContent>
+    </Dialog>
+  );
+}
+
+export default CompareAttemptsDialog;
@@ -1,5 +1,6 @@
 import { Dispatch, SetStateAction, useCallback, useContext } from 'react';
 import { Button } from '@/components/ui/button.tsx';
+import { Input } from '@/components/ui/input.tsx';
 import { ArrowDown, Settings2, X } from 'lucide-react';
 import {
   DropdownMenu,
@@ -30,6 +31,8 @@ import {
 } from '@/components/ui/dialog.tsx';
 import { useState } from 'react';
 
+const MAX_PARALLEL_ATTEMPTS = 5;
+
 type Props = {
   branches: GitBranch[];
   taskAttempts: TaskAttempt[];
@@ -65,6 +68,8 @@ function CreateAttempt({
     string | undefined
   >(undefined);
 
+  const [attemptCount, setAttemptCount] = useState(1);
+
   // Create attempt logic
   const actuallyCreateAttempt = useCallback(
     async (profile: ProfileVariantLabel, baseBranch?: string) => {
@@ -74,14 +79,22 @@ function CreateAttempt({
         throw new Error('Base branch is required to create an attempt');
       }
 
-      await attemptsApi.create({
-        task_id: task.id,
-        profile_variant_label: profile,
-        base_branch: effectiveBaseBranch,
-      });
+      if (attemptCount > 1) {
+        await attemptsApi.createBatch({
+          task_id: task.id,
+          profile_variant_labels: Array(attemptCount).fill(profile),
+          base_branch: effectiveBaseBranch,
+        });
+      } else {
+        await attemptsApi.create({
+          task_id: task.id,
+          profile_variant_label: profile,
+          base_branch: effectiveBaseBranch,
+        });
+      }
       fetchTaskAttempts();
     },
-    [task.id, selectedProfile, selectedBranch, fetchTaskAttempts]
+    [task.id, selectedProfile, selectedBranch, attemptCount, fetchTaskAttempts]
   );
 
   // Handler for Enter key or Start button
@@ -313,14 +326,30 @@ function CreateAttempt({
           </div>
 
           {/* Step 3: Start Attempt */}
-          <div className="space-y-1">
+          <div className="flex gap-2">
+            <Input
+              type="number"
+              min={1}
+              max={MAX_PARALLEL_ATTEMPTS}
+              value={attemptCount}
+              onChange={(e) =>
+                setAttemptCount(
+                  Math.min(
+                    MAX_PARALLEL_ATTEMPTS,
+                    Math.max(1, parseInt(e.target.value, 10) || 1)
+                  )
+                )
+              }
+              className="w-14 h-8 text-xs"
+              title="Number of attempts to run in parallel"
+            />
             <Button
               onClick={handleCreateAttempt}
               disabled={
                 !selectedProfile || !createAttemptBranch || isAttemptRunning
               }
               size="sm"
-              className={'w-full text-xs gap-2'}
+              className={'flex-1 text-xs gap-2'}
               title={
                 !createAttemptBranch
                   ? 'Base branch is required'
@@ -329,7 +358,7 @@ function CreateAttempt({
                     : undefined
               }
             >
-              Start
+              {attemptCount > 1 ? `Start ${attemptCount}` : 'Start'}
             </Button>
           </div>
         </div>
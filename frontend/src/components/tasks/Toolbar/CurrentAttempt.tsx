@@ -1,5 +1,7 @@
 import {
   Check,
+  Columns,
+  Download,
   ExternalLink,
   GitBranch as GitBranchIcon,
   GitPullRequest,
@@ -33,6 +35,7 @@ import {
   DialogTitle,
 } from '@/components/ui/dialog.tsx';
 import BranchSelector from '@/components/tasks/BranchSelector.tsx';
+import CompareAttemptsDialog from '@/components/tasks/Toolbar/CompareAttemptsDialog.tsx';
 import { attemptsApi, executionProcessesApi } from '@/lib/api.ts';
 import {
   Dispatch,
@@ -43,7 +46,7 @@ import {
   useMemo,
   useState,
 } from 'react';
-import type { ExecutionProcess } from 'shared/types';
+import type { ExecutionProcess, TranscriptFormat } from 'shared/types';
 import type { BranchStatus, GitBranch, TaskAttempt } from 'shared/types';
 import {
   TaskAttemptDataContext,
@@ -53,6 +56,12 @@ import {
 import { useConfig } from '@/components/config-provider.tsx';
 import { useKeyboardShortcuts } from '@/lib/keyboard-shortcuts.ts';
 
+const TRANSCRIPT_FORMATS: { format: TranscriptFormat; label: string }[] = [
+  { format: 'markdown', label: 'Markdown' },
+  { format: 'html', label: 'HTML page' },
+  { format: 'json', label: 'JSON' },
+];
+
 // Helper function to get the display name for different editor types
 function getEditorDisplayName(editorType: string): string {
   switch (editorType) {
@@ -73,6 +82,10 @@ function getEditorDisplayName(editorType: string): string {
   }
 }
 
+function devServerName(process: ExecutionProcess): string {
+  return process.executor_action.dev_server?.name ?? 'dev';
+}
+
 type Props = {
   setError: Dispatch<SetStateAction<string | null>>;
   setShowCreatePRDialog: Dispatch<SetStateAction<boolean>>;
@@ -82,6 +95,7 @@ type Props = {
   creatingPR: boolean;
   handleEnterCreateAttemptMode: () => void;
   handleAttemptSelect: (attempt: TaskAttempt) => void;
+  fetchTaskAttempts: () => void;
   branches: GitBranch[];
 };
 
@@ -94,6 +108,7 @@ function CurrentAttempt({
   creatingPR,
   handleEnterCreateAttemptMode,
   handleAttemptSelect,
+  fetchTaskAttempts,
   branches,
 }: Props) {
   const { task, projectId, handleOpenInEditor, projectHasDevScript } =
@@ -117,6 +132,7 @@ function CurrentAttempt({
   const [selectedRebaseBranch, setSelectedRebaseBranch] = useState<string>('');
   const [showStopConfirmation, setShowStopConfirmation] = useState(false);
   const [copied, setCopied] = useState(false);
+  const [showCompareDialog, setShowCompareDialog] = useState(false);
 
   const processedDevServerLogs = useMemo(() => {
     if (!devServerDetails) return 'No output yet...';
@@ -126,13 +142,17 @@ function CurrentAttempt({
     return `Status: ${devServerDetails.status}\nStarted: ${devServerDetails.started_at}`;
   }, [devServerDetails]);
 
-  // Find running dev server in current project
-  const runningDevServer = useMemo(() => {
-    return attemptData.processes.find(
+  // Find running dev servers in current project
+  const runningDevServers = useMemo(() => {
+    return attemptData.processes.filter(
       (process) =>
         process.run_reason === 'devserver' && process.status === 'running'
     );
   }, [attemptData.processes]);
+  const runningDevServer = runningDevServers[0];
+  const previewDevServers = runningDevServers.filter(
+    (process) => process.dev_server_url && process.executor_action.dev_server
+  );
 
   const fetchDevServerDetails = useCallback(async () => {
     if (!runningDevServer || !task || !selectedAttempt) return;
@@ -179,7 +199,11 @@ function CurrentAttempt({
     setIsStartingDevServer(true);
 
     try {
-      await executionProcessesApi.stopExecutionProcess(runningDevServer.id);
+      await Promise.all(
+        runningDevServers.map((process) =>
+          executionProcessesApi.stopExecutionProcess(process.id)
+        )
+      );
       fetchAttemptData(selectedAttempt.id);
     } catch (err) {
       console.error('Failed to stop dev server:', err);
@@ -231,6 +255,15 @@ function CurrentAttempt({
     [fetchAttemptData, handleAttemptSelect]
   );
 
+  // The failed attempt this one was automatically started to replace
+  const failedAttempt = useMemo(
+    () =>
+      taskAttempts.find(
+        (attempt) => attempt.id === selectedAttempt.fallback_from_attempt_id
+      ),
+    [taskAttempts, selectedAttempt.fallback_from_attempt_id]
+  );
+
   const handleMergeClick = async () => {
     if (!projectId || !selectedAttempt?.id || !selectedAttempt?.task_id) return;
 
@@ -398,6 +431,14 @@ function CurrentAttempt({
             Profile
           </div>
           <div className="text-sm font-medium">{selectedAttempt.profile}</div>
+          {failedAttempt && (
+            <button
+              onClick={() => handleAttemptChange(failedAttempt)}
+              className="text-xs text-muted-foreground hover:text-foreground underline"
+            >
+              Fallback after failed {failedAttempt.profile} attempt
+            </button>
+          )}
         </div>
 
         <div>
@@ -537,6 +578,14 @@ function CurrentAttempt({
                   </p>
                 ) : runningDevServer && devServerDetails ? (
                   <div className="space-y-2">
+                    <ul className="text-sm space-y-1">
+                      {runningDevServers.map((process) => (
+                        <li key={process.id}>
+                          {devServerName(process)}:{' '}
+                          {process.dev_server_health ?? 'starting'}
+                        </li>
+                      ))}
+                    </ul>
                     <p className="text-sm font-medium">
                       Dev Server Logs (Last 10 lines):
                     </p>
@@ -545,13 +594,43 @@ function CurrentAttempt({
                     </pre>
                   </div>
                 ) : runningDevServer ? (
-                  <p>Stop the running dev server</p>
+                  <p>Stop the running dev servers</p>
                 ) : (
                   <p>Start the dev server</p>
                 )}
               </TooltipContent>
             </Tooltip>
           </TooltipProvider>
+          {previewDevServers.map((process) => {
+            const name = process.executor_action.dev_server?.name ?? '';
+            return (
+              <TooltipProvider key={process.id}>
+                <Tooltip>
+                  <TooltipTrigger asChild>
+                    <Button
+                      variant="outline"
+                      size="sm"
+                      onClick={() =>
+                        window.open(
+                          `/api/preview/${selectedAttempt.id}/${encodeURIComponent(name)}/`,
+                          '_blank'
+                        )
+                      }
+                      className="gap-1"
+                    >
+                      <ExternalLink className="h-3 w-3" />
+                      {previewDevServers.length > 1
+                        ? `Preview ${name}`
+                        : 'Preview'}
+                    </Button>
+                  </TooltipTrigger>
+                  <TooltipContent>
+                    <p>Open the dev server at {process.dev_server_url}</p>
+                  </TooltipContent>
+                </Tooltip>
+              </TooltipProvider>
+            );
+          })}
         </div>
 
         <div className="flex items-center gap-2 flex-wrap">
@@ -588,6 +667,7 @@ function CurrentAttempt({
                       </span>
                       <span className="text-xs text-muted-foreground">
                         {attempt.profile || 'Base Agent'}
+                        {attempt.fallback_from_attempt_id && ' (fallback)'}
                       </span>
                     </div>
                   </DropdownMenuItem>
@@ -596,6 +676,44 @@ function CurrentAttempt({
             </DropdownMenu>
           )}
 
+          {taskAttempts.length > 1 && (
+            <Button
+              variant="outline"
+              size="sm"
+              className="gap-2"
+              onClick={() => setShowCompareDialog(true)}
+            >
+              <Columns className="h-4 w-4" />
+              Compare
+            </Button>
+          )}
+
+          {selectedAttempt && (
+            <DropdownMenu>
+              <DropdownMenuTrigger asChild>
+                <Button variant="outline" size="sm" className="gap-2">
+                  <Download className="h-4 w-4" />
+                  Transcript
+                </Button>
+              </DropdownMenuTrigger>
+              <DropdownMenuContent align="start">
+                {TRANSCRIPT_FORMATS.map(({ format, label }) => (
+                  <DropdownMenuItem key={format} asChild>
+                    <a
+                      href={attemptsApi.transcriptUrl(
+                        selectedAttempt.id,
+                        format
+                      )}
+                      download
+                    >
+                      {label}
+                    </a>
+                  </DropdownMenuItem>
+                ))}
+              </DropdownMenuContent>
+            </DropdownMenu>
+          )}
+
           {/* Git Operations */}
           {selectedAttempt && branchStatus && (
             <>
@@ -784,6 +902,16 @@ function CurrentAttempt({
           </DialogFooter>
         </DialogContent>
       </Dialog>
+
+      <CompareAttemptsDialog
+        taskId={task.id}
+        open={showCompareDialog}
+        onOpenChange={setShowCompareDialog}
+        onPicked={(attempt) => {
+          handleAttemptSelect(attempt);
+          fetchTaskAttempts();
+        }}
+      />
     </div>
   );
 }
@@ -10,10 +10,14 @@ export const PROCESS_RUN_REASONS = {
   CLEANUP_SCRIPT: 'cleanupscript' as ExecutionProcessRunReason,
   CODING_AGENT: 'codingagent' as ExecutionProcessRunReason,
   DEV_SERVER: 'devserver' as ExecutionProcessRunReason,
+  CODE_REVIEW: 'codereview' as ExecutionProcessRunReason,
+  TEST_SCRIPT: 'testscript' as ExecutionProcessRunReason,
+  PIPELINE_STEP: 'pipelinestep' as ExecutionProcessRunReason,
 } as const;
 
 // Process statuses
 export const PROCESS_STATUSES = {
+  QUEUED: 'queued' as ExecutionProcessStatus,
   RUNNING: 'running' as ExecutionProcessStatus,
   COMPLETED: 'completed' as ExecutionProcessStatus,
   FAILED: 'failed' as ExecutionProcessStatus,
@@ -42,6 +46,22 @@ export const isProcessCompleted = (status: ExecutionProcessStatus): boolean => {
   );
 };
 
+/**
+ * The process status, with its place in the queue while it waits to start
+ */
+export const processStatusLabel = (process: ExecutionProcess): string =>
+  process.status === PROCESS_STATUSES.QUEUED && process.queue_position !== null
+    ? `queued (#${process.queue_position})`
+    : process.status;
+
+/**
+ * The run reason, with the name of the pipeline step the process runs
+ */
+export const processRunReasonLabel = (process: ExecutionProcess): string => {
+  const step = process.executor_action.pipeline_step;
+  return step ? `${process.run_reason}: ${step.name}` : process.run_reason;
+};
+
 export const shouldShowInLogs = (
   runReason: ExecutionProcessRunReason
 ): boolean => {
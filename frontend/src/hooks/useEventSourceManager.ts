@@ -74,6 +74,7 @@ export const useEventSourceManager = ({
         const processStartPayload: ProcessStartPayload = {
           processId: process.id,
           runReason: process.run_reason,
+          stepName: process.executor_action.pipeline_step?.name ?? null,
           startedAt: process.started_at,
           status: process.status,
         };
@@ -92,6 +93,12 @@ export const useEventSourceManager = ({
         setError(null);
       };
 
+      // Index of the first entry sent, when only the latest ones are loaded
+      eventSource.addEventListener('entries_start', (event) => {
+        processDataRef.current[process.id].entriesStart = Number(event.data);
+        setProcessData({ ...processDataRef.current });
+      });
+
       eventSource.addEventListener('json_patch', (event) => {
         try {
           const patches = JSON.parse(event.data);
@@ -43,6 +43,7 @@ export const useProcessConversation = (
         const processStartPayload: ProcessStartPayload = {
           processId: processId,
           runReason: 'Manual', // Default value since we don't have process details here
+          stepName: null,
           startedAt: new Date().toISOString(),
           status: 'running',
         };
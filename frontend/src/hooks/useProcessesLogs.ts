@@ -1,25 +1,53 @@
-import { useMemo, useCallback } from 'react';
+import { useMemo, useCallback, useRef, useState } from 'react';
+import { applyPatch } from 'rfc6902';
+import type { ExecutionProcess, PatchType } from 'shared/types';
 import type {
-  ExecutionProcess,
-  NormalizedEntry,
-  PatchType,
-} from 'shared/types';
-import type { UnifiedLogEntry, ProcessStartPayload } from '@/types/logs';
+  UnifiedLogEntry,
+  ProcessStartPayload,
+  LoadEarlierPayload,
+} from '@/types/logs';
 import { useEventSourceManager } from './useEventSourceManager';
 
 interface UseProcessesLogsResult {
   entries: UnifiedLogEntry[];
   isConnected: boolean;
   error: string | null;
+  loadEarlier: (processId: string) => void;
 }
 
-const MAX_ENTRIES = 5000;
+// Entries loaded per process at first, and per "load earlier" request
+const PAGE_SIZE = 1000;
+
+// Entries of a process loaded before the first one it was opened with
+interface EarlierEntries {
+  start: number;
+  entries: PatchType[];
+}
+
+function toLogEntry(
+  patchEntry: PatchType
+): Pick<UnifiedLogEntry, 'channel' | 'payload'> | null {
+  switch (patchEntry.type) {
+    case 'STDOUT':
+      return { channel: 'stdout', payload: patchEntry.content };
+    case 'STDERR':
+      return { channel: 'stderr', payload: patchEntry.content };
+    case 'NORMALIZED_ENTRY':
+      return { channel: 'normalized', payload: patchEntry.content };
+    default:
+      // Skip unknown patch types
+      return null;
+  }
+}
 
 export const useProcessesLogs = (
   processes: ExecutionProcess[],
   enabled: boolean
 ): UseProcessesLogsResult => {
-  const getEndpoint = useCallback((process: ExecutionProcess) => {
+  const [earlier, setEarlier] = useState<Record<string, EarlierEntries>>({});
+  const loadingRef = useRef<Set<string>>(new Set());
+
+  const getBaseEndpoint = useCallback((process: ExecutionProcess) => {
     // Coding agents use normalized logs endpoint, scripts use raw logs endpoint
     // Both endpoints now return PatchType objects via JSON patches
     const isCodingAgent = process.run_reason === 'codingagent';
@@ -28,6 +56,13 @@ export const useProcessesLogs = (
       : `/api/execution-processes/${process.id}/raw-logs`;
   }, []);
 
+  // Only the latest entries are loaded, earlier ones on request
+  const getEndpoint = useCallback(
+    (process: ExecutionProcess) =>
+      `${getBaseEndpoint(process)}?limit=${PAGE_SIZE}`,
+    [getBaseEndpoint]
+  );
+
   const initialData = useMemo(() => ({ entries: [] }), []);
 
   const { processData, isConnected, error } = useEventSourceManager({
@@ -37,6 +72,48 @@ export const useProcessesLogs = (
     initialData,
   });
 
+  const loadEarlier = useCallback(
+    (processId: string) => {
+      const process = processes.find((p) => p.id === processId);
+      const before =
+        earlier[processId]?.start ?? processData[processId]?.entriesStart;
+      if (!process || !before || loadingRef.current.has(processId)) return;
+      loadingRef.current.add(processId);
+
+      const page: { entries: PatchType[] } = { entries: [] };
+      let start = before;
+      const eventSource = new EventSource(
+        `${getBaseEndpoint(process)}?before=${before}&limit=${PAGE_SIZE}`
+      );
+      const close = () => {
+        eventSource.close();
+        loadingRef.current.delete(processId);
+      };
+
+      eventSource.addEventListener('entries_start', (event) => {
+        start = Number(event.data);
+      });
+      eventSource.addEventListener('json_patch', (event) => {
+        applyPatch(page, JSON.parse(event.data));
+      });
+      eventSource.addEventListener('finished', () => {
+        close();
+        setEarlier((prev) => ({
+          ...prev,
+          [processId]: {
+            start,
+            entries: [...page.entries, ...(prev[processId]?.entries ?? [])],
+          },
+        }));
+      });
+      eventSource.onerror = () => {
+        console.error('Failed to load earlier logs of process', processId);
+        close();
+      };
+    },
+    [processes, processData, earlier, getBaseEndpoint]
+  );
+
   const entries = useMemo(() => {
     const allEntries: UnifiedLogEntry[] = [];
     let entryCounter = 0;
@@ -50,6 +127,7 @@ export const useProcessesLogs = (
       const processStartPayload: ProcessStartPayload = {
         processId: process.id,
         runReason: process.run_reason,
+        stepName: process.executor_action.pipeline_step?.name ?? null,
         startedAt: process.started_at,
         status: process.status,
       };
@@ -63,53 +141,57 @@ export const useProcessesLogs = (
         payload: processStartPayload,
       });
 
-      // Then add all logs for this process (skip the injected PROCESS_START entry)
-      data.entries.forEach(
-        (
-          patchEntry:
-            | PatchType
-            | { type: 'PROCESS_START'; content: ProcessStartPayload },
-          index: number
-        ) => {
-          // Skip the injected PROCESS_START entry since we handle it above
-          if (patchEntry.type === 'PROCESS_START') return;
-
-          let channel: UnifiedLogEntry['channel'];
-          let payload: string | NormalizedEntry;
-
-          switch (patchEntry.type) {
-            case 'STDOUT':
-              channel = 'stdout';
-              payload = patchEntry.content;
-              break;
-            case 'STDERR':
-              channel = 'stderr';
-              payload = patchEntry.content;
-              break;
-            case 'NORMALIZED_ENTRY':
-              channel = 'normalized';
-              payload = patchEntry.content;
-              break;
-            default:
-              // Skip unknown patch types
-              return;
-          }
-
+      const pushEntries = (patchEntries: PatchType[], start: number) => {
+        patchEntries.forEach((patchEntry, index) => {
+          const entry = toLogEntry(patchEntry);
+          if (!entry) return;
           allEntries.push({
-            id: `${process.id}-${index}`,
+            id: `${process.id}-${start + index}`,
             ts: entryCounter++,
             processId: process.id,
             processName: process.run_reason,
-            channel,
-            payload,
+            ...entry,
           });
-        }
+        });
+      };
+
+      // Offer to load the entries before the first one loaded
+      const latestStart: number = data.entriesStart ?? 0;
+      const loadedEarlier = earlier[process.id];
+      const firstLoaded = loadedEarlier?.start ?? latestStart;
+      if (firstLoaded > 0) {
+        const loadEarlierPayload: LoadEarlierPayload = {
+          processId: process.id,
+          earlierCount: firstLoaded,
+        };
+        allEntries.push({
+          id: `${process.id}-load-earlier`,
+          ts: entryCounter++,
+          processId: process.id,
+          processName: process.run_reason,
+          channel: 'load_earlier',
+          payload: loadEarlierPayload,
+        });
+      }
+
+      if (loadedEarlier) {
+        pushEntries(loadedEarlier.entries, loadedEarlier.start);
+      }
+      // Then add all logs for this process (skip the injected PROCESS_START entry)
+      pushEntries(
+        data.entries.filter(
+          (
+            patchEntry:
+              | PatchType
+              | { type: 'PROCESS_START'; content: ProcessStartPayload }
+          ) => patchEntry.type !== 'PROCESS_START'
+        ),
+        latestStart
       );
     });
 
-    // Limit entries (no sorting needed since we build in order)
-    return allEntries.slice(-MAX_ENTRIES);
-  }, [processData, processes]);
+    return allEntries;
+  }, [processData, processes, earlier]);
 
-  return { entries, isConnected, error };
+  return { entries, isConnected, error, loadEarlier };
 };
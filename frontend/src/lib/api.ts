@@ -2,6 +2,7 @@
 
 import {
   ApiResponse,
+  AttemptComparison,
   BranchStatus,
   CheckTokenResponse,
   Config,
@@ -9,6 +10,8 @@ import {
   CreateGitHubPrRequest,
   CreateTask,
   CreateTaskAttemptBody,
+  CreateTaskAttemptsBody,
+  CreateTaskDependency,
   CreateTaskTemplate,
   DeviceFlowStartResponse,
   DevicePollStatus,
@@ -16,15 +19,27 @@ import {
   EditorType,
   ExecutionProcess,
   GitBranch,
+  PickTaskAttemptRequest,
+  PlanReview,
+  ProcessStats,
   Project,
   CreateProject,
   RebaseTaskAttemptRequest,
   RepositoryInfo,
+  RespondToApprovalRequest,
+  RespondToPlanReviewRequest,
+  ReviewComment,
   SearchResult,
+  SendMessageRequest,
   Task,
   TaskAttempt,
+  TaskDependency,
   TaskTemplate,
   TaskWithAttemptStatus,
+  ToolApproval,
+  TranscriptFormat,
+  UpdateAutoStartRequest,
+  UpdatePlanReviewRequest,
   UpdateProject,
   UpdateTask,
   UpdateTaskTemplate,
@@ -281,6 +296,62 @@ export const tasksApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  compareAttempts: async (taskId: string): Promise<AttemptComparison[]> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/attempts/compare`
+    );
+    return handleApiResponse<AttemptComparison[]>(response);
+  },
+
+  pickAttempt: async (
+    taskId: string,
+    data: PickTaskAttemptRequest
+  ): Promise<TaskAttempt> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/attempts/pick`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  getDependencies: async (taskId: string): Promise<Task[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/dependencies`);
+    return handleApiResponse<Task[]>(response);
+  },
+
+  addDependency: async (
+    taskId: string,
+    data: CreateTaskDependency
+  ): Promise<TaskDependency> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/dependencies`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskDependency>(response);
+  },
+
+  removeDependency: async (
+    taskId: string,
+    blockedByTaskId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/dependencies/${blockedByTaskId}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  updateAutoStart: async (
+    taskId: string,
+    data: UpdateAutoStartRequest
+  ): Promise<Task> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/auto-start`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Task>(response);
+  },
 };
 
 // Task Attempts APIs
@@ -305,6 +376,21 @@ export const attemptsApi = {
     return handleApiResponse<TaskAttempt>(response);
   },
 
+  createBatch: async (data: CreateTaskAttemptsBody): Promise<TaskAttempt[]> => {
+    const response = await makeRequest(`/api/task-attempts/batch`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskAttempt[]>(response);
+  },
+
+  delete: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
   stop: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(`/api/task-attempts/${attemptId}/stop`, {
       method: 'POST',
@@ -312,6 +398,30 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  transcriptUrl: (attemptId: string, format: TranscriptFormat): string =>
+    `/api/task-attempts/${attemptId}/transcript?format=${format}`,
+
+  getApprovals: async (attemptId: string): Promise<ToolApproval[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/approvals`
+    );
+    return handleApiResponse<ToolApproval[]>(response);
+  },
+
+  getPlanReview: async (attemptId: string): Promise<PlanReview | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/plan-review`
+    );
+    return handleApiResponse<PlanReview | null>(response);
+  },
+
+  getReviewComments: async (attemptId: string): Promise<ReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments`
+    );
+    return handleApiResponse<ReviewComment[]>(response);
+  },
+
   followUp: async (
     attemptId: string,
     data: CreateFollowUpAttempt
@@ -438,6 +548,13 @@ export const executionProcessesApi = {
     return handleApiResponse<ExecutionProcess>(response);
   },
 
+  getStats: async (processId: string): Promise<ProcessStats | null> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/stats`
+    );
+    return handleApiResponse<ProcessStats | null>(response);
+  },
+
   stopExecutionProcess: async (processId: string): Promise<void> => {
     const response = await makeRequest(
       `/api/execution-processes/${processId}/stop`,
@@ -447,6 +564,65 @@ export const executionProcessesApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  sendMessage: async (
+    processId: string,
+    data: SendMessageRequest
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/message`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Tool Approval APIs
+export const approvalsApi = {
+  respond: async (
+    approvalId: string,
+    data: RespondToApprovalRequest
+  ): Promise<ToolApproval> => {
+    const response = await makeRequest(
+      `/api/approvals/${approvalId}/respond`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ToolApproval>(response);
+  },
+};
+
+// Plan Review APIs
+export const planReviewsApi = {
+  update: async (
+    planReviewId: string,
+    data: UpdatePlanReviewRequest
+  ): Promise<PlanReview> => {
+    const response = await makeRequest(`/api/plan-reviews/${planReviewId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<PlanReview>(response);
+  },
+
+  respond: async (
+    planReviewId: string,
+    data: RespondToPlanReviewRequest
+  ): Promise<PlanReview> => {
+    const response = await makeRequest(
+      `/api/plan-reviews/${planReviewId}/respond`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<PlanReview>(response);
+  },
 };
 
 // File System APIs
@@ -601,9 +777,17 @@ export const mcpServersApi = {
 
 // Profiles API
 export const profilesApi = {
-  load: async (): Promise<{ content: string; path: string }> => {
+  load: async (): Promise<{
+    content: string;
+    path: string;
+    errors: string[];
+  }> => {
     const response = await makeRequest('/api/profiles');
-    return handleApiResponse<{ content: string; path: string }>(response);
+    return handleApiResponse<{
+      content: string;
+      path: string;
+      errors: string[];
+    }>(response);
   },
   save: async (content: string): Promise<string> => {
     const response = await makeRequest('/api/profiles', {
@@ -72,6 +72,9 @@ export function Settings() {
         const result = await profilesApi.load();
         setProfilesContent(result.content);
         setProfilesPath(result.path);
+        if (result.errors.length > 0) {
+          setProfilesError(result.errors.join('\n'));
+        }
       } catch (err) {
         console.error('Failed to load profiles:', err);
         setProfilesError('Failed to load profiles');
@@ -383,6 +386,29 @@ export function Settings() {
                   attempt.
                 </p>
               </div>
+
+              <div className="space-y-2">
+                <Label htmlFor="max-concurrent-agents">
+                  Max Concurrent Agents
+                </Label>
+                <Input
+                  id="max-concurrent-agents"
+                  type="number"
+                  min={1}
+                  placeholder="Unlimited"
+                  value={config.max_concurrent_agents ?? ''}
+                  onChange={(e) => {
+                    const limit = parseInt(e.target.value, 10);
+                    updateConfig({
+                      max_concurrent_agents: limit > 0 ? limit : null,
+                    });
+                  }}
+                />
+                <p className="text-sm text-muted-foreground">
+                  Coding agents that run at once across all projects. Further
+                  attempts are queued and start as running ones finish.
+                </p>
+              </div>
             </CardContent>
           </Card>
 
@@ -687,13 +713,16 @@ export function Settings() {
               </CardTitle>
               <CardDescription>
                 Configure coding agent profiles with specific command-line
-                parameters.
+                parameters. A profile or variant can set "extends" to the
+                label of another profile and override only some of its fields.
               </CardDescription>
             </CardHeader>
             <CardContent className="space-y-4">
               {profilesError && (
                 <Alert variant="destructive">
-                  <AlertDescription>{profilesError}</AlertDescription>
+                  <AlertDescription className="whitespace-pre-line">
+                    {profilesError}
+                  </AlertDescription>
                 </Alert>
               )}
 
@@ -176,6 +176,7 @@ export function ProjectTasks() {
           title,
           description: description || null,
           parent_task_attempt: null,
+          auto_start_profile: null,
         });
         await fetchTasks();
         // Open the newly created task in the details panel
@@ -197,6 +198,7 @@ export function ProjectTasks() {
           title,
           description: description || null,
           parent_task_attempt: null,
+          auto_start_profile: null,
         };
         const result = await tasksApi.createAndStart(payload);
         await fetchTasks();
@@ -219,6 +221,7 @@ export function ProjectTasks() {
           description: description || null,
           status,
           parent_task_attempt: null,
+          auto_start_profile: null,
         });
         await fetchTasks();
         setEditingTask(null);
@@ -512,7 +515,7 @@ export function ProjectTasks() {
       {isPanelOpen && (
         <TaskDetailsPanel
           task={selectedTask}
-          projectHasDevScript={!!project?.dev_script}
+          projectHasDevScript={!!project?.dev_servers.length}
           projectId={projectId!}
           onClose={handleClosePanel}
           onEditTask={handleEditTask}
@@ -117,7 +117,7 @@ export function TaskDetailsPage() {
     <div className="min-h-screen bg-background">
       <TaskDetailsPanel
         task={task}
-        projectHasDevScript={!!project.dev_script}
+        projectHasDevScript={project.dev_servers.length > 0}
         projectId={projectId!}
         onClose={handleClose}
         onEditTask={handleEditTask}
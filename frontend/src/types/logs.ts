@@ -5,13 +5,27 @@ export interface UnifiedLogEntry {
   ts: number; // epoch-ms timestamp for sorting and react-window key
   processId: string;
   processName: string;
-  channel: 'raw' | 'stdout' | 'stderr' | 'normalized' | 'process_start';
-  payload: string | NormalizedEntry | ProcessStartPayload;
+  channel:
+    | 'raw'
+    | 'stdout'
+    | 'stderr'
+    | 'normalized'
+    | 'process_start'
+    | 'load_earlier';
+  payload: string | NormalizedEntry | ProcessStartPayload | LoadEarlierPayload;
 }
 
 export interface ProcessStartPayload {
   processId: string;
   runReason: string;
+  // Name of the pipeline step the process runs, if any
+  stepName: string | null;
   startedAt: string;
   status: string;
 }
+
+// Marks the entries of a process that haven't been loaded yet
+export interface LoadEarlierPayload {
+  processId: string;
+  earlierCount: number;
+}
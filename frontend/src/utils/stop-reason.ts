@@ -0,0 +1,16 @@
+import type { ExecutionProcessStopReason } from 'shared/types';
+
+const STOP_REASON_LABELS: Record<ExecutionProcessStopReason, string> = {
+  max_duration: 'Stopped: exceeded max duration',
+  max_idle: 'Stopped: no output for too long',
+  max_tool_calls: 'Stopped: too many tool calls',
+  max_memory: 'Stopped: used too much memory',
+  max_cpu: 'Stopped: used too much CPU time',
+  max_processes: 'Stopped: started too many processes',
+};
+
+/**
+ * Describes why an execution process was stopped by its execution limits
+ */
+export const stopReasonLabel = (reason: ExecutionProcessStopReason): string =>
+  STOP_REASON_LABELS[reason];
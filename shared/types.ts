@@ -8,29 +8,128 @@ export type DirectoryEntry = { name: string, path: string, is_directory: boolean
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, 
+/**
+ * Processes started together as the dev server, each restarted if it crashes
+ */
+dev_servers: Array<DevServer>, cleanup_script: string | null, 
+/**
+ * Runs after each coding agent run; when it fails, the agent is asked to fix it
+ */
+test_script: string | null, 
+/**
+ * Language the setup, cleanup and dev scripts are written in, bash if not set
+ */
+script_language: ScriptRequestLanguage | null, 
+/**
+ * Directory the setup, cleanup and dev scripts run in, relative to the worktree root
+ */
+script_working_dir: string | null, 
+/**
+ * Seconds the setup and cleanup scripts may run for before they are stopped
+ */
+script_timeout_secs: number | null, copy_files: string | null, 
+/**
+ * Execution limits for coding agents, on top of the profile's own limits
+ */
+max_duration_secs: number | null, max_idle_secs: number | null, max_tool_calls: number | null, 
+/**
+ * Resource limits for every process spawned for the project's attempts
+ */
+max_memory_mb: number | null, max_cpu_secs: number | null, max_processes: number | null, 
+/**
+ * Coding agents of the project's attempts that may run at once
+ */
+max_concurrent_agents: number | null, 
+/**
+ * Follow-ups the agent gets to fix a failing test script before the attempt is left
+ * for review
+ */
+max_fix_iterations: number | null, 
+/**
+ * Profile variant new attempts start with, instead of the global default
+ */
+default_profile: ProfileVariantLabel | null, 
+/**
+ * Profile variant that reviews the changes whenever a coding agent finishes
+ */
+review_profile: ProfileVariantLabel | null, 
+/**
+ * Environment variables for every process spawned for the project's attempts
+ */
+env_vars: Record<string, string>, 
+/**
+ * Steps run in order after each coding agent run
+ */
+pipeline: Array<PipelineStep>, 
+/**
+ * Run coding agents and scripts in a sandbox (Linux only)
+ */
+sandboxed: boolean, 
+/**
+ * Whether sandboxed processes have network access
+ */
+sandbox_network: boolean, created_at: Date, updated_at: Date, };
 
-export type ProjectWithBranch = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, current_branch: string | null, created_at: Date, updated_at: Date, };
+export type ProjectWithBranch = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_servers: Array<DevServer>, cleanup_script: string | null, test_script: string | null, script_language: ScriptRequestLanguage | null, script_working_dir: string | null, script_timeout_secs: number | null, copy_files: string | null, max_duration_secs: number | null, max_idle_secs: number | null, max_tool_calls: number | null, max_memory_mb: number | null, max_cpu_secs: number | null, max_processes: number | null, max_concurrent_agents: number | null, max_fix_iterations: number | null, default_profile: ProfileVariantLabel | null, review_profile: ProfileVariantLabel | null, env_vars: Record<string, string>, pipeline: Array<PipelineStep>, sandboxed: boolean, sandbox_network: boolean, current_branch: string | null, created_at: Date, updated_at: Date, };
 
-export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
+export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_servers: Array<DevServer> | null, cleanup_script: string | null, test_script: string | null, script_language: ScriptRequestLanguage | null, script_working_dir: string | null, script_timeout_secs: number | null, copy_files: string | null, max_duration_secs: number | null, max_idle_secs: number | null, max_tool_calls: number | null, max_memory_mb: number | null, max_cpu_secs: number | null, max_processes: number | null, max_concurrent_agents: number | null, max_fix_iterations: number | null, default_profile: ProfileVariantLabel | null, review_profile: ProfileVariantLabel | null, env_vars: { [key in string]?: string } | null, pipeline: Array<PipelineStep> | null, sandboxed: boolean | null, sandbox_network: boolean | null, };
 
-export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
+export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_servers: Array<DevServer> | null, cleanup_script: string | null, test_script: string | null, script_language: ScriptRequestLanguage | null, script_working_dir: string | null, script_timeout_secs: number | null, copy_files: string | null, max_duration_secs: number | null, max_idle_secs: number | null, max_tool_calls: number | null, max_memory_mb: number | null, max_cpu_secs: number | null, max_processes: number | null, max_concurrent_agents: number | null, max_fix_iterations: number | null, default_profile: ProfileVariantLabel | null, review_profile: ProfileVariantLabel | null, env_vars: { [key in string]?: string } | null, pipeline: Array<PipelineStep> | null, sandboxed: boolean | null, sandbox_network: boolean | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
-export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
+export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, 
+/**
+ * Set on actions compiled from a project's pipeline
+ */
+pipeline_step: PipelineStepInfo | null, 
+/**
+ * Set on actions that start one of a project's dev servers
+ */
+dev_server: DevServer | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, vibe_kanban: JsonValue, is_toml_config: boolean, };
 
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest;
 
-export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer";
+export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "TestScript" | "PipelineStep";
+
+export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, 
+/**
+ * Directory to run the script in, relative to the worktree root
+ */
+working_dir: string | null, 
+/**
+ * Environment variables for the script, on top of the project's
+ */
+env: { [key in string]?: string }, 
+/**
+ * Seconds the script may run for before it is stopped
+ */
+timeout_secs: number | null, };
+
+export type ScriptRequestLanguage = "Bash" | "Python" | "Node";
+
+export type PipelineStep = { name: string, action: PipelineStepAction, 
+/**
+ * Whether the pipeline goes on to the next step when this one fails
+ */
+continue_on_failure: boolean, };
+
+export type PipelineStepAction = { "type": "Script", script: string, } | { "type": "AgentFollowUp", prompt: string, };
+
+export type PipelineStepInfo = { name: string, continue_on_failure: boolean, };
 
-export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, };
+export type DevServer = { name: string, script: string, 
+/**
+ * How to tell the process is up; without one it counts as healthy while it runs
+ */
+health_check: DevServerHealthCheck | null, };
 
-export type ScriptRequestLanguage = "Bash";
+export type DevServerHealthCheck = { "type": "Url", url: string, } | { "type": "LogRegex", pattern: string, };
 
 export type TaskTemplate = { id: string, project_id: string | null, title: string, description: string | null, template_name: string, created_at: string, updated_at: string, };
 
@@ -40,11 +139,23 @@ export type UpdateTaskTemplate = { title: string | null, description: string | n
 
 export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, created_at: string, updated_at: string, };
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, 
+/**
+ * Profile variant an attempt is started with once all of the task's blockers are done
+ */
+auto_start_profile: ProfileVariantLabel | null, created_at: string, updated_at: string, };
 
-export type TaskWithAttemptStatus = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, created_at: string, updated_at: string, has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, profile: string, };
+export type TaskWithAttemptStatus = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, auto_start_profile: ProfileVariantLabel | null, created_at: string, updated_at: string, has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, awaiting_plan_approval: boolean, 
+/**
+ * Some task this one is blocked by is not done yet
+ */
+is_blocked: boolean, profile: string, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, };
+export type TaskDependency = { task_id: string, blocked_by_task_id: string, created_at: string, };
+
+export type CreateTaskDependency = { blocked_by_task_id: string, };
+
+export type CreateTask = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, auto_start_profile: ProfileVariantLabel | null, };
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, };
 
@@ -62,11 +173,43 @@ export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string,
 
 export type CreateFollowUpAttempt = { prompt: string, variant: string | null, };
 
+export type SendMessageRequest = { message: string, };
+
+export type RespondToApprovalRequest = { approved: boolean, 
+/**
+ * Passed back to the agent when the tool use is denied
+ */
+reason: string | null, };
+
+export type ToolApproval = { id: string, execution_process_id: string, tool_name: string, tool_input: JsonValue, status: ToolApprovalStatus, reason: string | null, created_at: string, responded_at: string | null, };
+
+export type ToolApprovalStatus = "pending" | "approved" | "denied";
+
+export type UpdatePlanReviewRequest = { plan: string, };
+
+export type RespondToPlanReviewRequest = { approved: boolean, 
+/**
+ * Replaces the presented plan before it is approved
+ */
+plan: string | null, };
+
+export type PlanReview = { id: string, execution_process_id: string, plan: string, status: PlanReviewStatus, created_at: string, responded_at: string | null, };
+
+export type PlanReviewStatus = "pending" | "approved" | "rejected";
+
+export type ReviewComment = { id: string, execution_process_id: string, file_path: string | null, line: number | null, severity: ReviewCommentSeverity, body: string, created_at: string, };
+
+export type ReviewCommentSeverity = "info" | "warning" | "error";
+
 export type CreateGitHubPrRequest = { title: string, body: string | null, base_branch: string | null, };
 
 export enum GitHubServiceError { TOKEN_INVALID = "TOKEN_INVALID", INSUFFICIENT_PERMISSIONS = "INSUFFICIENT_PERMISSIONS", REPO_NOT_FOUND_OR_NO_ACCESS = "REPO_NOT_FOUND_OR_NO_ACCESS" }
 
-export type Config = { config_version: string, theme: ThemeMode, profile: ProfileVariantLabel, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, workspace_dir: string | null, };
+export type Config = { config_version: string, theme: ThemeMode, profile: ProfileVariantLabel, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, workspace_dir: string | null, 
+/**
+ * Coding agents that may run at once across all projects; more are queued
+ */
+max_concurrent_agents: number | null, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -90,6 +233,24 @@ export type GitBranch = { name: string, is_current: boolean, is_remote: boolean,
 
 export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, up_to_date: boolean | null, merged: boolean, has_uncommitted_changes: boolean, base_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, remote_up_to_date: boolean | null, };
 
+export type DiffStats = { files_changed: number, additions: number, deletions: number, };
+
+export type ProcessStats = { 
+/**
+ * CPU usage of the whole group, where 100 is one fully used core
+ */
+cpu_percent: number, 
+/**
+ * Resident memory summed over the group's processes
+ */
+rss_bytes: number, process_count: number, };
+
+export type TranscriptFormat = "markdown" | "json" | "html";
+
+export type AttemptTranscript = { task_attempt_id: string, task_title: string, task_description: string | null, profile: string, branch: string | null, base_branch: string, created_at: string, runs: Array<AgentRunTranscript>, };
+
+export type AgentRunTranscript = { execution_process_id: string, status: ExecutionProcessStatus, exit_code: bigint | null, started_at: string, completed_at: string | null, conversation: NormalizedConversation, };
+
 export type Diff = { oldFile: FileDiffDetails | null, newFile: FileDiffDetails | null, hunks: Array<string>, };
 
 export type FileDiffDetails = { fileName: string | null, content: string | null, };
@@ -113,6 +274,10 @@ export type ProfileConfig = {
  * additional variants for this profile, e.g. plan, review, subagent
  */
 variants: Array<VariantAgentConfig>, 
+/**
+ * labels of the profiles to retry with, in order, when an attempt with this profile fails
+ */
+fallbacks?: Array<string>, 
 /**
  * Unique identifier for this profile (e.g., "MyClaudeCode", "FastAmp")
  */
@@ -120,7 +285,11 @@ label: string,
 /**
  * Optional profile-specific MCP config file path (absolute; supports leading ~). Overrides the default `BaseCodingAgent` config path
  */
-mcp_config_path: string | null, } & ({ "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR": Cursor });
+mcp_config_path: string | null, 
+/**
+ * Limits after which a running execution of this variant is stopped
+ */
+limits?: ExecutionLimits, } & ({ "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR": Cursor } | { "AIDER": Aider } | { "CUSTOM": Custom });
 
 export type VariantAgentConfig = { 
 /**
@@ -130,11 +299,38 @@ label: string,
 /**
  * Optional profile-specific MCP config file path (absolute; supports leading ~). Overrides the default `BaseCodingAgent` config path
  */
-mcp_config_path: string | null, } & ({ "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR": Cursor });
+mcp_config_path: string | null, 
+/**
+ * Limits after which a running execution of this variant is stopped
+ */
+limits?: ExecutionLimits, } & ({ "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR": Cursor } | { "AIDER": Aider } | { "CUSTOM": Custom });
+
+export type ExecutionLimits = { 
+/**
+ * Maximum wall-clock time the process may run for
+ */
+max_duration_secs: bigint | null, 
+/**
+ * Maximum time the process may go without writing to stdout
+ */
+max_idle_secs: bigint | null, 
+/**
+ * Maximum number of tool calls in the normalized logs
+ */
+max_tool_calls: bigint | null, };
 
 export type ProfileConfigs = { profiles: Array<ProfileConfig>, };
 
-export type ClaudeCode = { command: CommandBuilder, plan: boolean, };
+export type ClaudeCode = { command: CommandBuilder, plan: boolean, 
+/**
+ * Route permission prompts to the vibe-kanban MCP server so risky tool uses wait for
+ * approval on the board
+ */
+approvals: boolean, 
+/**
+ * Keep stdin open and read it as stream-json, so messages can be sent while Claude runs
+ */
+live_input: boolean, };
 
 export type Gemini = { command: CommandBuilder, };
 
@@ -146,21 +342,100 @@ export type Cursor = { command: CommandBuilder, };
 
 export type Opencode = { command: CommandBuilder, };
 
+export type Aider = { command: CommandBuilder, };
+
+export type Custom = { command: CommandBuilder, 
+/**
+ * Parameters appended to the command for follow-ups. `{session_id}` is substituted.
+ * Follow-ups are not supported when unset.
+ */
+follow_up_params: Array<string> | null, 
+/**
+ * JSON pointer (e.g. `/session_id`) to the session id in the agent's output
+ */
+session_id_field: string | null, rules: Array<CustomLogRule>, };
+
+export type CustomLogRule = { 
+/**
+ * JSON pointer -> value pairs that must all match for the rule to apply
+ */
+when: { [key in string]?: JsonValue }, entry_type: CustomEntryType, content: string | null, 
+/**
+ * Only used for `tool_use` entries
+ */
+tool_name: string | null, 
+/**
+ * Only used for `tool_use` entries, defaults to `other`
+ */
+action: CustomAction | null, };
+
+export type CustomEntryType = "user_message" | "assistant_message" | "tool_use" | "system_message" | "error_message" | "thinking" | "ignore";
+
+export type CustomAction = { "type": "file_read", path: string, } | { "type": "file_edit", path: string, unified_diff: string | null, content: string | null, } | { "type": "command_run", command: string, } | { "type": "search", query: string, } | { "type": "web_fetch", url: string, } | { "type": "task_create", description: string, } | { "type": "plan_presentation", plan: string, } | { "type": "other", description: string, };
+
 export type CodingAgentInitialRequest = { prompt: string, profile_variant_label: ProfileVariantLabel, };
 
 export type CodingAgentFollowUpRequest = { prompt: string, session_id: string, profile_variant_label: ProfileVariantLabel, };
 
 export type CreateTaskAttemptBody = { task_id: string, profile_variant_label: ProfileVariantLabel | null, base_branch: string, };
 
+export type CreateTaskAttemptsBody = { task_id: string, 
+/**
+ * One attempt is started per label; repeat a label to run the same profile several times
+ */
+profile_variant_labels: Array<ProfileVariantLabel>, base_branch: string, };
+
+export type AttemptComparison = { attempt: TaskAttempt, 
+/**
+ * Status of the attempt's latest coding agent run
+ */
+status: ExecutionProcessStatus | null, 
+/**
+ * Wall-clock time from the first process starting to the last one finishing; None
+ * while anything is still running
+ */
+duration_secs: bigint | null, 
+/**
+ * None until the attempt has a branch, or when its diff can't be computed
+ */
+diff_stats: DiffStats | null, token_usage: TokenUsageTotals, };
+
+export type PickTaskAttemptRequest = { attempt_id: string, };
+
+export type UpdateAutoStartRequest = { 
+/**
+ * Profile variant to start the task with once all its blockers are done; None turns
+ * auto-start off
+ */
+profile_variant_label: ProfileVariantLabel | null, };
+
 export type RebaseTaskAttemptRequest = { new_base_branch: string | null, };
 
-export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string | null, base_branch: string, merge_commit: string | null, profile: string, pr_url: string | null, pr_number: bigint | null, pr_status: string | null, pr_merged_at: string | null, worktree_deleted: boolean, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string | null, base_branch: string, merge_commit: string | null, profile: string, pr_url: string | null, pr_number: bigint | null, pr_status: string | null, pr_merged_at: string | null, worktree_deleted: boolean, setup_completed_at: string | null, fallback_from_attempt_id: string | null, created_at: string, updated_at: string, };
+
+export type ExecutionProcess = { id: string, task_attempt_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, stop_reason: ExecutionProcessStopReason | null, started_at: string, completed_at: string | null, 
+/**
+ * 1-based place in the queue while the process is queued
+ */
+queue_position: number | null, 
+/**
+ * URL a dev server reported it's listening on
+ */
+dev_server_url: string | null, dev_server_health: DevServerHealth | null, created_at: string, updated_at: string, };
+
+export type ExecutionProcessStatus = "queued" | "running" | "completed" | "failed" | "killed";
 
-export type ExecutionProcess = { id: string, task_attempt_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+export type ExecutionProcessStopReason = "max_duration" | "max_idle" | "max_tool_calls" | "max_memory" | "max_cpu" | "max_processes";
 
-export type ExecutionProcessStatus = "running" | "completed" | "failed" | "killed";
+export type DevServerHealth = "starting" | "healthy" | "unhealthy";
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver" | "codereview" | "testscript" | "pipelinestep";
+
+export type ExecutionProcessTokenUsage = { execution_process_id: string, model: string | null, input_tokens: bigint, output_tokens: bigint, cache_creation_input_tokens: bigint, cache_read_input_tokens: bigint, created_at: string, updated_at: string, };
+
+export type TokenUsageTotals = { model: string | null, input_tokens: bigint, output_tokens: bigint, cache_creation_input_tokens: bigint, cache_read_input_tokens: bigint, };
+
+export type TokenUsageSummary = { total: TokenUsageTotals, by_model: Array<TokenUsageTotals>, };
 
 export type EventPatch = { op: string, path: string, value: EventPatchInner, };
 
@@ -172,7 +447,11 @@ export type NormalizedConversation = { entries: Array<NormalizedEntry>, session_
 
 export type NormalizedEntry = { timestamp: string | null, entry_type: NormalizedEntryType, content: string, };
 
-export type NormalizedEntryType = { "type": "user_message" } | { "type": "assistant_message" } | { "type": "tool_use", tool_name: string, action_type: ActionType, } | { "type": "system_message" } | { "type": "error_message" } | { "type": "thinking" };
+export type NormalizedEntryType = { "type": "user_message" } | { "type": "assistant_message" } | { "type": "tool_use", tool_name: string, action_type: ActionType, 
+/**
+ * Filled in once the agent reports the outcome of the call
+ */
+result: ToolResult | null, } | { "type": "system_message" } | { "type": "error_message" } | { "type": "thinking" };
 
 export type FileChange = { "action": "write", content: string, } | { "action": "delete" } | { "action": "rename", new_path: string, } | { "action": "edit", 
 /**
@@ -188,6 +467,12 @@ export type ActionType = { "action": "file_read", path: string, } | { "action":
 
 export type TodoItem = { content: string, status: string, priority: string | null, };
 
+export type ToolResult = { success: boolean, exit_code: number | null, 
+/**
+ * Start of the tool's output, truncated to keep the conversation small
+ */
+output: string | null, };
+
 export type PatchType = { "type": "NORMALIZED_ENTRY", "content": NormalizedEntry } | { "type": "STDOUT", "content": string } | { "type": "STDERR", "content": string } | { "type": "DIFF", "content": Diff };
 
 export type JsonValue = number | string | boolean | Array<JsonValue> | { [key in string]?: JsonValue } | null;
\ No newline at end of file